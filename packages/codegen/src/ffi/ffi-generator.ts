@@ -249,9 +249,6 @@ export class CodeGenerator {
         if (this.ctx.usesCall) {
             lines.push(`import { call } from "../../batch.js";`);
         }
-        if (this.ctx.usesNativeError) {
-            lines.push(`import { NativeError } from "../../native/error.js";`);
-        }
         if (this.ctx.usesGetNativeObject) {
             lines.push(`import { getNativeObject } from "../../native/object.js";`);
         }
@@ -9,7 +9,6 @@ export class GenerationContext {
     usesRead = false;
     usesWrite = false;
     usesAlloc = false;
-    usesNativeError = false;
     usesNativeObject = false;
     usesGetNativeObject = false;
     usesRegisterNativeClass = false;
@@ -38,7 +37,6 @@ export class GenerationContext {
         this.usesRead = false;
         this.usesWrite = false;
         this.usesAlloc = false;
-        this.usesNativeError = false;
         this.usesNativeObject = false;
         this.usesGetNativeObject = false;
         this.usesRegisterNativeClass = false;
@@ -147,14 +147,7 @@ export abstract class BaseGenerator {
     }
 
     protected generateErrorArgument(indent = "      "): string {
-        return `${indent}  {\n${indent}    type: { type: "ref", innerType: { type: "boxed", innerType: "GError", lib: "libglib-2.0.so.0" } },\n${indent}    value: error,\n${indent}  }`;
-    }
-
-    protected generateErrorCheck(indent = "  "): string {
-        this.ctx.usesNativeError = true;
-        return `${indent}  if (error.value !== null) {
-${indent}    throw new NativeError(error.value);
-${indent}  }`;
+        return `${indent}  {\n${indent}    type: { type: "error" },\n${indent}    value: null,\n${indent}  }`;
     }
 
     protected identifyGtkAllocatesRefs(parameters: GirParameter[]): {
@@ -296,9 +296,6 @@ ${args ? `${args},` : ""}
 
         const lines: string[] = [];
         lines.push(`${ctorDoc}  static ${methodName}(${params}): ${className} {`);
-        if (ctor.throws) {
-            lines.push(`    const error = { value: null as unknown };`);
-        }
         lines.push(`    const ptr = call(
       "${sharedLibrary}",
       "${ctor.cIdentifier}",
@@ -307,9 +304,6 @@ ${allArgs}
       ],
       { type: "gobject", borrowed: ${borrowed} }
     );`);
-        if (ctor.throws) {
-            lines.push(this.generateErrorCheck());
-        }
         lines.push(`    return getNativeObject(ptr) as ${className};`);
         lines.push(`  }`);
         return `${lines.join("\n")}\n`;
@@ -350,10 +344,6 @@ ${allArgs}
         }
         lines.push(`  static ${funcName}(${params})${tsReturnType} {`);
 
-        if (func.throws) {
-            lines.push(`    const error = { value: null as unknown };`);
-        }
-
         const args = this.generateCallArguments(func.parameters);
         const errorArg = func.throws ? this.generateErrorArgument() : "";
         const allArgs = errorArg ? args + (args ? ",\n" : "") + errorArg : args;
@@ -368,14 +358,11 @@ ${allArgs ? `${allArgs},` : ""}
       ],
       ${this.generateTypeDescriptor(returnTypeMapping.ffi)}
     );`);
-            if (func.throws) {
-                lines.push(this.generateErrorCheck());
-            }
             lines.push(...this.generateRefRewrapCode(gtkAllocatesRefs));
             lines.push(`    return getNativeObject(ptr) as ${className};`);
         } else {
             const hasRefRewrap = gtkAllocatesRefs.length > 0;
-            const needsResultVar = func.throws || hasRefRewrap;
+            const needsResultVar = hasRefRewrap;
             const callPrefix = needsResultVar
                 ? hasReturnValue
                     ? `const ${resultVarName} = `
@@ -393,10 +380,6 @@ ${allArgs ? `${allArgs},` : ""}
       ${this.generateTypeDescriptor(returnTypeMapping.ffi)}
     )${needsCast ? ` as ${rawReturnType}` : ""};`);
 
-            if (func.throws) {
-                lines.push(this.generateErrorCheck());
-            }
-
             lines.push(...this.generateRefRewrapCode(gtkAllocatesRefs));
 
             if (needsResultVar && hasReturnValue) {
@@ -608,13 +591,7 @@ ${allArgs ? `${allArgs},` : ""}
         lines.push(`      const callback = (_source: unknown, asyncResult: unknown) => {`);
         lines.push(`        try {`);
 
-        if (finishMethod.throws) {
-            lines.push(`          const error = { value: null as unknown };`);
-        }
-
-        const errorArg = finishMethod.throws
-            ? `,\n            { type: { type: "ref", innerType: { type: "boxed", innerType: "GError", lib: "libglib-2.0.so.0" } }, value: error }`
-            : "";
+        const errorArg = finishMethod.throws ? `,\n            { type: { type: "error" }, value: null }` : "";
 
         if (hasMainReturn) {
             const varName = needsObjectWrap ? "ptr" : "result";
@@ -640,13 +617,6 @@ ${allArgs ? `${allArgs},` : ""}
             lines.push(`          );`);
         }
 
-        if (finishMethod.throws) {
-            lines.push(`          if (error.value !== null) {`);
-            lines.push(`            throw new NativeError(error.value);`);
-            lines.push(`          }`);
-            this.ctx.usesNativeError = true;
-        }
-
         if (outputParams.length === 0) {
             if (hasMainReturn) {
                 if (needsBoxedWrap || needsInterfaceWrap) {
@@ -797,10 +767,6 @@ ${allArgs ? `${allArgs},` : ""}
         }
         lines.push(`  ${methodName}(${params})${returnTypeAnnotation} {`);
 
-        if (method.throws) {
-            lines.push(`    const error = { value: null as unknown };`);
-        }
-
         const args = this.generateCallArguments(method.parameters);
         const errorArg = method.throws ? this.generateErrorArgument() : "";
         const allArgs = errorArg ? args + (args ? ",\n" : "") + errorArg : args;
@@ -819,9 +785,6 @@ ${allArgs ? `${allArgs},` : ""}
       ],
       ${this.generateTypeDescriptor(returnTypeMapping.ffi)}
     );`);
-            if (method.throws) {
-                lines.push(this.generateErrorCheck());
-            }
             lines.push(...this.generateRefRewrapCode(gtkAllocatesRefs));
             if (isNullable) {
                 lines.push(`    if (ptr === null) return null;`);
@@ -850,14 +813,11 @@ ${allArgs ? `${allArgs},` : ""}
       ],
       ${this.generateTypeDescriptor(returnTypeMapping.ffi)}
     ) as unknown[];`);
-            if (method.throws) {
-                lines.push(this.generateErrorCheck());
-            }
             lines.push(...this.generateRefRewrapCode(gtkAllocatesRefs));
             lines.push(`    return ptrs.map(ptr => getNativeObject(ptr) as ${elementType});`);
         } else {
             const hasRefRewrap = gtkAllocatesRefs.length > 0;
-            const needsResultVar = method.throws || hasRefRewrap;
+            const needsResultVar = hasRefRewrap;
             const callPrefix = needsResultVar
                 ? hasReturnValue
                     ? `const ${resultVarName} = `
@@ -881,9 +841,6 @@ ${allArgs ? `${allArgs},` : ""}
       ${this.generateTypeDescriptor(returnTypeMapping.ffi)}
     )${needsCast ? ` as ${tsReturnType}` : ""};`);
 
-            if (method.throws) {
-                lines.push(this.generateErrorCheck());
-            }
             lines.push(...this.generateRefRewrapCode(gtkAllocatesRefs));
             if (needsResultVar && hasReturnValue) {
                 lines.push(`    return ${resultVarName};`);
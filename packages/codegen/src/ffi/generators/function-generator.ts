@@ -54,10 +54,6 @@ export class FunctionGenerator extends BaseGenerator {
         }
         lines.push(`export const ${funcName} = (${params})${tsReturnType} => {`);
 
-        if (func.throws) {
-            lines.push(`  const error = { value: null as unknown };`);
-        }
-
         const args = this.generateCallArguments(func.parameters, "  ");
         const errorArg = func.throws ? this.generateErrorArgument("  ") : "";
         const allArgs = errorArg ? args + (args ? ",\n" : "") + errorArg : args;
@@ -68,9 +64,6 @@ export class FunctionGenerator extends BaseGenerator {
             lines.push(`  const ptr = call("${sharedLibrary}", "${func.cIdentifier}", [
 ${allArgs ? `${allArgs},` : ""}
   ], ${this.generateTypeDescriptor(returnTypeMapping.ffi)});`);
-            if (func.throws) {
-                lines.push(this.generateErrorCheck(""));
-            }
             lines.push(...refRewrapCode);
             if (isNullable) {
                 lines.push(`  if (ptr === null) return null;`);
@@ -84,7 +77,7 @@ ${allArgs ? `${allArgs},` : ""}
             }
         } else {
             const hasRefRewrap = gtkAllocatesRefs.length > 0;
-            const needsResultVar = func.throws || hasRefRewrap;
+            const needsResultVar = hasRefRewrap;
             const callPrefix = needsResultVar
                 ? hasReturnValue
                     ? `const ${resultVarName} = `
@@ -99,9 +92,6 @@ ${allArgs ? `${allArgs},` : ""}
 ${allArgs ? `${allArgs},` : ""}
   ], ${this.generateTypeDescriptor(returnTypeMapping.ffi)})${needsCast ? ` as ${returnTypeMapping.ts}` : ""};`);
 
-            if (func.throws) {
-                lines.push(this.generateErrorCheck(""));
-            }
             lines.push(...refRewrapCode);
             if (needsResultVar && hasReturnValue) {
                 lines.push(`  return ${resultVarName};`);
@@ -1,7 +1,20 @@
 import { type Arg, batchCall, type CallDescriptor, call as nativeCall, type Type } from "@gtkx/native";
+import { isGError, NativeError } from "./native/error.js";
 
 const batchStack: CallDescriptor[][] = [];
 
+/**
+ * Rethrows an error from a native call, wrapping a reported GError in a {@link NativeError}.
+ * @param error - The caught error
+ */
+const rethrowNativeError = (error: unknown): never => {
+    if (isGError(error) && !(error instanceof NativeError)) {
+        throw new NativeError(error);
+    }
+
+    throw error;
+};
+
 /**
  * Begins batching mode for FFI calls.
  * Batching is nestable - each beginBatch pushes a new queue onto the stack.
@@ -23,13 +36,18 @@ export const isBatching = (): boolean => {
  * Ends the current batch level and executes its queued FFI calls.
  * If nested inside another batch, the calls are executed immediately
  * rather than being merged into the parent batch.
+ * @throws {NativeError} If a queued call reports a GError, after the calls before it have run
  */
 export const endBatch = (): void => {
     const queue = batchStack.pop();
     if (!queue) return;
 
     if (queue.length > 0) {
-        batchCall(queue);
+        try {
+            batchCall(queue);
+        } catch (error) {
+            rethrowNativeError(error);
+        }
     }
 };
 
@@ -42,15 +60,21 @@ export const endBatch = (): void => {
  * @param args - Array of argument descriptors with types and values
  * @param returnType - Type descriptor for the return value
  * @returns The return value from the native function
+ * @throws {NativeError} If the function reports a GError through an `{ type: "error" }` argument
  */
 export const call = (library: string, symbol: string, args: Arg[], returnType: Type): unknown => {
     const currentQueue = batchStack[batchStack.length - 1];
 
     if (currentQueue && returnType.type === "undefined") {
         currentQueue.push({ library, symbol, args });
+        return undefined;
     }
 
-    return nativeCall(library, symbol, args, returnType);
+    try {
+        return nativeCall(library, symbol, args, returnType);
+    } catch (error) {
+        return rethrowNativeError(error);
+    }
 };
 
 /**
@@ -1,30 +1,32 @@
-import { read } from "@gtkx/native";
+/** The fields of a GLib GError, as reported by a failed native call. */
+type GErrorInfo = { message: string; domain: string; quark: number; code: number };
 
 /**
  * Error class that wraps a GLib GError.
  * Extends the native JavaScript Error with GError-specific properties.
+ * The GError is freed natively before it is thrown, so there is no GError object id to read from.
  */
 export class NativeError extends Error {
-    /** The GError object id */
-    readonly id: unknown;
-
     /** The GLib error domain (GQuark) */
     readonly domain: number;
 
+    /** The GLib error domain, as the quark string (e.g. "g-file-error-quark") */
+    readonly domainName: string;
+
     /** The error code within the domain */
     readonly code: number;
 
     /**
-     * Creates a NativeError from a GError object id.
-     * @param id - The GError object id from a failed FFI call
+     * Creates a NativeError from a GError reported by a native call.
+     * The GError itself has already been freed natively.
+     * @param error - The error thrown by a call with a `{ type: "error" }` argument
      */
-    constructor(id: unknown) {
-        const message = read(id, { type: "string" }, 8) as string;
-        super(message ?? "Unknown error");
+    constructor(error: GErrorInfo) {
+        super(error.message || "Unknown error");
 
-        this.id = id;
-        this.domain = read(id, { type: "int", size: 32, unsigned: true }, 0) as number;
-        this.code = read(id, { type: "int", size: 32, unsigned: false }, 4) as number;
+        this.domain = error.quark;
+        this.domainName = error.domain;
+        this.code = error.code;
 
         this.name = "NativeError";
 
@@ -33,3 +35,11 @@ export class NativeError extends Error {
         }
     }
 }
+
+/**
+ * Checks whether an error was thrown by a native call reporting a GError.
+ * @param error - The caught error
+ * @returns true if the error carries GError fields
+ */
+export const isGError = (error: unknown): error is Error & GErrorInfo =>
+    error instanceof Error && error.name === "NativeError" && "domain" in error && "quark" in error && "code" in error;
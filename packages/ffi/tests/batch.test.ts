@@ -1,6 +1,6 @@
 import { describe, expect, it } from "vitest";
 import * as Gtk from "../src/generated/gtk/index.js";
-import { beginBatch, call, endBatch, isBatching } from "../src/index.js";
+import { beginBatch, call, endBatch, isBatching, NativeError } from "../src/index.js";
 
 describe("beginBatch", () => {
     it("enables batching mode", () => {
@@ -36,6 +36,58 @@ describe("endBatch", () => {
         expect(label.getText()).toBe("After");
     });
 
+    it("throws a NativeError for a GError reported mid-batch", () => {
+        const label = new Gtk.Label("Before");
+        const setText = (text: string) =>
+            call(
+                "libgtk-4.so.1",
+                "gtk_label_set_text",
+                [
+                    { type: { type: "gobject" }, value: label.id },
+                    { type: { type: "string" }, value: text },
+                ],
+                { type: "undefined" },
+            );
+        const domain = call(
+            "libglib-2.0.so.0",
+            "g_quark_from_string",
+            [{ type: { type: "string" }, value: "gtkx-batch-error-quark" }],
+            { type: "int", size: 32, unsigned: true },
+        );
+
+        beginBatch();
+        setText("After");
+        call(
+            "libglib-2.0.so.0",
+            "g_set_error_literal",
+            [
+                { type: { type: "error" }, value: null },
+                { type: { type: "int", size: 32, unsigned: true }, value: domain },
+                { type: { type: "int", size: 32 }, value: 3 },
+                { type: { type: "string" }, value: "Batch failed" },
+            ],
+            { type: "undefined" },
+        );
+        setText("Skipped");
+
+        let thrown: unknown;
+        try {
+            endBatch();
+        } catch (error) {
+            thrown = error;
+        }
+
+        expect(thrown).toBeInstanceOf(NativeError);
+        expect(thrown).toMatchObject({
+            domain,
+            domainName: "gtkx-batch-error-quark",
+            code: 3,
+            message: "Batch failed",
+        });
+        expect(label.getText()).toBe("After");
+        expect(isBatching()).toBe(false);
+    });
+
     it("does nothing when not batching", () => {
         expect(() => endBatch()).not.toThrow();
     });
@@ -1,5 +1,5 @@
 import { describe, expect, it } from "vitest";
-import { NativeError } from "../src/index.js";
+import { isGError, NativeError } from "../src/index.js";
 
 describe("NativeError", () => {
     it("extends Error", () => {
@@ -11,6 +11,28 @@ describe("NativeError", () => {
         expect(NativeError.name === "NativeError").toBe(true);
     });
 
+    it("copies GError fields", () => {
+        const error = new NativeError({ message: "No such file", domain: "g-file-error-quark", quark: 42, code: 4 });
+
+        expect(error.message).toBe("No such file");
+        expect(error.domain).toBe(42);
+        expect(error.domainName).toBe("g-file-error-quark");
+        expect(error.code).toBe(4);
+        expect(error.name).toBe("NativeError");
+    });
+
+    it("recognizes errors thrown by native calls", () => {
+        const thrown = Object.assign(new Error("No such file"), {
+            name: "NativeError",
+            domain: "g-file-error-quark",
+            quark: 42,
+            code: 4,
+        });
+
+        expect(isGError(thrown)).toBe(true);
+        expect(isGError(new Error("other"))).toBe(false);
+    });
+
     describe("edge cases", () => {
         it("exposes domain property", () => {
             expect("domain" in NativeError.prototype || true).toBe(true);
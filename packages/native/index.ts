@@ -1,5 +1,5 @@
 import { createRequire } from "node:module";
-import type { Arg, Ref, Type } from "./types.js";
+import type { Arg, ArgType, Ref, Type } from "./types.js";
 
 const require = createRequire(import.meta.url);
 const native = require("./index.node");
@@ -13,43 +13,223 @@ export function createRef<T>(value: T): Ref<T> {
     return { value };
 }
 
+/**
+ * Options for a single FFI call.
+ */
+export type CallOptions = {
+    /**
+     * The thread the function runs on. Defaults to "gtk". With "worker", a thread-safe function such as
+     * `g_file_load_contents` runs on a worker thread pool so that it does not block rendering. Objects
+     * passed to it must be of thread-safe types, such as `GFile`, `GBytes` or `GRegex`, and callbacks
+     * cannot be passed.
+     */
+    thread?: "gtk" | "worker";
+    /**
+     * The priority the GTK thread runs the call at relative to its other work. Defaults to "idle",
+     * which runs after input handling and redrawing. "high" suits calls that a user interaction
+     * waits on, and "default" runs alongside input handling.
+     */
+    priority?: "high" | "default" | "idle";
+};
+
+/**
+ * Describes how `callAsyncOp` calls the finish function of an operation.
+ */
+export type AsyncOpFinish = {
+    /**
+     * Whether the source object is passed before the GAsyncResult. Defaults to true, as for methods such as
+     * `g_file_load_contents_finish`. Constructors such as `g_dbus_proxy_new_finish` take the result first.
+     */
+    source?: boolean;
+    /**
+     * Arguments passed after the GAsyncResult and before the GError, such as refs for out-parameters. Argument
+     * indices such as `lengthArg` count from the first argument of the finish function.
+     */
+    args?: Arg[];
+};
+
+/**
+ * Options for a batch of FFI calls, which always run on the GTK thread.
+ */
+export type BatchCallOptions = Pick<CallOptions, "priority">;
+
 /**
  * Calls a native GTK function via FFI.
  * @param library - The shared library name (e.g., "libgtk-4.so.1")
  * @param symbol - The C function symbol name to call
  * @param args - Array of argument descriptors with types and values
  * @param returnType - Type descriptor for the return value
+ * @param options - Optional call options
+ * @returns The return value from the native function
+ */
+export function call(library: string, symbol: string, args: Arg[], returnType: Type, options?: CallOptions): unknown {
+    return native.call(library, symbol, args, returnType, options);
+}
+
+/**
+ * Calls a native GTK function via FFI without blocking the JavaScript thread.
+ * Timers and I/O keep running while the call executes on the GTK thread.
+ * @param library - The shared library name (e.g., "libgtk-4.so.1")
+ * @param symbol - The C function symbol name to call
+ * @param args - Array of argument descriptors with types and values
+ * @param returnType - Type descriptor for the return value
+ * @param options - Optional call options
+ * @returns A promise for the return value, settled after refs are updated
+ */
+export function callAsync(
+    library: string,
+    symbol: string,
+    args: Arg[],
+    returnType: Type,
+    options?: CallOptions,
+): Promise<unknown> {
+    return native.callAsync(library, symbol, args, returnType, options);
+}
+
+/**
+ * Runs an asynchronous GIO operation and finishes it on the GTK thread.
+ * The start function is called with the given arguments followed by the
+ * async ready callback and its user data. On completion, the finish function
+ * is called as described by `finish`: with the source object unless disabled,
+ * the GAsyncResult, the extra finish arguments and a GError.
+ * @param library - The shared library name (e.g., "libgio-2.0.so.0")
+ * @param startSymbol - The C function symbol starting the operation (e.g., "g_file_load_contents_async")
+ * @param finishSymbol - The C function symbol finishing the operation (e.g., "g_file_load_contents_finish")
+ * @param args - Argument descriptors for the start function, without the callback and user data
+ * @param resultType - Type descriptor for the return value of the finish function
+ * @param finish - Optional description of the finish function's arguments
+ * @returns A promise for the finish function's return value, settled after refs are updated and rejected with
+ * the GError on failure
+ */
+export function callAsyncOp(
+    library: string,
+    startSymbol: string,
+    finishSymbol: string,
+    args: Arg[],
+    resultType: Type,
+    finish?: AsyncOpFinish,
+): Promise<unknown> {
+    return native.callAsyncOp(library, startSymbol, finishSymbol, args, resultType, finish);
+}
+
+/**
+ * Calls a function resolved at runtime from a GObject-Introspection typelib.
+ * Argument and return types, ownership transfer and nullability are read from
+ * the typelib, so arguments are passed as plain values: the instance first for
+ * methods, and refs for out-parameters. Throwing functions report their GError
+ * as a thrown error. Resolved functions are cached per namespace, version and
+ * symbol. Requires `libgirepository-1.0`; `libgirepository-2.0` is not supported.
+ * @param namespace - The typelib namespace (e.g., "Gtk")
+ * @param version - The namespace version (e.g., "4.0")
+ * @param symbol - The function name, or `Type.method` for methods and constructors (e.g., "Label.set_text")
+ * @param args - The argument values
+ * @returns The return value from the native function
+ */
+export function callIntrospected(namespace: string, version: string, symbol: string, args: unknown[]): unknown {
+    return native.callIntrospected(namespace, version, symbol, args);
+}
+
+/**
+ * Defines a native function for repeated calls.
+ * The type descriptors are parsed and the symbol resolved once, so calling the
+ * returned handle with `invoke` skips that work on every call.
+ * @param library - The shared library name (e.g., "libgtk-4.so.1")
+ * @param symbol - The C function symbol name
+ * @param argTypes - Type descriptors for the arguments
+ * @param returnType - Type descriptor for the return value
+ * @returns An opaque handle to pass to `invoke`
+ * @throws If the library or symbol cannot be found
+ */
+export function define(library: string, symbol: string, argTypes: ArgType[], returnType: Type): unknown {
+    return native.define(library, symbol, argTypes, returnType);
+}
+
+/**
+ * Calls a function defined with `define`.
+ * Behaves like `call` with the defined types.
+ * @param handle - The handle returned by `define`
+ * @param values - One value per argument
  * @returns The return value from the native function
  */
-export function call(library: string, symbol: string, args: Arg[], returnType: Type): unknown {
-    return native.call(library, symbol, args, returnType);
+export function invoke(handle: unknown, values: unknown[]): unknown {
+    return native.invoke(handle, values);
 }
 
 /**
  * Descriptor for a batched FFI call.
+ * An argument whose value is a `ResultOf` receives the result of an earlier call in the same batch.
  */
 export type CallDescriptor = {
     library: string;
     symbol: string;
     args: Arg[];
+    returnType?: Type;
 };
 
 /**
- * Executes multiple void FFI calls in a single native dispatch.
+ * Placeholder argument value referencing the result of the call at index `resultOf` in the same batch.
+ */
+export type ResultOf = { resultOf: number };
+
+/**
+ * Executes multiple FFI calls in a single native dispatch.
+ * @param calls - Array of call descriptors to execute
+ * @param options - Options for the batch, such as its priority
+ * @returns The result of each call, in order; `undefined` for calls without a return type
+ */
+export function batchCall(calls: CallDescriptor[], options?: BatchCallOptions): unknown[] {
+    return native.batchCall(calls, options);
+}
+
+/**
+ * Executes multiple FFI calls in a single native dispatch without blocking the JavaScript thread.
+ * @param calls - Array of call descriptors to execute
+ * @param options - Options for the batch, such as its priority
+ * @returns A promise for the result of each call, in order
+ */
+export function batchCallAsync(calls: CallDescriptor[], options?: BatchCallOptions): Promise<unknown[]> {
+    return native.batchCallAsync(calls, options);
+}
+
+/**
+ * Executes multiple FFI calls in the next frame of the application's windows.
+ *
+ * The calls are held until the frame clock's `update` phase and run there along with the other commits
+ * requested for the same frame, so that all their changes are painted together. Without a mapped, unminimized
+ * window, the calls run immediately, and they run without a frame if none comes within 100 ms.
  * @param calls - Array of call descriptors to execute
+ * @param onPresented - Called with the frame time in microseconds once the frame has been painted
+ * @returns A promise for the result of each call, in order, once they have run
  */
-export function batchCall(calls: CallDescriptor[]): void {
-    native.batchCall(calls);
+export function requestFrameCommit(
+    calls: CallDescriptor[],
+    onPresented?: (frameTime: number) => void,
+): Promise<unknown[]> {
+    return native.requestFrameCommit(calls, onPresented);
 }
 
+/**
+ * Options for starting the GTK application.
+ */
+export type StartOptions = {
+    /**
+     * Runs GTK on the JavaScript thread, with the GLib main context driven from the Node.js event loop.
+     * FFI calls and the callbacks they fire become direct calls without thread hops. Callbacks fired
+     * outside of a native call, such as while a finalizer releases an object, are deferred and their
+     * return values are ignored.
+     */
+    mainThread?: boolean;
+};
+
 /**
  * Starts the GTK application main loop.
  * @param appId - The application ID (e.g., "com.example.myapp")
  * @param flags - Optional GIO application flags
+ * @param options - Optional start options
  * @returns The GTK Application instance id
  */
-export function start(appId: string, flags?: number): unknown {
-    return native.start(appId, flags);
+export function start(appId: string, flags?: number, options?: StartOptions): unknown {
+    return native.start(appId, flags, options);
 }
 
 /**
@@ -113,4 +293,45 @@ export function poll(): void {
     native.poll();
 }
 
-export type { Ref, Arg, Type };
+/**
+ * Runtime configuration options.
+ */
+export type Config = {
+    /**
+     * Milliseconds a call or callback may wait for the other thread before the wait is reported
+     * to `onWaitTimeout`, along with what both threads are doing. 0 disables the watchdog, which is the default.
+     */
+    waitTimeout?: number;
+    /**
+     * Abandons waits that exceed `waitTimeout`: the call throws a timeout error, or the callback
+     * result is ignored. Defaults to false.
+     */
+    throwOnTimeout?: boolean;
+    /**
+     * Receives the report of each wait that exceeds `waitTimeout`, once the JavaScript thread is free to run it.
+     * Reports are dropped while it is not set, or after it is set to null.
+     */
+    onWaitTimeout?: ((report: string) => void) | null;
+    /**
+     * Maximum number of synchronous calls nested through callbacks before a `RangeError` is thrown.
+     * 0 removes the limit. Defaults to 64.
+     */
+    maxReentrancyDepth?: number;
+    /**
+     * Milliseconds the GTK thread may spend running queued calls before yielding to the main loop,
+     * so that input and drawing are not starved by a burst of calls. The remaining calls run in a
+     * later iteration at idle priority, after other pending sources, unless calls of the same priority made
+     * meanwhile pick them up first. 0 removes the budget, which is the default.
+     */
+    dispatchBudget?: number;
+};
+
+/**
+ * Updates the runtime configuration. Options that are not given keep their current value.
+ * @param config - The options to change
+ */
+export function configure(config: Config): void {
+    native.configure(config);
+}
+
+export type { Ref, Arg, ArgType, Type };
@@ -63,7 +63,7 @@ impl Arg {
         let type_prop: Handle<'_, JsValue> = obj.prop(cx, "type").get()?;
         let value_prop: Handle<'_, JsValue> = obj.prop(cx, "value").get()?;
         let type_ = Type::from_js_value(cx, type_prop)?;
-        let value = Value::from_js_value(cx, value_prop)?;
+        let value = Value::from_js_value_with_type(cx, value_prop, &type_)?;
 
         let optional_prop: Option<Handle<JsBoolean>> = obj.get_opt(cx, "optional")?;
         let optional = optional_prop.map(|h| h.value(cx)).unwrap_or(false);
@@ -67,6 +67,11 @@ impl Boxed {
             }
         }
     }
+
+    /// Returns the GType of the boxed value, if known.
+    pub fn type_(&self) -> Option<glib::Type> {
+        self.type_
+    }
 }
 
 impl AsRef<*mut c_void> for Boxed {
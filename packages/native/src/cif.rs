@@ -11,17 +11,65 @@ use std::{
 };
 
 use anyhow::bail;
-use gtk4::glib::{self, translate::IntoGlib as _};
+use gtk4::glib::{
+    self,
+    prelude::{ObjectType as _, StaticType as _},
+    translate::{IntoGlib as _, IntoGlibPtr as _},
+};
 use libffi::middle as libffi;
 use neon::prelude::*;
 
 use crate::{
     arg::{self, Arg},
-    callback, gtk_dispatch, js_dispatch,
+    callback, gtk_dispatch, js_dispatch, notify,
     types::*,
-    value,
+    value, variant, watchdog,
 };
 
+/// The libffi signature of a call, used as the key for prepared CIFs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Signature {
+    args: Vec<FfiTypeKey>,
+    result: FfiTypeKey,
+}
+
+impl Signature {
+    /// Builds the signature of a call from its argument and return types.
+    ///
+    /// Callbacks with a trampoline take the trampoline and its user data,
+    /// plus a destroy notify for draw functions.
+    pub fn new<'a>(arg_types: impl IntoIterator<Item = &'a Type>, result_type: &Type) -> Self {
+        let mut args = Vec::new();
+
+        for type_ in arg_types {
+            match type_ {
+                Type::Callback(cb) if cb.trampoline != CallbackTrampoline::Closure => {
+                    args.push(FfiTypeKey::Pointer);
+                    args.push(FfiTypeKey::Pointer);
+
+                    if cb.trampoline == CallbackTrampoline::DrawFunc {
+                        args.push(FfiTypeKey::Pointer);
+                    }
+                }
+                _ => args.push(type_.into()),
+            }
+        }
+
+        Signature {
+            args,
+            result: result_type.into(),
+        }
+    }
+
+    /// Prepares a CIF for this signature.
+    pub fn to_cif(&self) -> libffi::Cif {
+        libffi::Builder::new()
+            .res((&self.result).into())
+            .args(self.args.iter().map(libffi::Type::from))
+            .into_cif()
+    }
+}
+
 /// A pointer that owns its referenced data.
 ///
 /// This struct ensures that heap-allocated data passed to FFI calls
@@ -67,6 +115,8 @@ pub enum Value {
     Ptr(*mut c_void),
     /// Pointer with owned data that must outlive the FFI call.
     OwnedPtr(OwnedPtr),
+    /// Struct passed by value; `ptr` points to the struct's memory.
+    Struct(OwnedPtr),
     /// Callback with trampoline function for GTK signals.
     TrampolineCallback(TrampolineCallbackValue),
     /// Void (no value).
@@ -124,13 +174,20 @@ fn wait_for_js_result<T, F>(
 where
     F: FnOnce(Result<value::Value, ()>) -> T,
 {
+    let _activity = watchdog::GTK_THREAD.enter(|| "waiting for a JS callback".to_string());
+    let mut wait = watchdog::Wait::new(&watchdog::GTK_THREAD);
+
     loop {
+        let token = notify::GTK_THREAD.token();
         gtk_dispatch::dispatch_pending();
 
         match rx.try_recv() {
             Ok(result) => return on_result(result),
             Err(std::sync::mpsc::TryRecvError::Empty) => {
-                std::thread::yield_now();
+                // An abandoned callback reports no result, as if it had failed.
+                if wait.sleep(&notify::GTK_THREAD, token).is_err() {
+                    return on_result(Err(()));
+                }
             }
             Err(std::sync::mpsc::TryRecvError::Disconnected) => {
                 return on_result(Err(()));
@@ -149,6 +206,10 @@ fn invoke_and_wait_for_js_result<T, F>(
 where
     F: FnOnce(Result<value::Value, ()>) -> T,
 {
+    if gtk_dispatch::is_main_thread() {
+        return invoke_on_main_thread(channel, callback, args_values, capture_result, on_result);
+    }
+
     let rx = if gtk_dispatch::is_js_waiting() {
         js_dispatch::queue(callback.clone(), args_values, capture_result)
     } else {
@@ -158,6 +219,63 @@ where
     wait_for_js_result(rx, on_result)
 }
 
+/// Queues a callback that returns nothing on the JS thread without waiting
+/// for it to run, so that the GTK thread can continue with the emission.
+///
+/// Callbacks are queued in order with those that are waited for. With a
+/// coalesce policy, an emission merged into one that is still queued needs
+/// no wake-up of its own.
+fn invoke_without_waiting(
+    channel: &Channel,
+    callback: &Arc<Root<JsFunction>>,
+    args_values: Vec<value::Value>,
+    coalesce: Option<CoalescePolicy>,
+) {
+    let queued = match coalesce {
+        Some(policy) => js_dispatch::queue_coalesced(callback.clone(), args_values, policy),
+        None => {
+            js_dispatch::queue(callback.clone(), args_values, false);
+            true
+        }
+    };
+
+    if !queued {
+        return;
+    }
+
+    if gtk_dispatch::is_main_thread() {
+        if !js_dispatch::process_pending_in_scope() {
+            js_dispatch::wake(channel);
+        }
+    } else if !gtk_dispatch::is_js_waiting() {
+        js_dispatch::wake(channel);
+    }
+}
+
+/// Invokes a callback fired on the JS thread in main thread mode.
+///
+/// Callbacks fired while native code runs inside a JS context are invoked
+/// directly. Others, such as those fired while a finalizer releases an
+/// object, are deferred to the channel and report no result.
+fn invoke_on_main_thread<T, F>(
+    channel: &Channel,
+    callback: &Arc<Root<JsFunction>>,
+    args_values: Vec<value::Value>,
+    capture_result: bool,
+    on_result: F,
+) -> T
+where
+    F: FnOnce(Result<value::Value, ()>) -> T,
+{
+    let rx = js_dispatch::queue(callback.clone(), args_values, capture_result);
+
+    if !js_dispatch::process_pending_in_scope() {
+        js_dispatch::wake(channel);
+    }
+
+    on_result(rx.try_recv().unwrap_or(Err(())))
+}
+
 /// Transfers ownership of a closure to C, returning a raw pointer.
 ///
 /// This adds a reference to the closure (and sinks any floating reference),
@@ -183,6 +301,19 @@ fn closure_ptr_for_transfer(closure: glib::Closure) -> *mut c_void {
     ptr
 }
 
+/// Passes a closure as a `GAsyncReadyCallback`.
+///
+/// The trampoline invokes the closure once with the source object and the
+/// `GAsyncResult`, then releases it.
+fn async_ready_callback(closure: glib::Closure) -> Value {
+    Value::TrampolineCallback(TrampolineCallbackValue {
+        trampoline_ptr: callback::get_async_ready_trampoline_ptr(),
+        closure: OwnedPtr::new((), closure_ptr_for_transfer(closure)),
+        destroy_ptr: None,
+        data_first: false,
+    })
+}
+
 fn convert_glib_args(
     args: &[glib::Value],
     arg_types: &Option<Vec<Type>>,
@@ -219,6 +350,12 @@ fn type_to_glib_type(type_: &Type) -> glib::Type {
         },
         Type::Boolean => glib::types::Type::BOOL,
         Type::String(_) => glib::types::Type::STRING,
+        Type::Variant(_) => glib::types::Type::VARIANT,
+        Type::Enum(enum_type) => enum_type.get_gtype().unwrap_or(glib::types::Type::I32),
+        Type::Flags(flags_type) => flags_type.get_gtype().unwrap_or(glib::types::Type::U32),
+        Type::Bytes(bytes_type) if bytes_type.kind == BytesKind::GBytes => {
+            glib::Bytes::static_type()
+        }
         _ => glib::types::Type::POINTER,
     }
 }
@@ -230,20 +367,22 @@ fn arg_types_to_glib_types(arg_types: &Option<Vec<Type>>) -> Vec<glib::Type> {
         .unwrap_or_default()
 }
 
-impl TryFrom<arg::Arg> for Value {
+impl TryFrom<&arg::Arg> for Value {
     type Error = anyhow::Error;
 
-    fn try_from(arg: arg::Arg) -> anyhow::Result<Value> {
+    fn try_from(arg: &arg::Arg) -> anyhow::Result<Value> {
         match &arg.type_ {
-            Type::Integer(type_) => {
-                let number = match arg.value {
-                    value::Value::Number(n) => n,
-                    value::Value::Null | value::Value::Undefined if arg.optional => 0.0,
-                    _ => bail!("Expected a Number for integer type, got {:?}", arg.value),
-                };
-
-                dispatch_integer_to_cif!(type_, number)
-            }
+            Type::Integer(type_) => match arg.value {
+                value::Value::Number(n) => dispatch_integer_to_cif!(type_, n),
+                value::Value::BigInt(n) => {
+                    type_.check_range(n)?;
+                    dispatch_integer_to_cif!(type_, n)
+                }
+                value::Value::Null | value::Value::Undefined if arg.optional => {
+                    dispatch_integer_to_cif!(type_, 0)
+                }
+                _ => bail!("Expected a Number for integer type, got {:?}", arg.value),
+            },
             Type::Float(type_) => {
                 let number = match arg.value {
                     value::Value::Number(n) => n,
@@ -318,9 +457,45 @@ impl TryFrom<arg::Arg> for Value {
 
                 Ok(Value::Ptr(ptr))
             }
-            Type::Array(type_) => Value::try_from_array(&arg, type_),
-            Type::Callback(type_) => Value::try_from_callback(&arg, type_),
-            Type::Ref(type_) => Value::try_from_ref(&arg, type_),
+            Type::Array(type_) => Value::try_from_array(arg, type_),
+            Type::HashTable(type_) => Value::try_from_hash_table(arg, type_),
+            Type::Callback(type_) => Value::try_from_callback(arg, type_),
+            Type::Ref(type_) => Value::try_from_ref(arg, type_),
+            Type::Struct(type_) => Value::try_from_struct(arg, type_),
+            Type::Variant(type_) => Value::try_from_variant(arg, type_),
+            Type::Error => {
+                // The callee writes a GError* into this slot when it fails.
+                let error_storage: Box<*mut glib::ffi::GError> = Box::new(std::ptr::null_mut());
+                let ptr = error_storage.as_ref() as *const *mut glib::ffi::GError as *mut c_void;
+
+                Ok(Value::OwnedPtr(OwnedPtr {
+                    ptr,
+                    value: error_storage,
+                }))
+            }
+            Type::Enum(type_) => match arg.value {
+                value::Value::Null | value::Value::Undefined if arg.optional => Ok(Value::I32(0)),
+                _ => Ok(Value::I32(type_.to_raw(&arg.value)?)),
+            },
+            Type::Flags(type_) => match arg.value {
+                value::Value::Null | value::Value::Undefined if arg.optional => Ok(Value::U32(0)),
+                _ => Ok(Value::U32(type_.to_raw(&arg.value)?)),
+            },
+            Type::Bytes(type_) => Value::try_from_bytes(arg, type_),
+            Type::Cancellable => match &arg.value {
+                value::Value::AbortSignal(signal) => {
+                    let cancellable = signal.create_cancellable();
+                    let ptr = cancellable.as_ptr() as *mut c_void;
+                    Ok(Value::OwnedPtr(OwnedPtr::new(cancellable, ptr)))
+                }
+                value::Value::Null | value::Value::Undefined => {
+                    Ok(Value::Ptr(std::ptr::null_mut()))
+                }
+                _ => bail!(
+                    "Expected an AbortSignal for cancellable type, got {:?}",
+                    arg.value
+                ),
+            },
         }
     }
 }
@@ -346,6 +521,7 @@ impl Value {
             Value::F64(value) => value as *const f64 as *mut c_void,
             Value::Ptr(ptr) => ptr as *const *mut c_void as *mut c_void,
             Value::OwnedPtr(owned_ptr) => owned_ptr as *const OwnedPtr as *mut c_void,
+            Value::Struct(owned_ptr) => owned_ptr.ptr,
             Value::TrampolineCallback(_) => {
                 unreachable!(
                     "TrampolineCallback should not be converted to a single pointer - it requires special handling in call.rs"
@@ -358,9 +534,19 @@ impl Value {
     fn try_from_array(arg: &arg::Arg, type_: &ArrayType) -> anyhow::Result<Value> {
         let array = match &arg.value {
             value::Value::Array(arr) => arr,
+            value::Value::Null | value::Value::Undefined => {
+                return Ok(Value::Ptr(std::ptr::null_mut()));
+            }
             _ => bail!("Expected an Array for array type, got {:?}", arg.value),
         };
 
+        if matches!(
+            type_.list_type,
+            ListType::PtrArray | ListType::GArray | ListType::ByteArray
+        ) {
+            return Value::try_from_garray(array, type_);
+        }
+
         match *type_.item_type {
             Type::Integer(type_) => {
                 let mut values = Vec::new();
@@ -486,9 +672,200 @@ impl Value {
         }
     }
 
+    fn try_from_garray(array: &[value::Value], type_: &ArrayType) -> anyhow::Result<Value> {
+        let len = array.len() as u32;
+        let item_type = &*type_.item_type;
+
+        let ptr = match type_.list_type {
+            ListType::PtrArray => unsafe {
+                glib::ffi::g_ptr_array_new_full(len, pointer_item_destroy_func(item_type))
+                    as *mut c_void
+            },
+            ListType::GArray => {
+                let (size, _) = type_layout(item_type).ok_or_else(|| {
+                    anyhow::anyhow!("Unsupported GArray item type: {:?}", item_type)
+                })?;
+
+                unsafe {
+                    let garray = glib::ffi::g_array_sized_new(0, 1, size as u32, len);
+                    glib::ffi::g_array_set_size(garray, len);
+                    garray as *mut c_void
+                }
+            }
+            ListType::ByteArray => unsafe { glib::ffi::g_byte_array_sized_new(len) as *mut c_void },
+            _ => bail!("Expected a GLib array list type, got {:?}", type_.list_type),
+        };
+
+        let guard = value::ArrayGuard::new(ptr, type_.list_type.clone(), true);
+        let mut strings = Vec::new();
+
+        match type_.list_type {
+            ListType::PtrArray => {
+                for item in array {
+                    let item_ptr = pointer_item_to_ptr(item, item_type)?;
+                    unsafe { glib::ffi::g_ptr_array_add(ptr as *mut _, item_ptr) };
+                }
+            }
+            ListType::GArray => {
+                let size = unsafe { glib::ffi::g_array_get_element_size(ptr as *mut _) } as usize;
+                let data = unsafe { (*(ptr as *mut glib::ffi::GArray)).data } as *mut u8;
+
+                for (i, item) in array.iter().enumerate() {
+                    let item_ptr = unsafe { data.add(i * size) };
+
+                    if !write_inline_value(item_ptr, item_type, item, &mut strings)? {
+                        bail!(
+                            "Expected a value of type {:?} for GArray item, got {:?}",
+                            item_type,
+                            item
+                        );
+                    }
+                }
+            }
+            _ => {
+                let bytes = array
+                    .iter()
+                    .map(|item| match item {
+                        value::Value::Number(n) => Ok(*n as u8),
+                        _ => bail!("Expected a Number for GByteArray item, got {:?}", item),
+                    })
+                    .collect::<anyhow::Result<Vec<u8>>>()?;
+
+                unsafe { glib::ffi::g_byte_array_append(ptr as *mut _, bytes.as_ptr(), len) };
+            }
+        }
+
+        // Transfer-none arrays are released after the call; transfer-full arrays
+        // belong to the callee once the call is made.
+        if type_.is_borrowed {
+            Ok(Value::OwnedPtr(OwnedPtr::new((guard, strings), ptr)))
+        } else if strings.is_empty() {
+            std::mem::forget(guard);
+            Ok(Value::Ptr(ptr))
+        } else {
+            bail!("GArray string items cannot be transferred to the callee")
+        }
+    }
+
+    fn try_from_hash_table(arg: &arg::Arg, type_: &HashTableType) -> anyhow::Result<Value> {
+        let entries = match &arg.value {
+            value::Value::HashTable(entries) => entries,
+            value::Value::Null | value::Value::Undefined => {
+                return Ok(Value::Ptr(std::ptr::null_mut()));
+            }
+            _ => bail!(
+                "Expected a Map or Object for hash table type, got {:?}",
+                arg.value
+            ),
+        };
+
+        let (hash_func, equal_func): (glib::ffi::GHashFunc, glib::ffi::GEqualFunc) =
+            match *type_.key_type {
+                Type::String(_) => (Some(glib::ffi::g_str_hash), Some(glib::ffi::g_str_equal)),
+                _ => (
+                    Some(glib::ffi::g_direct_hash),
+                    Some(glib::ffi::g_direct_equal),
+                ),
+            };
+
+        let table = unsafe {
+            glib::ffi::g_hash_table_new_full(
+                hash_func,
+                equal_func,
+                pointer_item_destroy_func(&type_.key_type),
+                pointer_item_destroy_func(&type_.value_type),
+            )
+        } as *mut c_void;
+
+        let guard = value::HashTableGuard::new(table, true);
+
+        for (key, value) in entries {
+            let mut key_ptr = pointer_item_to_ptr(key, &type_.key_type)?;
+            let mut value_ptr = pointer_item_to_ptr(value, &type_.value_type)?;
+
+            // Boxed items are still owned by their JavaScript wrappers, so a
+            // table handed over to the callee gets its own copies.
+            if !type_.is_borrowed {
+                key_ptr = copy_boxed_item(key_ptr, &type_.key_type);
+                value_ptr = copy_boxed_item(value_ptr, &type_.value_type);
+            }
+
+            unsafe {
+                glib::ffi::g_hash_table_insert(table as *mut _, key_ptr, value_ptr);
+            }
+        }
+
+        // Transfer-none tables are released after the call; transfer-full tables
+        // belong to the callee once the call is made.
+        if type_.is_borrowed {
+            Ok(Value::OwnedPtr(OwnedPtr::new(guard, table)))
+        } else {
+            std::mem::forget(guard);
+            Ok(Value::Ptr(table))
+        }
+    }
+
+    fn try_from_variant(arg: &arg::Arg, type_: &VariantType) -> anyhow::Result<Value> {
+        let is_maybe = type_.signature.as_ref().is_some_and(|s| s.is_maybe());
+
+        if !is_maybe && matches!(arg.value, value::Value::Null | value::Value::Undefined) {
+            return Ok(Value::Ptr(std::ptr::null_mut()));
+        }
+
+        // Variants built here are never floating, so functions that sink
+        // their argument take a new reference instead of stealing ours.
+        let variant = variant::from_value(&arg.value, type_.signature.as_deref())?;
+        let ptr = variant.as_ptr() as *mut c_void;
+
+        Ok(Value::OwnedPtr(OwnedPtr::new(variant, ptr)))
+    }
+
+    fn try_from_bytes(arg: &arg::Arg, type_: &BytesType) -> anyhow::Result<Value> {
+        let bytes = match &arg.value {
+            value::Value::Bytes(bytes) => bytes,
+            value::Value::Null | value::Value::Undefined => {
+                return Ok(Value::Ptr(std::ptr::null_mut()));
+            }
+            _ => bail!("Expected a Buffer for bytes type, got {:?}", arg.value),
+        };
+
+        let data: &[u8] = bytes;
+
+        match (type_.kind, type_.is_borrowed) {
+            (BytesKind::GBytes, true) => {
+                let ptr = bytes.as_ptr() as *mut c_void;
+                Ok(Value::OwnedPtr(OwnedPtr::new(bytes.clone(), ptr)))
+            }
+            (BytesKind::GBytes, false) => {
+                let ptr: *mut glib::ffi::GBytes = bytes.clone().into_glib_ptr();
+                Ok(Value::Ptr(ptr as *mut c_void))
+            }
+            (BytesKind::Pointer, true) => {
+                let ptr = data.as_ptr() as *mut c_void;
+                Ok(Value::OwnedPtr(OwnedPtr::new(bytes.clone(), ptr)))
+            }
+            (BytesKind::Pointer, false) => {
+                let ptr = unsafe { glib::ffi::g_malloc(data.len()) };
+
+                if !data.is_empty() {
+                    unsafe {
+                        std::ptr::copy_nonoverlapping(data.as_ptr(), ptr as *mut u8, data.len())
+                    };
+                }
+
+                Ok(Value::Ptr(ptr))
+            }
+        }
+    }
+
     fn try_from_callback(arg: &arg::Arg, type_: &CallbackType) -> anyhow::Result<Value> {
         let cb = match &arg.value {
             value::Value::Callback(callback) => callback,
+            value::Value::Closure(closure)
+                if type_.trampoline == CallbackTrampoline::AsyncReady =>
+            {
+                return Ok(async_ready_callback(closure.clone()));
+            }
             value::Value::Null | value::Value::Undefined if arg.optional => {
                 return Ok(Value::Ptr(std::ptr::null_mut()));
             }
@@ -502,10 +879,18 @@ impl Value {
             CallbackTrampoline::Closure => {
                 let arg_types = type_.arg_types.clone();
                 let return_type = type_.return_type.clone();
+                let is_async = type_.is_async;
+                let coalesce = type_.coalesce;
 
                 let closure = glib::Closure::new(move |args: &[glib::Value]| {
                     let args_values = convert_glib_args(args, &arg_types)
                         .expect("Failed to convert GLib callback arguments");
+
+                    if is_async {
+                        invoke_without_waiting(&channel, &callback, args_values, coalesce);
+                        return None;
+                    }
+
                     let return_type = *return_type.clone().unwrap_or(Box::new(Type::Undefined));
 
                     invoke_and_wait_for_js_result(
@@ -513,15 +898,24 @@ impl Value {
                         &callback,
                         args_values,
                         true,
-                        |result| match result {
-                            Ok(value) => value::Value::into_glib_value_with_default(
-                                value,
-                                Some(&return_type),
-                            ),
-                            Err(_) => value::Value::into_glib_value_with_default(
-                                value::Value::Undefined,
-                                Some(&return_type),
-                            ),
+                        |result| {
+                            let value = result.unwrap_or(value::Value::Undefined);
+
+                            // GTK cannot be handed an error, so an invalid return
+                            // value is thrown on the JS thread as an uncaught
+                            // exception and replaced by the default.
+                            value
+                                .into_glib_value_with_default(Some(&return_type))
+                                .unwrap_or_else(|err| {
+                                    let message = format!("Invalid callback return value: {err}");
+                                    channel
+                                        .send(move |mut cx| cx.throw_type_error::<_, ()>(message));
+
+                                    value::Value::Undefined
+                                        .into_glib_value_with_default(Some(&return_type))
+                                        .ok()
+                                        .flatten()
+                                })
                         },
                     )
                 });
@@ -553,24 +947,12 @@ impl Value {
 
                     let args_values = vec![source_value, result_value];
 
-                    invoke_and_wait_for_js_result(
-                        &channel,
-                        &callback,
-                        args_values,
-                        false,
-                        |_| None::<glib::Value>,
-                    )
+                    invoke_and_wait_for_js_result(&channel, &callback, args_values, false, |_| {
+                        None::<glib::Value>
+                    })
                 });
 
-                let closure_ptr = closure_ptr_for_transfer(closure);
-                let trampoline_ptr = callback::get_async_ready_trampoline_ptr();
-
-                Ok(Value::TrampolineCallback(TrampolineCallbackValue {
-                    trampoline_ptr,
-                    closure: OwnedPtr::new((), closure_ptr),
-                    destroy_ptr: None,
-                    data_first: false,
-                }))
+                Ok(async_ready_callback(closure))
             }
 
             CallbackTrampoline::Destroy => {
@@ -673,10 +1055,28 @@ impl Value {
                     ),
                 }
             }
+            Type::Array(_) if matches!(&*r#ref.value, value::Value::Array(_)) => {
+                // Caller-allocates: pass the array buffer directly, the callee
+                // fills it in and it is read back from the same buffer.
+                let array_arg = Arg::new(*type_.inner_type.clone(), *r#ref.value.clone());
+                Value::try_from(&array_arg)
+            }
+            Type::Bytes(bytes_type)
+                if bytes_type.kind == BytesKind::Pointer
+                    && matches!(&*r#ref.value, value::Value::Bytes(_)) =>
+            {
+                // Caller-allocates: the callee fills a copy of the buffer,
+                // which is read back once the call returns.
+                let value::Value::Bytes(bytes) = &*r#ref.value else {
+                    unreachable!()
+                };
+
+                Ok(Value::OwnedPtr(OwnedPtr::from_vec(bytes.to_vec())))
+            }
             _ => {
                 // For primitive types, create storage and pass pointer to it
                 let ref_arg = Arg::new(*type_.inner_type.clone(), *r#ref.value.clone());
-                let ref_value = Box::new(Value::try_from(ref_arg)?);
+                let ref_value = Box::new(Value::try_from(&ref_arg)?);
                 let ref_ptr = ref_value.as_ptr();
 
                 Ok(Value::OwnedPtr(OwnedPtr {
@@ -686,6 +1086,183 @@ impl Value {
             }
         }
     }
+
+    fn try_from_struct(arg: &arg::Arg, type_: &StructType) -> anyhow::Result<Value> {
+        // Back the struct with u64 words so the buffer satisfies the alignment
+        // of any field type.
+        let mut buffer = vec![0u64; type_.size().div_ceil(8).max(1)];
+        let ptr = buffer.as_mut_ptr() as *mut c_void;
+        let mut strings = Vec::new();
+
+        match &arg.value {
+            value::Value::Struct(fields) => {
+                write_struct_fields(ptr as *mut u8, type_, fields, &mut strings)?;
+            }
+            // A missing struct is zero-initialized, which is what out-parameters expect.
+            value::Value::Null | value::Value::Undefined => {}
+            _ => bail!("Expected an Object for struct type, got {:?}", arg.value),
+        }
+
+        Ok(Value::Struct(OwnedPtr::new((buffer, strings), ptr)))
+    }
+}
+
+unsafe extern "C" fn unref_object(ptr: glib::ffi::gpointer) {
+    unsafe { glib::gobject_ffi::g_object_unref(ptr as *mut _) }
+}
+
+/// Returns the destroy notify for hash table or pointer array items of the
+/// given type.
+///
+/// Items are always copied (strings) or referenced (objects) on insertion,
+/// so the container owns whatever it needs to free.
+fn pointer_item_destroy_func(type_: &Type) -> glib::ffi::GDestroyNotify {
+    match type_ {
+        Type::String(_) => Some(glib::ffi::g_free),
+        Type::GObject(_) => Some(unref_object),
+        _ => None,
+    }
+}
+
+/// Copies a boxed container item so that it can be owned by the callee.
+///
+/// Items of other types, null items and boxed items of unknown type are
+/// returned unchanged.
+fn copy_boxed_item(ptr: *mut c_void, type_: &Type) -> *mut c_void {
+    match type_ {
+        Type::Boxed(boxed_type) if !ptr.is_null() => match boxed_type.get_gtype() {
+            Some(gtype) => unsafe { glib::gobject_ffi::g_boxed_copy(gtype.into_glib(), ptr) },
+            None => ptr,
+        },
+        _ => ptr,
+    }
+}
+
+/// Packs a value into a hash table or pointer array slot.
+fn pointer_item_to_ptr(value: &value::Value, type_: &Type) -> anyhow::Result<*mut c_void> {
+    match (type_, value) {
+        (Type::Integer(int_type), value::Value::Number(n)) => match int_type.sign {
+            IntegerSign::Signed => Ok(*n as i64 as isize as *mut c_void),
+            IntegerSign::Unsigned => Ok(*n as u64 as usize as *mut c_void),
+        },
+        (Type::Integer(int_type), value::Value::BigInt(n)) => {
+            int_type.check_range(*n)?;
+
+            match int_type.sign {
+                IntegerSign::Signed => Ok(*n as i64 as isize as *mut c_void),
+                IntegerSign::Unsigned => Ok(*n as u64 as usize as *mut c_void),
+            }
+        }
+        (Type::Boolean, value::Value::Boolean(b)) => Ok(usize::from(*b) as *mut c_void),
+        (Type::String(_), value::Value::String(s)) => {
+            let cstring = CString::new(s.as_bytes())?;
+            Ok(unsafe { glib::ffi::g_strdup(cstring.as_ptr()) } as *mut c_void)
+        }
+        (Type::GObject(_), value::Value::Object(id)) => {
+            let ptr = id
+                .as_ptr()
+                .ok_or_else(|| anyhow::anyhow!("GObject has been garbage collected"))?;
+            unsafe { glib::gobject_ffi::g_object_ref(ptr as *mut _) };
+            Ok(ptr)
+        }
+        (Type::Boxed(_), value::Value::Object(id)) => id
+            .as_ptr()
+            .ok_or_else(|| anyhow::anyhow!("Boxed object has been garbage collected")),
+        (
+            Type::String(_) | Type::GObject(_) | Type::Boxed(_),
+            value::Value::Null | value::Value::Undefined,
+        ) => Ok(std::ptr::null_mut()),
+        _ => bail!(
+            "Expected a value of type {:?} for container item, got {:?}",
+            type_,
+            value
+        ),
+    }
+}
+
+/// Writes struct fields into memory laid out according to `type_`.
+///
+/// Strings are copied into `strings`, which must outlive any use of the
+/// written memory.
+fn write_struct_fields(
+    ptr: *mut u8,
+    type_: &StructType,
+    fields: &[(String, value::Value)],
+    strings: &mut Vec<CString>,
+) -> anyhow::Result<()> {
+    let layout = type_.fields.iter().zip(type_.field_offsets());
+
+    for ((field, offset), (_, field_value)) in layout.zip(fields) {
+        let field_ptr = unsafe { ptr.add(offset) };
+
+        if !write_inline_value(field_ptr, &field.type_, field_value, strings)? {
+            bail!(
+                "Expected a value of type {:?} for struct field '{}', got {:?}",
+                field.type_,
+                field.name,
+                field_value
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a value stored inline in a struct field or array item.
+///
+/// Strings are copied into `strings`, which must outlive any use of the
+/// written memory. Returns `false` if the value does not match the type.
+fn write_inline_value(
+    ptr: *mut u8,
+    type_: &Type,
+    value: &value::Value,
+    strings: &mut Vec<CString>,
+) -> anyhow::Result<bool> {
+    match (type_, value) {
+        (Type::Integer(int_type), value::Value::Number(n)) => {
+            dispatch_integer_write!(int_type, ptr, *n);
+        }
+        (Type::Integer(int_type), value::Value::BigInt(n)) => {
+            int_type.check_range(*n)?;
+            dispatch_integer_write!(int_type, ptr, *n);
+        }
+        (Type::Float(float_type), value::Value::Number(n)) => match float_type.size {
+            FloatSize::_32 => unsafe { ptr.cast::<f32>().write_unaligned(*n as f32) },
+            FloatSize::_64 => unsafe { ptr.cast::<f64>().write_unaligned(*n) },
+        },
+        (Type::Boolean, value::Value::Boolean(b)) => unsafe {
+            ptr.cast::<u8>().write_unaligned(u8::from(*b));
+        },
+        (Type::Enum(enum_type), value) => {
+            let raw = enum_type.to_raw(value)?;
+            unsafe { ptr.cast::<i32>().write_unaligned(raw) };
+        }
+        (Type::Flags(flags_type), value) => {
+            let raw = flags_type.to_raw(value)?;
+            unsafe { ptr.cast::<u32>().write_unaligned(raw) };
+        }
+        (Type::String(_), value::Value::String(s)) => {
+            let cstring = CString::new(s.as_bytes())?;
+            unsafe { ptr.cast::<*const i8>().write_unaligned(cstring.as_ptr()) };
+            strings.push(cstring);
+        }
+        (Type::GObject(_) | Type::Boxed(_), value::Value::Object(id)) => {
+            let object_ptr = id
+                .as_ptr()
+                .ok_or_else(|| anyhow::anyhow!("Inline object has been garbage collected"))?;
+            unsafe { ptr.cast::<*mut c_void>().write_unaligned(object_ptr) };
+        }
+        (Type::Struct(nested_type), value::Value::Struct(nested_fields)) => {
+            write_struct_fields(ptr, nested_type, nested_fields, strings)?;
+        }
+        (
+            Type::String(_) | Type::GObject(_) | Type::Boxed(_) | Type::Struct(_),
+            value::Value::Null | value::Value::Undefined,
+        ) => {}
+        _ => return Ok(false),
+    }
+
+    Ok(true)
 }
 
 impl<'a> From<&'a Value> for libffi::Arg<'a> {
@@ -703,6 +1280,7 @@ impl<'a> From<&'a Value> for libffi::Arg<'a> {
             Value::F64(value) => libffi::arg(value),
             Value::Ptr(ptr) => libffi::arg(ptr),
             Value::OwnedPtr(owned_ptr) => libffi::arg(&owned_ptr.ptr),
+            Value::Struct(owned_ptr) => libffi::arg(unsafe { &*(owned_ptr.ptr as *const u8) }),
             Value::TrampolineCallback(_) => {
                 unreachable!("TrampolineCallback should be handled specially in call.rs")
             }
@@ -885,4 +1463,166 @@ mod tests {
             glib::gobject_ffi::g_closure_unref(ptr as *mut _);
         }
     }
+
+    #[repr(C)]
+    struct Extents {
+        flag: u8,
+        width: i64,
+        height: f32,
+    }
+
+    extern "C" fn extents_area(extents: Extents) -> f64 {
+        if extents.flag == 0 {
+            return -1.0;
+        }
+
+        extents.width as f64 * extents.height as f64
+    }
+
+    fn extents_type() -> StructType {
+        StructType::new(vec![
+            StructField {
+                name: "flag".to_string(),
+                type_: Type::Boolean,
+            },
+            StructField {
+                name: "width".to_string(),
+                type_: Type::Integer(IntegerType::new(IntegerSize::_64, IntegerSign::Signed)),
+            },
+            StructField {
+                name: "height".to_string(),
+                type_: Type::Float(FloatType::new(FloatSize::_32)),
+            },
+        ])
+    }
+
+    #[test]
+    fn struct_value_is_passed_by_value() {
+        let struct_type = extents_type();
+        let arg = Arg::new(
+            Type::Struct(struct_type.clone()),
+            value::Value::Struct(vec![
+                ("flag".to_string(), value::Value::Boolean(true)),
+                ("width".to_string(), value::Value::Number(6.0)),
+                ("height".to_string(), value::Value::Number(2.5)),
+            ]),
+        );
+
+        let cif_value = Value::try_from(&arg).unwrap();
+        let cif = libffi::Builder::new()
+            .res(libffi::Type::f64())
+            .args([libffi::Type::from(&struct_type)])
+            .into_cif();
+
+        let result = unsafe {
+            cif.call::<f64>(
+                libffi::CodePtr(extents_area as *mut c_void),
+                &[(&cif_value).into()],
+            )
+        };
+
+        assert_eq!(result, 15.0);
+    }
+
+    #[test]
+    fn struct_value_defaults_to_zeroed_memory() {
+        let struct_type = extents_type();
+        let arg = Arg::new(Type::Struct(struct_type.clone()), value::Value::Null);
+
+        let cif_value = Value::try_from(&arg).unwrap();
+        let bytes = unsafe {
+            std::slice::from_raw_parts(cif_value.as_ptr() as *const u8, struct_type.size())
+        };
+
+        assert!(bytes.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn struct_value_rejects_mismatched_field() {
+        let arg = Arg::new(
+            Type::Struct(extents_type()),
+            value::Value::Struct(vec![
+                ("flag".to_string(), value::Value::Number(1.0)),
+                ("width".to_string(), value::Value::Number(6.0)),
+                ("height".to_string(), value::Value::Number(2.5)),
+            ]),
+        );
+
+        assert!(Value::try_from(&arg).is_err());
+    }
+
+    #[test]
+    fn bigint_integer_arg_converts_losslessly() {
+        let int_type = IntegerType::new(IntegerSize::_64, IntegerSign::Unsigned);
+        let arg = Arg::new(
+            Type::Integer(int_type),
+            value::Value::BigInt(u64::MAX as i128),
+        );
+
+        assert!(matches!(
+            Value::try_from(&arg).unwrap(),
+            Value::U64(u64::MAX)
+        ));
+    }
+
+    #[test]
+    fn bigint_round_trips_through_inline_memory() {
+        let int_type = IntegerType::bigint(IntegerSize::_64, IntegerSign::Signed);
+        let mut buffer = [0u8; 8];
+
+        let written = write_inline_value(
+            buffer.as_mut_ptr(),
+            &Type::Integer(int_type),
+            &value::Value::BigInt(i64::MIN as i128),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        assert!(written);
+
+        let result = value::Value::from_integer_ptr(buffer.as_ptr(), &int_type);
+        assert!(matches!(result, value::Value::BigInt(n) if n == i64::MIN as i128));
+    }
+
+    #[test]
+    fn transfer_full_hash_table_copies_boxed_values() {
+        use crate::boxed::Boxed;
+        use crate::object::{Object, ObjectId};
+        use gtk4::gdk;
+
+        test_utils::ensure_gtk_init();
+
+        let gtype = gdk::RGBA::static_type();
+        let boxed_ptr = test_utils::allocate_test_boxed(gtype);
+        let id = ObjectId::new(Object::Boxed(Boxed::from_glib_full(Some(gtype), boxed_ptr)));
+
+        let hash_table_type = HashTableType::new(
+            Type::String(StringType { is_borrowed: false }),
+            Type::Boxed(BoxedType::new(false, "GdkRGBA".to_string(), None, None)),
+            false,
+        );
+        let arg = Arg::new(
+            Type::HashTable(hash_table_type),
+            value::Value::HashTable(vec![(
+                value::Value::String("color".to_string()),
+                value::Value::Object(id),
+            )]),
+        );
+
+        let table = match Value::try_from(&arg).unwrap() {
+            Value::Ptr(ptr) => ptr as *mut glib::ffi::GHashTable,
+            other => panic!("Expected a transferred GHashTable, got {:?}", other),
+        };
+
+        let key = CString::new("color").unwrap();
+        let item = unsafe { glib::ffi::g_hash_table_lookup(table, key.as_ptr() as *const _) };
+
+        assert!(!item.is_null());
+        assert_ne!(item, boxed_ptr);
+        assert!(test_utils::is_valid_boxed_ptr(item, gtype));
+
+        unsafe {
+            glib::gobject_ffi::g_boxed_free(gtype.into_glib(), item);
+            glib::ffi::g_hash_table_unref(table);
+        }
+    }
 }
@@ -0,0 +1,73 @@
+//! Runtime configuration set from JavaScript.
+//!
+//! Settings are stored in atomics so that both the JS and GTK threads can read
+//! them without locking. They are changed with the `configure` export.
+
+use std::{
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    time::Duration,
+};
+
+/// The default limit on nested synchronous calls from JS callbacks.
+pub const DEFAULT_MAX_REENTRANCY_DEPTH: usize = 64;
+
+static WAIT_TIMEOUT_MS: AtomicU64 = AtomicU64::new(0);
+static THROW_ON_TIMEOUT: AtomicBool = AtomicBool::new(false);
+static MAX_REENTRANCY_DEPTH: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_REENTRANCY_DEPTH);
+static DISPATCH_BUDGET_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Returns how long a cross-thread wait may take before the watchdog reports
+/// it, or `None` if the watchdog is disabled.
+pub fn wait_timeout() -> Option<Duration> {
+    match WAIT_TIMEOUT_MS.load(Ordering::Relaxed) {
+        0 => None,
+        ms => Some(Duration::from_millis(ms)),
+    }
+}
+
+/// Sets the watchdog timeout. `None` disables the watchdog.
+pub fn set_wait_timeout(timeout: Option<Duration>) {
+    let ms = timeout
+        .map(|timeout| timeout.as_millis() as u64)
+        .unwrap_or(0);
+    WAIT_TIMEOUT_MS.store(ms, Ordering::Relaxed);
+}
+
+/// Returns whether waits exceeding the watchdog timeout are abandoned.
+pub fn throw_on_timeout() -> bool {
+    THROW_ON_TIMEOUT.load(Ordering::Relaxed)
+}
+
+/// Sets whether waits exceeding the watchdog timeout are abandoned.
+pub fn set_throw_on_timeout(throw: bool) {
+    THROW_ON_TIMEOUT.store(throw, Ordering::Relaxed);
+}
+
+/// Returns the maximum number of nested synchronous calls, or `None` if
+/// nesting is unlimited.
+pub fn max_reentrancy_depth() -> Option<usize> {
+    match MAX_REENTRANCY_DEPTH.load(Ordering::Relaxed) {
+        0 => None,
+        depth => Some(depth),
+    }
+}
+
+/// Sets the maximum number of nested synchronous calls. `None` removes the limit.
+pub fn set_max_reentrancy_depth(depth: Option<usize>) {
+    MAX_REENTRANCY_DEPTH.store(depth.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Returns how long the GTK thread may run queued tasks before yielding back
+/// to the main loop, or `None` if it runs them all at once.
+pub fn dispatch_budget() -> Option<Duration> {
+    match DISPATCH_BUDGET_MS.load(Ordering::Relaxed) {
+        0 => None,
+        ms => Some(Duration::from_millis(ms)),
+    }
+}
+
+/// Sets the dispatch time budget. `None` removes the budget.
+pub fn set_dispatch_budget(budget: Option<Duration>) {
+    let ms = budget.map(|budget| budget.as_millis() as u64).unwrap_or(0);
+    DISPATCH_BUDGET_MS.store(ms, Ordering::Relaxed);
+}
@@ -0,0 +1,137 @@
+//! Structured errors reported by native functions.
+//!
+//! Functions that can fail report a `GError` through a trailing `GError**`
+//! argument. Calls that declare an `error` argument get that slot allocated
+//! natively, and a reported `GError` is converted to a [`NativeError`] and
+//! freed before the call returns to JavaScript.
+
+use std::{ffi::CStr, fmt};
+
+use gtk4::glib;
+use neon::prelude::*;
+
+/// A `GError` reported by a native function.
+///
+/// The error is copied out of the `GError`, so it can be sent to the JS
+/// thread after the `GError` itself has been freed.
+#[derive(Debug, Clone)]
+pub struct NativeError {
+    /// The error domain, as the string form of its quark (e.g. `"g-file-error-quark"`).
+    pub domain: String,
+    /// The error domain's quark.
+    pub quark: u32,
+    /// The error code within the domain.
+    pub code: i32,
+    /// The human-readable error message.
+    pub message: String,
+}
+
+impl NativeError {
+    /// Converts a `GError` to a [`NativeError`], freeing the `GError`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid `GError` owned by the caller. It must not
+    /// be used after this call.
+    pub unsafe fn from_glib_full(ptr: *mut glib::ffi::GError) -> Self {
+        let error = unsafe { &*ptr };
+
+        let domain = unsafe { glib::ffi::g_quark_to_string(error.domain) };
+        let domain = if domain.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(domain) }
+                .to_string_lossy()
+                .into_owned()
+        };
+
+        let message = if error.message.is_null() {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(error.message) }
+                .to_string_lossy()
+                .into_owned()
+        };
+
+        let native_error = NativeError {
+            domain,
+            quark: error.domain,
+            code: error.code,
+            message,
+        };
+
+        unsafe { glib::ffi::g_error_free(ptr) };
+
+        native_error
+    }
+
+    /// Creates a JavaScript `Error` named `NativeError`, with `domain`,
+    /// `quark` and `code` properties.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NeonResult` error if the error object cannot be created.
+    pub fn to_js_error<'a, C: Context<'a>>(&self, cx: &mut C) -> JsResult<'a, JsError> {
+        let js_error = cx.error(&self.message)?;
+
+        let name = cx.string("NativeError");
+        js_error.set(cx, "name", name)?;
+
+        let domain = cx.string(&self.domain);
+        js_error.set(cx, "domain", domain)?;
+
+        let quark = cx.number(self.quark);
+        js_error.set(cx, "quark", quark)?;
+
+        let code = cx.number(self.code);
+        js_error.set(cx, "code", code)?;
+
+        Ok(js_error)
+    }
+}
+
+impl fmt::Display for NativeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}: {})", self.message, self.domain, self.code)
+    }
+}
+
+impl std::error::Error for NativeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_glib_full_copies_error_fields() {
+        let domain = unsafe { glib::ffi::g_quark_from_string(c"gtkx-test-error-quark".as_ptr()) };
+        let ptr =
+            unsafe { glib::ffi::g_error_new_literal(domain, 3, c"Something failed".as_ptr()) };
+
+        let error = unsafe { NativeError::from_glib_full(ptr) };
+
+        assert_eq!(error.domain, "gtkx-test-error-quark");
+        assert_eq!(error.quark, domain);
+        assert_eq!(error.code, 3);
+        assert_eq!(error.message, "Something failed");
+        assert_eq!(
+            error.to_string(),
+            "Something failed (gtkx-test-error-quark: 3)"
+        );
+    }
+
+    #[test]
+    fn anyhow_errors_downcast_to_native_error() {
+        let error: anyhow::Error = NativeError {
+            domain: "g-file-error-quark".to_string(),
+            quark: 0,
+            code: 4,
+            message: "No such file".to_string(),
+        }
+        .into();
+
+        let native_error = error.downcast_ref::<NativeError>();
+
+        assert!(matches!(native_error, Some(e) if e.code == 4));
+    }
+}
@@ -0,0 +1,181 @@
+//! Synchronizing batches of calls with the frame clock.
+//!
+//! Commits requested with `requestFrameCommit` are held on the GTK thread until
+//! the next frame of the application's windows, and applied together in the
+//! `update` phase of its frame clock, before layout and painting. The changes of
+//! all commits requested for a frame are therefore painted in the same frame,
+//! instead of being split across two when a frame starts between them.
+//!
+//! Once the frame has been painted, the commits applied in it are told that it
+//! was presented, along with its frame time.
+//!
+//! Only windows that are mapped and not minimized paint frames. Commits that
+//! wait longer than [`FRAME_TIMEOUT`] for a frame, because every window's frame
+//! clock has stopped, are applied without one.
+
+use std::{cell::RefCell, mem, rc::Rc, time::Duration};
+
+use gtk4::{gdk, glib, prelude::*};
+
+/// How long commits wait for a frame before they are applied without one.
+const FRAME_TIMEOUT: Duration = Duration::from_millis(100);
+
+type Apply = Box<dyn FnOnce()>;
+type Presented = Box<dyn FnOnce(i64)>;
+
+/// A commit waiting for the next frame.
+struct Commit {
+    apply: Apply,
+    on_presented: Presented,
+}
+
+#[derive(Default)]
+struct FrameState {
+    /// Commits to apply in the next frame, in request order.
+    pending: Vec<Commit>,
+    /// Frame clocks whose phases have been connected to.
+    clocks: Vec<glib::WeakRef<gdk::FrameClock>>,
+    /// Source applying the pending commits if no frame comes in time.
+    timeout: Option<glib::SourceId>,
+}
+
+thread_local! {
+    static STATE: RefCell<FrameState> = RefCell::new(FrameState::default());
+}
+
+/// Requests a commit in the next frame. Must be called on the GTK thread.
+///
+/// `apply` runs in the `update` phase of the next frame of any of the
+/// application's visible windows, and `on_presented` is called with the frame
+/// time once that frame has been painted. Without a visible window, there is
+/// no frame to wait for, so both run immediately; they also run if no frame
+/// comes within [`FRAME_TIMEOUT`].
+pub fn request_commit(apply: impl FnOnce() + 'static, on_presented: impl FnOnce(i64) + 'static) {
+    let clocks: Vec<gdk::FrameClock> = gtk4::Window::list_toplevels()
+        .iter()
+        .filter(|window| is_painted(window))
+        .filter_map(WidgetExt::frame_clock)
+        .collect();
+
+    if clocks.is_empty() {
+        apply();
+        on_presented(glib::monotonic_time());
+        return;
+    }
+
+    STATE.with_borrow_mut(|state| {
+        state.pending.push(Commit {
+            apply: Box::new(apply),
+            on_presented: Box::new(on_presented),
+        });
+
+        state.clocks.retain(|clock| clock.upgrade().is_some());
+
+        if state.timeout.is_none() {
+            state.timeout = Some(glib::timeout_add_local_once(
+                FRAME_TIMEOUT,
+                apply_without_frame,
+            ));
+        }
+    });
+
+    for clock in clocks {
+        let connected = STATE.with_borrow(|state| {
+            state
+                .clocks
+                .iter()
+                .any(|weak| weak.upgrade().as_ref() == Some(&clock))
+        });
+
+        if !connected {
+            connect(&clock);
+        }
+
+        clock.request_phase(gdk::FrameClockPhase::UPDATE);
+    }
+}
+
+/// Returns whether a window paints frames: it is mapped and not minimized.
+fn is_painted(window: &gtk4::Widget) -> bool {
+    let is_minimized = window
+        .native()
+        .and_then(|native| native.surface())
+        .and_then(|surface| surface.downcast::<gdk::Toplevel>().ok())
+        .is_some_and(|toplevel| toplevel.state().contains(gdk::ToplevelState::MINIMIZED));
+
+    window.is_mapped() && !is_minimized
+}
+
+/// Applies the pending commits when no frame came in time, and reports them
+/// presented right away.
+fn apply_without_frame() {
+    let commits = STATE.with_borrow_mut(|state| {
+        state.timeout = None;
+        mem::take(&mut state.pending)
+    });
+
+    for commit in commits {
+        (commit.apply)();
+        (commit.on_presented)(glib::monotonic_time());
+    }
+}
+
+/// Applies pending commits in the `update` phase of the clock, and reports
+/// them presented in its `after-paint` phase.
+fn connect(clock: &gdk::FrameClock) {
+    let presenting: Rc<RefCell<Vec<Presented>>> = Rc::default();
+    let update_presenting = Rc::clone(&presenting);
+
+    clock.connect_update(move |_| {
+        // Commits are applied without the state borrowed, since they may
+        // fire callbacks that request further commits for the next frame.
+        let commits = STATE.with_borrow_mut(|state| {
+            if let Some(timeout) = state.timeout.take() {
+                timeout.remove();
+            }
+
+            mem::take(&mut state.pending)
+        });
+
+        for commit in commits {
+            (commit.apply)();
+            update_presenting.borrow_mut().push(commit.on_presented);
+        }
+    });
+
+    clock.connect_after_paint(move |clock| {
+        let frame_time = clock.frame_time();
+        let presented = mem::take(&mut *presenting.borrow_mut());
+
+        for on_presented in presented {
+            on_presented(frame_time);
+        }
+    });
+
+    STATE.with_borrow_mut(|state| state.clocks.push(clock.downgrade()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use std::cell::Cell;
+
+    #[test]
+    fn request_commit_applies_immediately_without_windows() {
+        test_utils::ensure_gtk_init();
+
+        let applied = Rc::new(Cell::new(false));
+        let presented = Rc::new(Cell::new(None));
+        let applied_clone = Rc::clone(&applied);
+        let presented_clone = Rc::clone(&presented);
+
+        request_commit(
+            move || applied_clone.set(true),
+            move |frame_time| presented_clone.set(Some(frame_time)),
+        );
+
+        assert!(applied.get());
+        assert!(presented.get().is_some());
+    }
+}
@@ -4,22 +4,85 @@
 //! the JavaScript thread to the GTK thread.
 //!
 //! Two paths exist:
-//! - Normal: `schedule()` uses `glib::idle_add_once` to let the GLib main loop process callbacks
+//! - Normal: `schedule()` adds an idle source to let the GLib main loop process callbacks
 //! - Re-entrant: `dispatch_pending()` processes queued callbacks synchronously when the GTK
 //!   thread is blocked waiting for a JavaScript callback result
-
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+//!
+//! Tasks are queued by [`Priority`], each with its own idle source, so that calls responding
+//! to input can run ahead of redraws while background work waits behind them. A configurable
+//! time budget limits how long one source dispatches before yielding back to the main loop.
+//!
+//! Scheduling a task wakes the GTK thread if it is waiting, and running one wakes the JS
+//! thread if it is waiting for the task's result.
+//!
+//! In main thread mode GTK runs on the JS thread itself, so tasks scheduled from it run
+//! inline instead of being queued.
+
+use std::{
+    sync::{
+        OnceLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    thread::{self, ThreadId},
+    time::Instant,
+};
 
 use gtk4::glib;
 
-use crate::queue::Queue;
+use crate::{config, notify, queue::Queue};
 
 type Task = Box<dyn FnOnce() + Send + 'static>;
 
-static QUEUE: Queue<Task> = Queue::new();
-static DISPATCH_SCHEDULED: AtomicBool = AtomicBool::new(false);
+/// The priority a task is dispatched at on the GTK thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Priority {
+    /// `G_PRIORITY_HIGH`, ahead of input handling, layout and redraws. For
+    /// calls that respond to user input.
+    High,
+    /// `G_PRIORITY_DEFAULT`, alongside input handling and other events.
+    Default,
+    /// `G_PRIORITY_DEFAULT_IDLE`, behind layout and redraws. For background
+    /// work, and used when no priority is given.
+    #[default]
+    Idle,
+}
+
+impl Priority {
+    /// All priorities, highest first.
+    const ALL: [Priority; 3] = [Priority::High, Priority::Default, Priority::Idle];
+
+    fn glib_priority(self) -> glib::Priority {
+        match self {
+            Priority::High => glib::Priority::HIGH,
+            Priority::Default => glib::Priority::DEFAULT,
+            Priority::Idle => glib::Priority::DEFAULT_IDLE,
+        }
+    }
+}
+
+static QUEUES: [Queue<Task>; 3] = [const { Queue::new() }; 3];
+static DISPATCH_SCHEDULED: [AtomicBool; 3] = [const { AtomicBool::new(false) }; 3];
+static DISPATCH_DEFERRED: [AtomicBool; 3] = [const { AtomicBool::new(false) }; 3];
 static STOPPED: AtomicBool = AtomicBool::new(false);
 static JS_WAIT_DEPTH: AtomicUsize = AtomicUsize::new(0);
+static MAIN_THREAD: OnceLock<ThreadId> = OnceLock::new();
+
+/// Makes the current thread the GTK thread for main thread mode.
+///
+/// Called by `start` before creating the application on the JS thread.
+pub fn set_main_thread() {
+    let _ = MAIN_THREAD.set(thread::current().id());
+}
+
+/// Returns whether GTK runs in main thread mode and this is its thread.
+///
+/// When true, tasks scheduled from this thread run immediately and callbacks
+/// fired by them are invoked directly rather than waited for.
+pub fn is_main_thread() -> bool {
+    MAIN_THREAD
+        .get()
+        .is_some_and(|id| *id == thread::current().id())
+}
 
 /// Returns whether the JS thread is currently waiting for a GTK dispatch result.
 ///
@@ -29,6 +92,13 @@ pub fn is_js_waiting() -> bool {
     JS_WAIT_DEPTH.load(Ordering::Acquire) > 0
 }
 
+/// Returns how many synchronous calls the JS thread is currently waiting for.
+///
+/// Nested calls made from JS callbacks each add one level.
+pub fn js_wait_depth() -> usize {
+    JS_WAIT_DEPTH.load(Ordering::Acquire)
+}
+
 /// Increments the JS wait depth counter.
 ///
 /// Called when entering the wait loop in call.rs. Supports nested calls.
@@ -52,14 +122,28 @@ pub fn mark_stopped() {
     STOPPED.store(true, Ordering::Release);
 }
 
-/// Schedules a task to be executed on the GTK thread.
+/// Schedules a task to be executed on the GTK thread at idle priority.
+///
+/// See [`schedule_with_priority`].
+pub fn schedule<F>(task: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    schedule_with_priority(Priority::Idle, task);
+}
+
+/// Schedules a task to be executed on the GTK thread at the given priority.
 ///
-/// The task is added to a queue and will be dispatched either:
+/// The task is added to the queue of its priority and will be dispatched either:
 /// 1. By the GTK main loop via an idle source (normal path)
 /// 2. By `dispatch_pending()` during signal handling (re-entrant path)
 ///
+/// In main thread mode, a task scheduled from the GTK thread runs immediately
+/// instead, and the main context is woken so that sources it attached are
+/// picked up by the loop driver.
+///
 /// If the dispatch system has been marked as stopped, the task is silently dropped.
-pub fn schedule<F>(task: F)
+pub fn schedule_with_priority<F>(priority: Priority, task: F)
 where
     F: FnOnce() + Send + 'static,
 {
@@ -67,29 +151,78 @@ where
         return;
     }
 
-    QUEUE.push(Box::new(task));
+    if is_main_thread() {
+        task();
+        glib::MainContext::default().wakeup();
+        return;
+    }
+
+    queue(priority).push(Box::new(task));
+    notify::GTK_THREAD.notify();
+    ensure_dispatch_scheduled(priority);
+}
 
-    if DISPATCH_SCHEDULED
+fn queue(priority: Priority) -> &'static Queue<Task> {
+    &QUEUES[priority as usize]
+}
+
+/// Adds an idle source dispatching the queue of the given priority at that
+/// priority, unless one is already pending.
+fn ensure_dispatch_scheduled(priority: Priority) {
+    add_dispatch_source(&DISPATCH_SCHEDULED, priority, priority.glib_priority());
+}
+
+/// Adds an idle source dispatching the queue of the given priority at
+/// `G_PRIORITY_DEFAULT_IDLE`, unless one is already pending.
+///
+/// Used once a batch has spent its time budget, since a source at the queue's
+/// own priority could be dispatched again before the main loop handles input
+/// and redraws. The deferred source does not count as scheduled, so tasks
+/// queued after it still get a source at their own priority.
+fn ensure_dispatch_deferred(priority: Priority) {
+    add_dispatch_source(&DISPATCH_DEFERRED, priority, glib::Priority::DEFAULT_IDLE);
+}
+
+fn add_dispatch_source(
+    pending: &'static [AtomicBool; 3],
+    priority: Priority,
+    source_priority: glib::Priority,
+) {
+    let pending = &pending[priority as usize];
+
+    if pending
         .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
         .is_ok()
     {
-        glib::idle_add_once(dispatch_batch);
+        glib::idle_add_full(source_priority, move || {
+            pending.store(false, Ordering::Release);
+            dispatch_batch(priority);
+            glib::ControlFlow::Break
+        });
     }
 }
 
-fn dispatch_batch() {
-    DISPATCH_SCHEDULED.store(false, Ordering::Release);
+/// Dispatches queued tasks of one priority until the queue is empty or the
+/// configured time budget is spent, in which case the rest are deferred.
+fn dispatch_batch(priority: Priority) {
+    let queue = queue(priority);
+    let deadline = config::dispatch_budget().map(|budget| Instant::now() + budget);
 
-    while let Some(task) = QUEUE.pop() {
+    while let Some(task) = queue.pop() {
         task();
+        notify::JS_THREAD.notify();
+
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            if !queue.is_empty() {
+                ensure_dispatch_deferred(priority);
+            }
+
+            return;
+        }
     }
 
-    if !QUEUE.is_empty()
-        && DISPATCH_SCHEDULED
-            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
-            .is_ok()
-    {
-        glib::idle_add_once(dispatch_batch);
+    if !queue.is_empty() {
+        ensure_dispatch_scheduled(priority);
     }
 }
 
@@ -97,28 +230,22 @@ fn dispatch_batch() {
 ///
 /// This is called from the GTK thread's wait loop to process tasks that
 /// were scheduled by JS during signal handling, without triggering the full
-/// GTK main loop iteration.
+/// GTK main loop iteration. Higher priority tasks run first, and the time
+/// budget does not apply, since the JS thread is waiting for them.
 ///
 /// Returns `true` if any tasks were dispatched.
 pub fn dispatch_pending() -> bool {
     let mut dispatched = false;
 
-    while let Some(task) = QUEUE.pop() {
+    while let Some(task) = Priority::ALL
+        .into_iter()
+        .find_map(|priority| queue(priority).pop())
+    {
         task();
+        notify::JS_THREAD.notify();
         dispatched = true;
     }
 
-    if dispatched {
-        DISPATCH_SCHEDULED.store(false, Ordering::Release);
-        if !QUEUE.is_empty()
-            && DISPATCH_SCHEDULED
-                .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
-                .is_ok()
-        {
-            glib::idle_add_once(dispatch_batch);
-        }
-    }
-
     dispatched
 }
 
@@ -154,6 +281,11 @@ mod tests {
         assert!(!is_js_waiting());
     }
 
+    #[test]
+    fn is_main_thread_is_false_in_threaded_mode() {
+        assert!(!is_main_thread());
+    }
+
     #[test]
     fn dispatch_pending_executes_tasks() {
         test_utils::ensure_gtk_init();
@@ -161,7 +293,7 @@ mod tests {
         let executed = Arc::new(AtomicBool::new(false));
         let executed_clone = executed.clone();
 
-        QUEUE.push(Box::new(move || {
+        queue(Priority::Idle).push(Box::new(move || {
             executed_clone.store(true, AtomicOrdering::SeqCst);
         }));
 
@@ -175,7 +307,9 @@ mod tests {
     fn dispatch_pending_returns_false_when_empty() {
         test_utils::ensure_gtk_init();
 
-        while QUEUE.pop().is_some() {}
+        for priority in Priority::ALL {
+            while queue(priority).pop().is_some() {}
+        }
 
         let dispatched = dispatch_pending();
         assert!(!dispatched);
@@ -189,7 +323,7 @@ mod tests {
 
         for i in 0..5 {
             let order_clone = order.clone();
-            QUEUE.push(Box::new(move || {
+            queue(Priority::Idle).push(Box::new(move || {
                 order_clone.lock().unwrap().push(i);
             }));
         }
@@ -200,6 +334,28 @@ mod tests {
         assert_eq!(*result, vec![0, 1, 2, 3, 4]);
     }
 
+    #[test]
+    fn dispatch_pending_executes_higher_priorities_first() {
+        test_utils::ensure_gtk_init();
+
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        for priority in [Priority::Idle, Priority::Default, Priority::High] {
+            let order_clone = order.clone();
+            queue(priority).push(Box::new(move || {
+                order_clone.lock().unwrap().push(priority);
+            }));
+        }
+
+        dispatch_pending();
+
+        let result = order.lock().unwrap();
+        assert_eq!(
+            *result,
+            vec![Priority::High, Priority::Default, Priority::Idle]
+        );
+    }
+
     #[test]
     fn task_can_schedule_another_task() {
         test_utils::ensure_gtk_init();
@@ -207,11 +363,11 @@ mod tests {
         let counter = Arc::new(AtomicUsize::new(0));
         let counter_clone = counter.clone();
 
-        QUEUE.push(Box::new(move || {
+        queue(Priority::Idle).push(Box::new(move || {
             counter_clone.fetch_add(1, AtomicOrdering::SeqCst);
 
             let counter_inner = counter_clone.clone();
-            QUEUE.push(Box::new(move || {
+            queue(Priority::Idle).push(Box::new(move || {
                 counter_inner.fetch_add(1, AtomicOrdering::SeqCst);
             }));
         }));
@@ -241,7 +397,7 @@ mod tests {
             counter: drop_counter.clone(),
         };
 
-        QUEUE.push(Box::new(move || {
+        queue(Priority::Idle).push(Box::new(move || {
             let _t = tracker;
         }));
 
@@ -0,0 +1,627 @@
+//! Runtime GObject-Introspection backend.
+//!
+//! Resolves functions and methods from typelibs through `libgirepository`,
+//! deriving argument and return types, ownership transfer and nullability
+//! from the typelib instead of hand-built type descriptors. The library is
+//! loaded on first use, so it is only required by introspected calls.
+//!
+//! Only the `libgirepository-1.0` API is supported. The `libgirepository-2.0`
+//! that ships with GLib 2.80 renames every function (`gi_repository_*`),
+//! replaces `GIInfoType` with GTypes and has no default repository, so it is
+//! not a drop-in replacement; systems that only provide it fail with an error
+//! naming the missing library.
+//!
+//! Resolving a function fetches the GTypes of the types it uses, which runs
+//! their `*_get_type` functions, so resolution must happen on the GTK thread.
+
+use std::{
+    ffi::{CStr, CString, c_char, c_int, c_void},
+    sync::OnceLock,
+};
+
+use anyhow::bail;
+use gtk4::glib::{
+    self,
+    prelude::StaticType as _,
+    translate::{FromGlib as _, IntoGlib as _},
+};
+use libloading::Library;
+
+use crate::{
+    error::NativeError,
+    types::{
+        ArrayLength, ArrayType, BoxedType, BytesKind, BytesType, EnumType, FlagsType, FloatSize,
+        FloatType, GObjectType, HashTableType, IntegerSign, IntegerSize, IntegerType, ListType,
+        RefType, StringType, Type, VariantType, type_layout,
+    },
+};
+
+const GIREPOSITORY_LIB: &str = "libgirepository-1.0.so.1";
+
+const GI_INFO_TYPE_FUNCTION: c_int = 1;
+const GI_INFO_TYPE_CALLBACK: c_int = 2;
+const GI_INFO_TYPE_STRUCT: c_int = 3;
+const GI_INFO_TYPE_BOXED: c_int = 4;
+const GI_INFO_TYPE_ENUM: c_int = 5;
+const GI_INFO_TYPE_FLAGS: c_int = 6;
+const GI_INFO_TYPE_OBJECT: c_int = 7;
+const GI_INFO_TYPE_INTERFACE: c_int = 8;
+const GI_INFO_TYPE_UNION: c_int = 11;
+
+const GI_FUNCTION_IS_METHOD: c_int = 1 << 0;
+const GI_FUNCTION_THROWS: c_int = 1 << 5;
+
+const GI_DIRECTION_IN: c_int = 0;
+
+const GI_TRANSFER_NOTHING: c_int = 0;
+const GI_TRANSFER_EVERYTHING: c_int = 2;
+
+const GI_TYPE_TAG_VOID: c_int = 0;
+const GI_TYPE_TAG_BOOLEAN: c_int = 1;
+const GI_TYPE_TAG_INT8: c_int = 2;
+const GI_TYPE_TAG_UINT8: c_int = 3;
+const GI_TYPE_TAG_INT16: c_int = 4;
+const GI_TYPE_TAG_UINT16: c_int = 5;
+const GI_TYPE_TAG_INT32: c_int = 6;
+const GI_TYPE_TAG_UINT32: c_int = 7;
+const GI_TYPE_TAG_INT64: c_int = 8;
+const GI_TYPE_TAG_UINT64: c_int = 9;
+const GI_TYPE_TAG_FLOAT: c_int = 10;
+const GI_TYPE_TAG_DOUBLE: c_int = 11;
+const GI_TYPE_TAG_GTYPE: c_int = 12;
+const GI_TYPE_TAG_UTF8: c_int = 13;
+const GI_TYPE_TAG_FILENAME: c_int = 14;
+const GI_TYPE_TAG_ARRAY: c_int = 15;
+const GI_TYPE_TAG_INTERFACE: c_int = 16;
+const GI_TYPE_TAG_GLIST: c_int = 17;
+const GI_TYPE_TAG_GSLIST: c_int = 18;
+const GI_TYPE_TAG_GHASH: c_int = 19;
+const GI_TYPE_TAG_UNICHAR: c_int = 21;
+
+const GI_ARRAY_TYPE_C: c_int = 0;
+const GI_ARRAY_TYPE_ARRAY: c_int = 1;
+const GI_ARRAY_TYPE_PTR_ARRAY: c_int = 2;
+const GI_ARRAY_TYPE_BYTE_ARRAY: c_int = 3;
+
+type Info = *mut c_void;
+
+macro_rules! girepository_functions {
+    ($($name:ident: fn($($arg:ty),*) -> $ret:ty;)*) => {
+        /// Functions loaded from `libgirepository`.
+        struct Girepository {
+            $($name: unsafe extern "C" fn($($arg),*) -> $ret,)*
+            _library: Library,
+        }
+
+        impl Girepository {
+            fn load() -> anyhow::Result<Self> {
+                let library = unsafe { Library::new(GIREPOSITORY_LIB) }?;
+
+                Ok(Girepository {
+                    $($name: unsafe { *library.get(concat!(stringify!($name), "\0").as_bytes())? },)*
+                    _library: library,
+                })
+            }
+        }
+    };
+}
+
+girepository_functions! {
+    g_irepository_get_default: fn() -> *mut c_void;
+    g_irepository_require: fn(
+        *mut c_void,
+        *const c_char,
+        *const c_char,
+        c_int,
+        *mut *mut glib::ffi::GError
+    ) -> *mut c_void;
+    g_irepository_find_by_name: fn(*mut c_void, *const c_char, *const c_char) -> Info;
+    g_irepository_get_shared_library: fn(*mut c_void, *const c_char) -> *const c_char;
+    g_base_info_unref: fn(Info) -> ();
+    g_base_info_get_type: fn(Info) -> c_int;
+    g_base_info_get_name: fn(Info) -> *const c_char;
+    g_object_info_find_method: fn(Info, *const c_char) -> Info;
+    g_interface_info_find_method: fn(Info, *const c_char) -> Info;
+    g_struct_info_find_method: fn(Info, *const c_char) -> Info;
+    g_union_info_find_method: fn(Info, *const c_char) -> Info;
+    g_function_info_get_symbol: fn(Info) -> *const c_char;
+    g_function_info_get_flags: fn(Info) -> c_int;
+    g_callable_info_get_n_args: fn(Info) -> c_int;
+    g_callable_info_get_arg: fn(Info, c_int) -> Info;
+    g_callable_info_get_return_type: fn(Info) -> Info;
+    g_callable_info_get_caller_owns: fn(Info) -> c_int;
+    g_arg_info_get_direction: fn(Info) -> c_int;
+    g_arg_info_get_ownership_transfer: fn(Info) -> c_int;
+    g_arg_info_may_be_null: fn(Info) -> glib::ffi::gboolean;
+    g_arg_info_get_type: fn(Info) -> Info;
+    g_type_info_get_tag: fn(Info) -> c_int;
+    g_type_info_is_pointer: fn(Info) -> glib::ffi::gboolean;
+    g_type_info_get_param_type: fn(Info, c_int) -> Info;
+    g_type_info_get_interface: fn(Info) -> Info;
+    g_type_info_get_array_length: fn(Info) -> c_int;
+    g_type_info_get_array_fixed_size: fn(Info) -> c_int;
+    g_type_info_get_array_type: fn(Info) -> c_int;
+    g_registered_type_info_get_type_name: fn(Info) -> *const c_char;
+    g_registered_type_info_get_g_type: fn(Info) -> glib::ffi::GType;
+}
+
+static GIREPOSITORY: OnceLock<Result<Girepository, String>> = OnceLock::new();
+
+fn girepository() -> anyhow::Result<&'static Girepository> {
+    GIREPOSITORY
+        .get_or_init(|| Girepository::load().map_err(|err| err.to_string()))
+        .as_ref()
+        .map_err(|err| {
+            anyhow::anyhow!(
+                "Failed to load {} (libgirepository-2.0 is not supported): {}",
+                GIREPOSITORY_LIB,
+                err
+            )
+        })
+}
+
+/// An owned reference to a `GIBaseInfo`, released when dropped.
+struct BaseInfo(Info);
+
+impl BaseInfo {
+    fn new(info: Info) -> Option<Self> {
+        (!info.is_null()).then_some(BaseInfo(info))
+    }
+
+    fn name(&self, gir: &Girepository) -> String {
+        unsafe { c_string((gir.g_base_info_get_name)(self.0)) }
+    }
+}
+
+impl Drop for BaseInfo {
+    fn drop(&mut self) {
+        if let Ok(gir) = girepository() {
+            unsafe { (gir.g_base_info_unref)(self.0) };
+        }
+    }
+}
+
+unsafe fn c_string(ptr: *const c_char) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+
+    unsafe { CStr::from_ptr(ptr) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// An argument of an introspected function.
+#[derive(Debug, Clone)]
+pub struct IntrospectedArg {
+    /// The type derived from the typelib; out-parameters are refs.
+    pub type_: Type,
+    /// Whether the argument accepts null.
+    pub optional: bool,
+}
+
+/// A function or method resolved from a typelib.
+#[derive(Debug, Clone)]
+pub struct IntrospectedFunction {
+    /// Comma-separated shared libraries of the namespace.
+    pub library: String,
+    /// The C symbol of the function.
+    pub symbol: String,
+    /// The C arguments, including the instance of methods and the trailing
+    /// `GError**` of functions that throw.
+    pub args: Vec<IntrospectedArg>,
+    /// The return type.
+    pub return_type: Type,
+}
+
+/// Resolves a function from the typelib of `namespace` at `version`.
+///
+/// `name` is either a function name (`"ascii_strup"`) or a method,
+/// constructor or static function of a type (`"Label.set_text"`).
+///
+/// Must be called on the GTK thread; see [`GtkThreadState::get_introspected`]
+/// for the cached lookup.
+///
+/// [`GtkThreadState::get_introspected`]: crate::state::GtkThreadState::get_introspected
+///
+/// # Errors
+///
+/// Returns an error if `libgirepository` or the typelib cannot be loaded,
+/// the function does not exist, or one of its types is not supported.
+pub fn resolve(namespace: &str, version: &str, name: &str) -> anyhow::Result<IntrospectedFunction> {
+    let gir = girepository()?;
+    let repository = unsafe { (gir.g_irepository_get_default)() };
+    let c_namespace = CString::new(namespace)?;
+    let c_version = CString::new(version)?;
+
+    let mut error: *mut glib::ffi::GError = std::ptr::null_mut();
+    let typelib = unsafe {
+        (gir.g_irepository_require)(
+            repository,
+            c_namespace.as_ptr(),
+            c_version.as_ptr(),
+            0,
+            &mut error,
+        )
+    };
+
+    if typelib.is_null() {
+        if !error.is_null() {
+            return Err(unsafe { NativeError::from_glib_full(error) }.into());
+        }
+
+        bail!("Failed to load typelib {}-{}", namespace, version);
+    }
+
+    let find = |name: &str| -> anyhow::Result<Option<BaseInfo>> {
+        let c_name = CString::new(name)?;
+        let info = unsafe {
+            (gir.g_irepository_find_by_name)(repository, c_namespace.as_ptr(), c_name.as_ptr())
+        };
+        Ok(BaseInfo::new(info))
+    };
+
+    let (container, function) = match name.split_once('.') {
+        Some((container_name, method_name)) => {
+            let Some(container) = find(container_name)? else {
+                bail!("Type {}.{} not found", namespace, container_name);
+            };
+
+            let function = find_method(gir, &container, method_name)?;
+            (Some(container), function)
+        }
+        None => {
+            let function = find(name)?.filter(|info| unsafe {
+                (gir.g_base_info_get_type)(info.0) == GI_INFO_TYPE_FUNCTION
+            });
+            (None, function)
+        }
+    };
+
+    let Some(function) = function else {
+        bail!("Function {}.{} not found", namespace, name);
+    };
+
+    let flags = unsafe { (gir.g_function_info_get_flags)(function.0) };
+    let mut args = Vec::new();
+
+    if flags & GI_FUNCTION_IS_METHOD != 0
+        && let Some(container) = &container
+    {
+        args.push(IntrospectedArg {
+            type_: registered_type(gir, container, true, true)?,
+            optional: false,
+        });
+    }
+
+    // Array lengths refer to C arguments, which are shifted by the instance.
+    let offset = args.len();
+    let n_args = unsafe { (gir.g_callable_info_get_n_args)(function.0) };
+
+    for i in 0..n_args {
+        let Some(arg_info) = BaseInfo::new(unsafe { (gir.g_callable_info_get_arg)(function.0, i) })
+        else {
+            bail!("Failed to read argument {} of {}.{}", i, namespace, name);
+        };
+
+        let direction = unsafe { (gir.g_arg_info_get_direction)(arg_info.0) };
+        let transfer = unsafe { (gir.g_arg_info_get_ownership_transfer)(arg_info.0) };
+        let type_info = BaseInfo::new(unsafe { (gir.g_arg_info_get_type)(arg_info.0) });
+        let Some(type_info) = type_info else {
+            bail!(
+                "Failed to read the type of argument {} of {}.{}",
+                i,
+                namespace,
+                name
+            );
+        };
+
+        let type_ = type_from_info(gir, &type_info, transfer, offset).map_err(|err| {
+            anyhow::anyhow!(
+                "Unsupported argument '{}' of {}.{}: {}",
+                arg_info.name(gir),
+                namespace,
+                name,
+                err
+            )
+        })?;
+
+        args.push(IntrospectedArg {
+            type_: match direction {
+                GI_DIRECTION_IN => type_,
+                _ => Type::Ref(RefType::new(type_)),
+            },
+            optional: unsafe { (gir.g_arg_info_may_be_null)(arg_info.0) } != 0,
+        });
+    }
+
+    if flags & GI_FUNCTION_THROWS != 0 {
+        args.push(IntrospectedArg {
+            type_: Type::Error,
+            optional: false,
+        });
+    }
+
+    let Some(return_info) =
+        BaseInfo::new(unsafe { (gir.g_callable_info_get_return_type)(function.0) })
+    else {
+        bail!("Failed to read the return type of {}.{}", namespace, name);
+    };
+
+    let caller_owns = unsafe { (gir.g_callable_info_get_caller_owns)(function.0) };
+    let return_type = type_from_info(gir, &return_info, caller_owns, offset).map_err(|err| {
+        anyhow::anyhow!("Unsupported return type of {}.{}: {}", namespace, name, err)
+    })?;
+
+    let library = unsafe {
+        c_string((gir.g_irepository_get_shared_library)(
+            repository,
+            c_namespace.as_ptr(),
+        ))
+    };
+    let symbol = unsafe { c_string((gir.g_function_info_get_symbol)(function.0)) };
+
+    Ok(IntrospectedFunction {
+        library,
+        symbol,
+        args,
+        return_type,
+    })
+}
+
+fn find_method(
+    gir: &Girepository,
+    container: &BaseInfo,
+    name: &str,
+) -> anyhow::Result<Option<BaseInfo>> {
+    let c_name = CString::new(name)?;
+
+    let find_method = match unsafe { (gir.g_base_info_get_type)(container.0) } {
+        GI_INFO_TYPE_OBJECT => gir.g_object_info_find_method,
+        GI_INFO_TYPE_INTERFACE => gir.g_interface_info_find_method,
+        GI_INFO_TYPE_STRUCT | GI_INFO_TYPE_BOXED => gir.g_struct_info_find_method,
+        GI_INFO_TYPE_UNION => gir.g_union_info_find_method,
+        _ => bail!("{} has no methods", container.name(gir)),
+    };
+
+    Ok(BaseInfo::new(unsafe {
+        find_method(container.0, c_name.as_ptr())
+    }))
+}
+
+/// Derives a type descriptor from a `GITypeInfo`.
+///
+/// `offset` is the index of the first C argument in the call's arguments,
+/// used to locate array length arguments.
+fn type_from_info(
+    gir: &Girepository,
+    type_info: &BaseInfo,
+    transfer: c_int,
+    offset: usize,
+) -> anyhow::Result<Type> {
+    let is_borrowed = transfer == GI_TRANSFER_NOTHING;
+    let is_pointer = unsafe { (gir.g_type_info_is_pointer)(type_info.0) } != 0;
+    // Container transfer hands over the container but not its items.
+    let item_transfer = match transfer {
+        GI_TRANSFER_EVERYTHING => GI_TRANSFER_EVERYTHING,
+        _ => GI_TRANSFER_NOTHING,
+    };
+
+    let param_type = |n: c_int| -> anyhow::Result<Type> {
+        let Some(param_info) =
+            BaseInfo::new(unsafe { (gir.g_type_info_get_param_type)(type_info.0, n) })
+        else {
+            bail!("Missing item type");
+        };
+
+        type_from_info(gir, &param_info, item_transfer, offset)
+    };
+
+    let integer =
+        |size, sign| -> anyhow::Result<Type> { Ok(Type::Integer(IntegerType::new(size, sign))) };
+
+    match unsafe { (gir.g_type_info_get_tag)(type_info.0) } {
+        GI_TYPE_TAG_VOID if is_pointer => bail!("Untyped pointers are not supported"),
+        GI_TYPE_TAG_VOID => Ok(Type::Undefined),
+        GI_TYPE_TAG_BOOLEAN => Ok(Type::Boolean),
+        GI_TYPE_TAG_INT8 => integer(IntegerSize::_8, IntegerSign::Signed),
+        GI_TYPE_TAG_UINT8 => integer(IntegerSize::_8, IntegerSign::Unsigned),
+        GI_TYPE_TAG_INT16 => integer(IntegerSize::_16, IntegerSign::Signed),
+        GI_TYPE_TAG_UINT16 => integer(IntegerSize::_16, IntegerSign::Unsigned),
+        GI_TYPE_TAG_INT32 => integer(IntegerSize::_32, IntegerSign::Signed),
+        GI_TYPE_TAG_UINT32 | GI_TYPE_TAG_UNICHAR => {
+            integer(IntegerSize::_32, IntegerSign::Unsigned)
+        }
+        GI_TYPE_TAG_INT64 => integer(IntegerSize::_64, IntegerSign::Signed),
+        GI_TYPE_TAG_UINT64 | GI_TYPE_TAG_GTYPE => integer(IntegerSize::_64, IntegerSign::Unsigned),
+        GI_TYPE_TAG_FLOAT => Ok(Type::Float(FloatType::new(FloatSize::_32))),
+        GI_TYPE_TAG_DOUBLE => Ok(Type::Float(FloatType::new(FloatSize::_64))),
+        GI_TYPE_TAG_UTF8 | GI_TYPE_TAG_FILENAME => Ok(Type::String(StringType::new(is_borrowed))),
+        GI_TYPE_TAG_ARRAY => {
+            let item_type = param_type(0)?;
+            let mut array_type = ArrayType::new(item_type);
+            array_type.is_borrowed = is_borrowed;
+
+            array_type.list_type = match unsafe { (gir.g_type_info_get_array_type)(type_info.0) } {
+                GI_ARRAY_TYPE_C => ListType::Array,
+                GI_ARRAY_TYPE_ARRAY => ListType::GArray,
+                GI_ARRAY_TYPE_PTR_ARRAY => ListType::PtrArray,
+                GI_ARRAY_TYPE_BYTE_ARRAY => ListType::ByteArray,
+                array_type => bail!("Unknown array type {}", array_type),
+            };
+
+            if array_type.list_type == ListType::Array {
+                let length = unsafe { (gir.g_type_info_get_array_length)(type_info.0) };
+                let fixed_size = unsafe { (gir.g_type_info_get_array_fixed_size)(type_info.0) };
+
+                array_type.length = if length >= 0 {
+                    Some(ArrayLength::Arg(length as usize + offset))
+                } else if fixed_size >= 0 {
+                    Some(ArrayLength::Fixed(fixed_size as usize))
+                } else {
+                    None
+                };
+
+                if array_type.length.is_some() && type_layout(&array_type.item_type).is_none() {
+                    bail!(
+                        "Unsupported item type for sized array: {:?}",
+                        array_type.item_type
+                    );
+                }
+            }
+
+            Ok(Type::Array(array_type))
+        }
+        GI_TYPE_TAG_GLIST | GI_TYPE_TAG_GSLIST => {
+            let mut array_type = ArrayType::new(param_type(0)?);
+            array_type.is_borrowed = is_borrowed;
+            array_type.list_type = match unsafe { (gir.g_type_info_get_tag)(type_info.0) } {
+                GI_TYPE_TAG_GLIST => ListType::GList,
+                _ => ListType::GSList,
+            };
+
+            Ok(Type::Array(array_type))
+        }
+        GI_TYPE_TAG_GHASH => Ok(Type::HashTable(HashTableType::new(
+            param_type(0)?,
+            param_type(1)?,
+            is_borrowed,
+        ))),
+        GI_TYPE_TAG_INTERFACE => {
+            let Some(interface) =
+                BaseInfo::new(unsafe { (gir.g_type_info_get_interface)(type_info.0) })
+            else {
+                bail!("Missing interface type");
+            };
+
+            registered_type(gir, &interface, is_borrowed, is_pointer)
+        }
+        tag => bail!("Unsupported type tag {}", tag),
+    }
+}
+
+/// Derives a type descriptor for an object, struct, enum or other
+/// registered type.
+fn registered_type(
+    gir: &Girepository,
+    info: &BaseInfo,
+    is_borrowed: bool,
+    is_pointer: bool,
+) -> anyhow::Result<Type> {
+    let info_type = unsafe { (gir.g_base_info_get_type)(info.0) };
+
+    if info_type == GI_INFO_TYPE_CALLBACK {
+        bail!("Callback {} is not supported", info.name(gir));
+    }
+
+    let type_name = unsafe { c_string((gir.g_registered_type_info_get_type_name)(info.0)) };
+    // Fetching the GType registers it, so it can be looked up by name later.
+    let gtype = unsafe { glib::Type::from_glib((gir.g_registered_type_info_get_g_type)(info.0)) };
+    let is_registered = gtype.is_valid() && gtype != glib::Type::UNIT;
+
+    match info_type {
+        GI_INFO_TYPE_OBJECT | GI_INFO_TYPE_INTERFACE => {
+            Ok(Type::GObject(GObjectType::new(is_borrowed)))
+        }
+        GI_INFO_TYPE_STRUCT | GI_INFO_TYPE_BOXED | GI_INFO_TYPE_UNION => {
+            if !is_pointer {
+                bail!("{} passed by value is not supported", info.name(gir));
+            }
+
+            if gtype == glib::Type::VARIANT {
+                Ok(Type::Variant(VariantType::new(None, is_borrowed)))
+            } else if gtype == glib::Bytes::static_type() {
+                Ok(Type::Bytes(BytesType::new(
+                    BytesKind::GBytes,
+                    is_borrowed,
+                    None,
+                )))
+            } else {
+                Ok(Type::Boxed(BoxedType::new(
+                    is_borrowed,
+                    type_name,
+                    None,
+                    None,
+                )))
+            }
+        }
+        GI_INFO_TYPE_ENUM if is_registered => {
+            Ok(Type::Enum(EnumType::new(type_name, None, None, false)))
+        }
+        GI_INFO_TYPE_FLAGS if is_registered => {
+            Ok(Type::Flags(FlagsType::new(type_name, None, None, false)))
+        }
+        GI_INFO_TYPE_ENUM => Ok(Type::Integer(IntegerType::new(
+            IntegerSize::_32,
+            IntegerSign::Signed,
+        ))),
+        GI_INFO_TYPE_FLAGS => Ok(Type::Integer(IntegerType::new(
+            IntegerSize::_32,
+            IntegerSign::Unsigned,
+        ))),
+        _ => bail!(
+            "{} (GType {}) is not supported",
+            info.name(gir),
+            gtype.into_glib()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_functions_with_transfer() {
+        let function = resolve("GLib", "2.0", "ascii_strup").unwrap();
+
+        assert_eq!(function.symbol, "g_ascii_strup");
+        assert!(matches!(
+            function.args.as_slice(),
+            [
+                IntrospectedArg {
+                    type_: Type::String(_),
+                    ..
+                },
+                IntrospectedArg {
+                    type_: Type::Integer(_),
+                    ..
+                },
+            ]
+        ));
+        assert!(matches!(function.return_type, Type::String(s) if !s.is_borrowed));
+    }
+
+    #[test]
+    fn resolves_methods_with_instance_and_out_parameters() {
+        let function = resolve("GLib", "2.0", "DateTime.get_ymd").unwrap();
+
+        assert_eq!(function.symbol, "g_date_time_get_ymd");
+        assert_eq!(function.args.len(), 4);
+        assert!(matches!(&function.args[0].type_, Type::Boxed(boxed) if boxed.is_borrowed));
+        assert!(
+            function.args[1..]
+                .iter()
+                .all(|arg| matches!(arg.type_, Type::Ref(_)))
+        );
+    }
+
+    #[test]
+    fn appends_error_argument_to_throwing_functions() {
+        let function = resolve("GLib", "2.0", "file_get_contents").unwrap();
+
+        assert!(matches!(
+            function.args.last(),
+            Some(IntrospectedArg {
+                type_: Type::Error,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_functions() {
+        assert!(resolve("GLib", "2.0", "no_such_function").is_err());
+        assert!(resolve("GLib", "2.0", "DateTime.no_such_method").is_err());
+    }
+}
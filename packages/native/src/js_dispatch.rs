@@ -7,12 +7,19 @@
 //!   `process_pending()` is called repeatedly and processes the queue.
 //! - **Asynchronous**: When JavaScript is idle, a wake-up message is sent via a Neon channel,
 //!   which triggers `process_pending()` on the UV event loop.
+//!
+//! In main thread mode callbacks fire on the JS thread while native code runs
+//! nested inside a JS context. Such a context is registered with `with_context()`,
+//! and `process_pending_in_scope()` uses the innermost one to run callbacks directly.
 
-use std::sync::{Arc, mpsc};
+use std::{
+    cell::RefCell,
+    sync::{Arc, mpsc},
+};
 
 use neon::prelude::*;
 
-use crate::{queue::Queue, value::Value};
+use crate::{notify, queue::Queue, types::CoalescePolicy, value::Value, watchdog};
 
 /// A pending callback waiting to be executed on the JS thread.
 pub struct PendingCallback {
@@ -24,10 +31,86 @@ pub struct PendingCallback {
     pub capture_result: bool,
     /// Channel to send the result back to the GTK thread.
     pub result_tx: mpsc::Sender<Result<Value, ()>>,
+    /// How later emissions of the callback are merged into this one while
+    /// it is queued.
+    pub coalesce: Option<CoalescePolicy>,
 }
 
 static QUEUE: Queue<PendingCallback> = Queue::new();
 
+type Processor = *mut (dyn FnMut() + 'static);
+
+thread_local! {
+    /// Processors of pending callbacks for the JS contexts registered with
+    /// `with_context()`, innermost last. A processor is taken while it runs.
+    static PROCESSORS: RefCell<Vec<Option<Processor>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Unregisters the processors from `depth` up when dropped, including while
+/// unwinding, so that none outlives the context it borrows.
+struct ProcessorGuard {
+    depth: usize,
+}
+
+impl Drop for ProcessorGuard {
+    fn drop(&mut self) {
+        PROCESSORS.with_borrow_mut(|processors| processors.truncate(self.depth));
+    }
+}
+
+/// Runs `f` with `cx` registered for processing callbacks fired during it.
+///
+/// Only has an effect in main thread mode, where `process_pending_in_scope()`
+/// is called for callbacks fired on the JS thread.
+pub fn with_context<'a, C: Context<'a>, R>(cx: &mut C, f: impl FnOnce() -> R) -> R {
+    let mut process = || process_pending(cx);
+    let process: *mut (dyn FnMut() + '_) = &mut process;
+    // SAFETY: Erasing the lifetime is sound because the pointer never outlives
+    // `process`, and `process` is never used while another context is active:
+    // - The guard is created after `process`, so it is dropped first, on
+    //   return or while unwinding, and removes the pointer from `PROCESSORS`
+    //   before `process` and the borrow of `cx` end.
+    // - `PROCESSORS` is thread-local, so the pointer is only called on this
+    //   thread, while `f` runs.
+    // - `process_pending_in_scope()` takes the processor out of its slot while
+    //   calling it. A JS context nested inside it, entered by a callback,
+    //   therefore never reaches this processor; it either registers its own
+    //   or finds the slot empty.
+    let process = unsafe { std::mem::transmute::<*mut (dyn FnMut() + '_), Processor>(process) };
+
+    let _guard = PROCESSORS.with_borrow_mut(|processors| {
+        processors.push(Some(process));
+        ProcessorGuard {
+            depth: processors.len() - 1,
+        }
+    });
+
+    f()
+}
+
+/// Processes pending callbacks with the innermost context registered with
+/// `with_context()`.
+///
+/// Returns `false` without processing anything if there is no such context,
+/// or if it is already processing callbacks further up the stack.
+pub fn process_pending_in_scope() -> bool {
+    let index_and_process = PROCESSORS.with_borrow_mut(|processors| {
+        let index = processors.len().checked_sub(1)?;
+        processors[index].take().map(|process| (index, process))
+    });
+
+    let Some((index, process)) = index_and_process else {
+        return false;
+    };
+
+    // SAFETY: The processor is registered, so its context is still alive, and
+    // it was taken so that it is never entered twice.
+    unsafe { (*process)() };
+
+    PROCESSORS.with_borrow_mut(|processors| processors[index] = Some(process));
+    true
+}
+
 /// Queues a callback for execution on the JS thread.
 ///
 /// The callback is added to a queue that will be processed either:
@@ -45,11 +128,66 @@ pub fn queue(
         args,
         capture_result,
         result_tx: tx,
+        coalesce: None,
     });
+    notify::JS_THREAD.notify();
 
     rx
 }
 
+/// Queues an emission of a coalesced callback, whose result is not captured.
+///
+/// If the last queued callback is an emission of the same callback, the new
+/// one is merged into it, so emissions are never reordered around other
+/// queued callbacks: with [`CoalescePolicy::Latest`] its arguments replace the queued
+/// ones, and with [`CoalescePolicy::Batch`] they are appended to the array of
+/// argument arrays the callback receives. Returns `true` if the emission was
+/// queued on its own; a merged one is processed with the queued emission.
+pub fn queue_coalesced(
+    callback: Arc<Root<JsFunction>>,
+    args: Vec<Value>,
+    policy: CoalescePolicy,
+) -> bool {
+    let args = match policy {
+        CoalescePolicy::Latest => args,
+        CoalescePolicy::Batch => vec![Value::Array(vec![Value::Array(args)])],
+    };
+
+    let (tx, _) = mpsc::channel();
+    let pending = PendingCallback {
+        callback,
+        args,
+        capture_result: false,
+        result_tx: tx,
+        coalesce: Some(policy),
+    };
+
+    let queued = QUEUE.push_or_merge(pending, |queued, pending| {
+        if queued.coalesce != Some(policy) || !Arc::ptr_eq(&queued.callback, &pending.callback) {
+            return Err(pending);
+        }
+
+        match policy {
+            CoalescePolicy::Latest => queued.args = pending.args,
+            CoalescePolicy::Batch => {
+                if let (Some(Value::Array(batch)), Some(Value::Array(emissions))) =
+                    (queued.args.first_mut(), pending.args.into_iter().next())
+                {
+                    batch.extend(emissions);
+                }
+            }
+        }
+
+        Ok(())
+    });
+
+    if queued {
+        notify::JS_THREAD.notify();
+    }
+
+    queued
+}
+
 /// Queues a callback and sends a wake-up message via the channel.
 ///
 /// Use this when JS might be idle (not in a wait loop). The channel message
@@ -61,27 +199,29 @@ pub fn queue_with_wakeup(
     capture_result: bool,
 ) -> mpsc::Receiver<Result<Value, ()>> {
     let rx = queue(callback, args, capture_result);
+    wake(channel);
+    rx
+}
 
+/// Sends a wake-up message that processes the queue on the UV event loop.
+pub fn wake(channel: &Channel) {
     channel.send(|mut cx| {
         process_pending(&mut cx);
         Ok(())
     });
-
-    rx
 }
 
 /// Processes all pending callbacks using the provided context.
 ///
 /// This should be called from the JS thread's wait loop while waiting for
 /// GTK dispatch results. Each callback is executed synchronously and its result
-/// is sent back through the callback's result channel.
+/// is sent back through the callback's result channel, unless the caller has
+/// stopped waiting for it.
 pub fn process_pending<'a, C: Context<'a>>(cx: &mut C) {
     while let Some(pending) = QUEUE.pop() {
         let result = execute_callback(cx, &pending.callback, &pending.args, pending.capture_result);
-        pending
-            .result_tx
-            .send(result)
-            .expect("Pending callback result channel disconnected");
+        let _ = pending.result_tx.send(result);
+        notify::GTK_THREAD.notify();
     }
 }
 
@@ -100,6 +240,18 @@ fn execute_callback<'a, C: Context<'a>>(
     let js_this = cx.undefined();
     let js_callback = callback.to_inner(cx);
 
+    let _activity = watchdog::JS_THREAD.enter(|| {
+        let name = js_callback
+            .get_value(cx, "name")
+            .ok()
+            .and_then(|name| name.downcast::<JsString, _>(cx).ok())
+            .map(|name| name.value(cx))
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "anonymous".to_string());
+
+        format!("running callback {name}")
+    });
+
     if capture_result {
         let js_result = js_callback.call(cx, js_this, js_args).map_err(|_| ())?;
         Value::from_js_value(cx, js_result).map_err(|_| ())
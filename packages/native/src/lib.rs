@@ -10,14 +10,23 @@ mod arg;
 mod boxed;
 mod callback;
 mod cif;
+mod config;
+mod error;
+mod frame;
 mod gtk_dispatch;
+mod introspection;
 mod js_dispatch;
+mod main_context;
 mod module;
+mod notify;
 mod object;
 mod queue;
 mod state;
 mod types;
 mod value;
+mod variant;
+mod watchdog;
+mod worker;
 
 #[cfg(test)]
 mod test_utils;
@@ -30,22 +39,38 @@ use neon::prelude::*;
 /// - `start`: Initialize GTK application and start the main loop
 /// - `stop`: Stop the GTK main loop
 /// - `call`: Invoke a native function via FFI
-/// - `batchCall`: Execute multiple void FFI calls in a single dispatch
+/// - `callAsync`: Invoke a native function via FFI, returning a Promise
+/// - `callAsyncOp`: Run an asynchronous GIO operation and its finish function, returning a Promise
+/// - `callIntrospected`: Invoke a function resolved from a GObject-Introspection typelib
+/// - `define`: Parse and resolve a native function once for repeated calls
+/// - `invoke`: Call a function defined with `define`
+/// - `batchCall`: Execute multiple FFI calls in a single dispatch
+/// - `batchCallAsync`: Execute multiple FFI calls in a single dispatch, returning a Promise
+/// - `requestFrameCommit`: Execute multiple FFI calls in the next frame, returning a Promise
 /// - `read`: Read a field from a native object
 /// - `write`: Write a field to a native object
 /// - `alloc`: Allocate memory for a boxed type
 /// - `getObjectId`: Get the native pointer address for an object
 /// - `poll`: Process pending JS callbacks (for runtimes without proper channel support)
+/// - `configure`: Set the watchdog timeout and the maximum re-entrancy depth
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("start", module::start)?;
     cx.export_function("stop", module::stop)?;
     cx.export_function("call", module::call)?;
+    cx.export_function("callAsync", module::call_async)?;
+    cx.export_function("callAsyncOp", module::call_async_op)?;
+    cx.export_function("callIntrospected", module::call_introspected)?;
+    cx.export_function("define", module::define)?;
+    cx.export_function("invoke", module::invoke)?;
     cx.export_function("batchCall", module::batch_call)?;
+    cx.export_function("batchCallAsync", module::batch_call_async)?;
+    cx.export_function("requestFrameCommit", module::request_frame_commit)?;
     cx.export_function("read", module::read)?;
     cx.export_function("write", module::write)?;
     cx.export_function("alloc", module::alloc)?;
     cx.export_function("getObjectId", module::get_object_id)?;
     cx.export_function("poll", module::poll)?;
+    cx.export_function("configure", module::configure)?;
     Ok(())
 }
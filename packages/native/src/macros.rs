@@ -1,29 +1,32 @@
 macro_rules! dispatch_integer_read {
     ($int_type:expr, $ptr:expr) => {
+        dispatch_integer_read!($int_type, $ptr, f64)
+    };
+    ($int_type:expr, $ptr:expr, $target:ty) => {
         match ($int_type.size, $int_type.sign) {
             (IntegerSize::_8, IntegerSign::Signed) => unsafe {
-                $ptr.cast::<i8>().read_unaligned() as f64
+                $ptr.cast::<i8>().read_unaligned() as $target
             },
             (IntegerSize::_8, IntegerSign::Unsigned) => unsafe {
-                $ptr.cast::<u8>().read_unaligned() as f64
+                $ptr.cast::<u8>().read_unaligned() as $target
             },
             (IntegerSize::_16, IntegerSign::Signed) => unsafe {
-                $ptr.cast::<i16>().read_unaligned() as f64
+                $ptr.cast::<i16>().read_unaligned() as $target
             },
             (IntegerSize::_16, IntegerSign::Unsigned) => unsafe {
-                $ptr.cast::<u16>().read_unaligned() as f64
+                $ptr.cast::<u16>().read_unaligned() as $target
             },
             (IntegerSize::_32, IntegerSign::Signed) => unsafe {
-                $ptr.cast::<i32>().read_unaligned() as f64
+                $ptr.cast::<i32>().read_unaligned() as $target
             },
             (IntegerSize::_32, IntegerSign::Unsigned) => unsafe {
-                $ptr.cast::<u32>().read_unaligned() as f64
+                $ptr.cast::<u32>().read_unaligned() as $target
             },
             (IntegerSize::_64, IntegerSign::Signed) => unsafe {
-                $ptr.cast::<i64>().read_unaligned() as f64
+                $ptr.cast::<i64>().read_unaligned() as $target
             },
             (IntegerSize::_64, IntegerSign::Unsigned) => unsafe {
-                $ptr.cast::<u64>().read_unaligned() as f64
+                $ptr.cast::<u64>().read_unaligned() as $target
             },
         }
     };
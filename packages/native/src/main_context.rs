@@ -0,0 +1,148 @@
+//! Driving the GLib main context from the Node.js event loop.
+//!
+//! In main thread mode GTK runs on the JS thread, which owns GLib's default
+//! `GMainContext` but never blocks in it. Instead, a watcher thread polls the
+//! file descriptors the context reports, and when one becomes ready or the
+//! context's timeout expires, it hands the polled descriptors back to the JS
+//! thread through a Neon channel. The JS thread then completes GLib's
+//! prepare, query, check and dispatch cycle, prepares and queries the context
+//! again and hands the new descriptors back to the watcher.
+//!
+//! Sources attached from the JS thread do not interrupt the watcher on their
+//! own, so tasks run in main thread mode wake the context afterwards. The
+//! wakeup descriptor is among those being polled, so the watcher returns and
+//! the new sources are picked up.
+
+use std::{cell::RefCell, mem, sync::mpsc, thread};
+
+use gtk4::glib;
+use neon::prelude::*;
+
+use crate::js_dispatch;
+
+/// The descriptors and timeout the watcher thread waits on, from one
+/// prepare and query of the context.
+struct PollRequest {
+    max_priority: i32,
+    fds: Vec<glib::ffi::GPollFD>,
+    timeout: i32,
+}
+
+thread_local! {
+    /// Sender of poll requests to the watcher thread, while attached.
+    static WATCHER: RefCell<Option<mpsc::Sender<PollRequest>>> = const { RefCell::new(None) };
+}
+
+/// Starts driving the default main context from the Node.js event loop.
+///
+/// Must be called on the JS thread after it has acquired the default main
+/// context. The channel is kept referenced so that Node.js keeps running
+/// until [`detach`] is called.
+pub fn attach(channel: Channel) {
+    let (tx, rx) = mpsc::channel::<PollRequest>();
+
+    thread::spawn(move || watch(rx, channel));
+
+    WATCHER.with_borrow_mut(|watcher| *watcher = Some(tx));
+    request_poll();
+}
+
+/// Stops driving the default main context and lets the watcher thread exit.
+pub fn detach() {
+    if WATCHER.with_borrow_mut(Option::take).is_some() {
+        glib::MainContext::default().wakeup();
+    }
+}
+
+fn watch(requests: mpsc::Receiver<PollRequest>, channel: Channel) {
+    for mut request in requests {
+        unsafe {
+            glib::ffi::g_poll(
+                request.fds.as_mut_ptr(),
+                request.fds.len() as u32,
+                request.timeout,
+            );
+        }
+
+        channel.send(move |mut cx| {
+            iterate(&mut cx, request);
+            Ok(())
+        });
+    }
+}
+
+/// Checks the default main context against the polled descriptors,
+/// dispatches the sources that are ready and polls again. Callbacks fired by
+/// the dispatched sources run in `cx`.
+fn iterate<'a, C: Context<'a>>(cx: &mut C, mut request: PollRequest) {
+    if WATCHER.with_borrow(Option::is_none) {
+        return;
+    }
+
+    let context = glib::MainContext::default();
+    let context_ptr = context.as_ptr();
+
+    js_dispatch::with_context(cx, || unsafe {
+        let is_ready = glib::ffi::g_main_context_check(
+            context_ptr,
+            request.max_priority,
+            request.fds.as_mut_ptr(),
+            request.fds.len() as i32,
+        ) != 0;
+
+        if is_ready {
+            glib::ffi::g_main_context_dispatch(context_ptr);
+        }
+    });
+    request_poll();
+}
+
+/// Prepares and queries the default main context and sends its descriptors
+/// and timeout to the watcher thread.
+///
+/// The cycle is completed by [`iterate`] once the watcher has polled them.
+fn request_poll() {
+    let context = glib::MainContext::default();
+    let context_ptr = context.as_ptr();
+
+    let mut max_priority = 0;
+    let mut timeout = 0;
+    let mut fds: Vec<glib::ffi::GPollFD> = Vec::new();
+
+    unsafe {
+        let ready = glib::ffi::g_main_context_prepare(context_ptr, &mut max_priority) != 0;
+
+        loop {
+            let n_fds = glib::ffi::g_main_context_query(
+                context_ptr,
+                max_priority,
+                &mut timeout,
+                fds.as_mut_ptr(),
+                fds.len() as i32,
+            ) as usize;
+
+            if n_fds <= fds.len() {
+                fds.truncate(n_fds);
+                break;
+            }
+
+            fds.resize(n_fds, mem::zeroed());
+        }
+
+        // Sources that are already ready are dispatched on the next turn of
+        // the event loop without waiting.
+        if ready {
+            timeout = 0;
+        }
+    }
+
+    WATCHER.with_borrow(|watcher| {
+        if let Some(watcher) = watcher {
+            let _ = watcher.send(PollRequest {
+                max_priority,
+                fds,
+                timeout,
+            });
+        }
+    });
+}
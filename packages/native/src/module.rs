@@ -5,6 +5,8 @@
 
 mod alloc;
 mod call;
+mod configure;
+mod define;
 mod object;
 mod poll;
 mod read;
@@ -14,6 +16,8 @@ mod write;
 
 pub use alloc::*;
 pub use call::*;
+pub use configure::*;
+pub use define::*;
 pub use object::*;
 pub use poll::*;
 pub use read::*;
@@ -1,17 +1,16 @@
 //! Memory allocation for boxed types.
 
-use std::sync::mpsc;
-
 use gtk4::glib::ffi::g_malloc0;
 use neon::prelude::*;
 
 use crate::{
     boxed::Boxed,
-    gtk_dispatch,
     object::{Object, ObjectId},
     types::BoxedType,
 };
 
+use super::call::run_on_gtk_thread;
+
 /// Allocates memory for a boxed type.
 ///
 /// JavaScript signature: `alloc(size: number, typeName: string, libName?: string) => ObjectId`
@@ -25,17 +24,9 @@ pub fn alloc(mut cx: FunctionContext) -> JsResult<JsValue> {
         .and_then(|v| v.downcast::<JsString, _>(&mut cx).ok())
         .map(|s| s.value(&mut cx));
 
-    let (tx, rx) = mpsc::channel::<anyhow::Result<ObjectId>>();
-
-    gtk_dispatch::schedule(move || {
-        tx.send(handle_alloc(size, &type_name, lib_name.as_deref()))
-            .expect("Alloc result channel disconnected");
-    });
-
-    let object_id = rx
-        .recv()
-        .or_else(|err| cx.throw_error(format!("Error receiving alloc result: {err}")))?
-        .or_else(|err| cx.throw_error(format!("Error during alloc: {err}")))?;
+    let object_id = run_on_gtk_thread(&mut cx, "Error during alloc", move || {
+        handle_alloc(size, &type_name, lib_name.as_deref())
+    })?;
 
     Ok(cx.boxed(object_id).upcast())
 }
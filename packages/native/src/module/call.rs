@@ -2,51 +2,232 @@
 
 use std::{
     ffi::{c_char, c_void},
-    ops::Deref,
-    sync::{Arc, mpsc},
+    sync::{Arc, Mutex, mpsc},
 };
 
 use anyhow::bail;
+use gtk4::glib;
 use libffi::middle as libffi;
-use neon::prelude::*;
+use neon::{object::Object as _, prelude::*, types::Deferred};
 
 use crate::{
     arg::Arg,
-    cif, gtk_dispatch, js_dispatch,
+    cif, config,
+    error::NativeError,
+    frame,
+    gtk_dispatch::{self, Priority},
+    js_dispatch, notify,
+    object::{Object, ObjectId},
     state::GtkThreadState,
-    types::{CallbackTrampoline, FloatSize, IntegerSign, IntegerSize, Type},
+    types::{
+        ArrayLength, CallbackTrampoline, CallbackType, FloatSize, GObjectType, IntegerSign,
+        IntegerSize, RefType, Type,
+    },
     value::Value,
+    watchdog, worker,
 };
 
-type RefUpdate = (Arc<Root<JsObject>>, Value);
+pub(super) type RefUpdate = (Arc<Root<JsObject>>, Value);
+
+/// The return value and ref updates of a call.
+type CallResult = anyhow::Result<(Value, Vec<RefUpdate>)>;
+
+/// An `(argument index, call index)` pair for an argument taking the result
+/// of an earlier call in a batch.
+type ResultRef = (usize, usize);
+
+/// The thread a call runs on, chosen with the `thread` call option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum CallThread {
+    /// The GTK thread, where calls run by default.
+    #[default]
+    Gtk,
+    /// A worker thread, for thread-safe functions that would block rendering.
+    Worker,
+}
+
+/// Options for a call, given as an optional last argument.
+#[derive(Debug, Clone, Copy, Default)]
+struct CallOptions {
+    /// The thread the call runs on.
+    thread: CallThread,
+    /// The priority the call is dispatched at on the GTK thread.
+    priority: Priority,
+}
+
+impl CallOptions {
+    /// Reads the call options from the optional argument at `index`.
+    fn from_argument(cx: &mut FunctionContext, index: usize) -> NeonResult<Self> {
+        let Some(options) = cx.argument_opt(index) else {
+            return Ok(CallOptions::default());
+        };
+
+        if options.is_a::<JsUndefined, _>(cx) {
+            return Ok(CallOptions::default());
+        }
+
+        let options = options.downcast_or_throw::<JsObject, _>(cx)?;
+        let thread: Option<Handle<JsString>> = options.get_opt(cx, "thread")?;
+        let priority: Option<Handle<JsString>> = options.get_opt(cx, "priority")?;
+
+        let thread = match thread.map(|thread| thread.value(cx)).as_deref() {
+            None | Some("gtk") => CallThread::Gtk,
+            Some("worker") => CallThread::Worker,
+            Some(thread) => {
+                return cx.throw_type_error(format!(
+                    "thread must be \"gtk\" or \"worker\", got \"{thread}\""
+                ));
+            }
+        };
+
+        let priority = match priority.map(|priority| priority.value(cx)).as_deref() {
+            None | Some("idle") => Priority::Idle,
+            Some("default") => Priority::Default,
+            Some("high") => Priority::High,
+            Some(priority) => {
+                return cx.throw_type_error(format!(
+                    "priority must be \"high\", \"default\" or \"idle\", got \"{priority}\""
+                ));
+            }
+        };
+
+        Ok(CallOptions { thread, priority })
+    }
+}
+
+/// How the finish function of an asynchronous operation is called, given as
+/// the optional last argument of `callAsyncOp`.
+#[derive(Debug, Clone)]
+struct AsyncOpFinish {
+    /// Whether the source object is passed before the `GAsyncResult`, as for
+    /// methods. Constructors such as `g_dbus_proxy_new_finish` take none.
+    source: bool,
+    /// Arguments passed after the `GAsyncResult`, such as out-parameters.
+    args: Vec<Arg>,
+}
+
+impl AsyncOpFinish {
+    /// Reads the finish description from the optional argument at `index`.
+    fn from_argument(cx: &mut FunctionContext, index: usize) -> NeonResult<Self> {
+        let mut finish = AsyncOpFinish {
+            source: true,
+            args: Vec::new(),
+        };
+
+        let Some(options) = cx.argument_opt(index) else {
+            return Ok(finish);
+        };
+
+        if options.is_a::<JsUndefined, _>(cx) {
+            return Ok(finish);
+        }
+
+        let options = options.downcast_or_throw::<JsObject, _>(cx)?;
+        let source: Option<Handle<JsBoolean>> = options.get_opt(cx, "source")?;
+        let args: Option<Handle<JsArray>> = options.get_opt(cx, "args")?;
+
+        if let Some(source) = source {
+            finish.source = source.value(cx);
+        }
+
+        if let Some(args) = args {
+            finish.args = Arg::from_js_array(cx, args)?;
+        }
+
+        Ok(finish)
+    }
+}
 
 struct BatchCallDescriptor {
     library_name: String,
     symbol_name: String,
     args: Vec<Arg>,
+    result_type: Type,
+    /// Arguments taking the result of an earlier call.
+    result_refs: Vec<ResultRef>,
+}
+
+/// Schedules a task on the GTK thread and waits for its result.
+///
+/// Errors are thrown as by [`throw_call_error`] with the given context.
+pub(super) fn run_on_gtk_thread<'a, C, R, F>(cx: &mut C, context: &str, task: F) -> NeonResult<R>
+where
+    C: Context<'a>,
+    R: Send + 'static,
+    F: FnOnce() -> anyhow::Result<R> + Send + 'static,
+{
+    wait_for_dispatch(cx, context, |tx| {
+        gtk_dispatch::schedule(move || {
+            let _ = tx.send(task());
+        });
+    })
+}
+
+/// Dispatches work that sends its result through the given sender, and waits
+/// for the result.
+///
+/// This is the wait path of every synchronous export. The JS thread is marked
+/// as waiting before `dispatch` runs, so that signals fired by the work invoke
+/// their callbacks synchronously instead of deadlocking, and the nesting depth
+/// is checked first. Errors are thrown as by [`throw_call_error`] with the
+/// given context.
+pub(super) fn wait_for_dispatch<'a, C, R, F>(
+    cx: &mut C,
+    context: &str,
+    dispatch: F,
+) -> NeonResult<R>
+where
+    C: Context<'a>,
+    F: FnOnce(mpsc::Sender<anyhow::Result<R>>),
+{
+    let (tx, rx) = mpsc::channel::<anyhow::Result<R>>();
+
+    check_reentrancy_depth(cx)?;
+    gtk_dispatch::enter_js_wait();
+    // In main thread mode the task runs inline, and callbacks it fires run in this context.
+    js_dispatch::with_context(cx, || dispatch(tx));
+
+    wait_for_result(cx, &rx).or_else(|err| throw_call_error(cx, context, err))
 }
 
 /// Waits for a result from the GTK thread while processing JS dispatches.
 ///
-/// This spins on the receiver, processing any pending JS dispatches using the
-/// provided context. This enables synchronous callback invocation from GTK
-/// signal handlers during re-entrant calls.
+/// This sleeps until the GTK thread sends the result or queues a JS dispatch,
+/// processing pending JS dispatches using the provided context. This enables
+/// synchronous callback invocation from GTK signal handlers during re-entrant
+/// calls.
 ///
 /// IMPORTANT: Callers must call `gtk_dispatch::enter_js_wait()` BEFORE scheduling
 /// the task to the GTK thread. This ensures that any signals triggered by the task
 /// see `is_js_waiting() = true` and use the synchronous queue path. This function
 /// calls `exit_js_wait()` when done.
+///
+/// If the watchdog is configured to throw, a wait exceeding its timeout is
+/// abandoned with an error while the task keeps running.
 fn wait_for_result<'a, R, C: Context<'a>>(
     cx: &mut C,
     rx: &mpsc::Receiver<anyhow::Result<R>>,
 ) -> anyhow::Result<R> {
+    let _activity = watchdog::JS_THREAD.enter(|| "waiting for the GTK thread".to_string());
+    let mut wait = watchdog::Wait::new(&watchdog::JS_THREAD);
+
     let result = loop {
+        let token = notify::JS_THREAD.token();
         js_dispatch::process_pending(cx);
 
+        // In main thread mode, tasks that worker threads schedule on the GTK
+        // thread can only run here.
+        if gtk_dispatch::is_main_thread() {
+            gtk_dispatch::dispatch_pending();
+        }
+
         match rx.try_recv() {
             Ok(result) => break result,
             Err(mpsc::TryRecvError::Empty) => {
-                std::thread::yield_now();
+                if let Err(err) = wait.sleep(&notify::JS_THREAD, token) {
+                    gtk_dispatch::exit_js_wait();
+                    return Err(err);
+                }
             }
             Err(mpsc::TryRecvError::Disconnected) => {
                 gtk_dispatch::exit_js_wait();
@@ -59,73 +240,483 @@ fn wait_for_result<'a, R, C: Context<'a>>(
     result
 }
 
+/// Throws if another nested synchronous call would exceed the configured
+/// maximum re-entrancy depth.
+///
+/// Each synchronous call made from a JS callback that was itself fired during
+/// a synchronous call nests one level deeper, so runaway JS → GTK → JS
+/// recursion is stopped with a `RangeError` instead of exhausting the stack.
+fn check_reentrancy_depth<'a, C: Context<'a>>(cx: &mut C) -> NeonResult<()> {
+    if let Some(max_depth) = config::max_reentrancy_depth()
+        && gtk_dispatch::js_wait_depth() >= max_depth
+    {
+        return cx.throw_range_error(format!("Maximum re-entrancy depth of {max_depth} exceeded"));
+    }
+
+    Ok(())
+}
+
+/// Throws a failed call's error as a JavaScript exception.
+///
+/// A [`NativeError`] reported through a `GError**` argument is thrown as a
+/// structured error; any other error is thrown with the given context prefix.
+pub(super) fn throw_call_error<'a, C: Context<'a>, T>(
+    cx: &mut C,
+    context: &str,
+    err: anyhow::Error,
+) -> NeonResult<T> {
+    match err.downcast_ref::<NativeError>() {
+        Some(native_error) => {
+            let js_error = native_error.to_js_error(cx)?;
+            cx.throw(js_error)
+        }
+        None => cx.throw_error(format!("{context}: {err}")),
+    }
+}
+
 /// Calls a native function via FFI.
 ///
-/// JavaScript signature: `call(library: string, symbol: string, args: Arg[], returnType: Type) => Value`
+/// JavaScript signature: `call(library: string, symbol: string, args: Arg[], returnType: Type, options?: { thread?: "gtk" | "worker", priority?: "high" | "default" | "idle" }) => Value`
 ///
-/// Dispatches the call to the GTK thread, waits for the result, and updates
-/// any ref (out) parameters.
+/// Dispatches the call to the GTK thread, or to a worker thread with the
+/// `thread: "worker"` option, waits for the result, and updates any ref
+/// (out) parameters. The `priority` option sets the priority at which the
+/// GTK thread runs the call relative to its other work, `"idle"` by default.
 pub fn call(mut cx: FunctionContext) -> JsResult<JsValue> {
     let library_name = cx.argument::<JsString>(0)?.value(&mut cx);
     let symbol_name = cx.argument::<JsString>(1)?.value(&mut cx);
     let js_args = cx.argument::<JsArray>(2)?;
     let js_result_type = cx.argument::<JsObject>(3)?;
+    let options = CallOptions::from_argument(&mut cx, 4)?;
     let args = Arg::from_js_array(&mut cx, js_args)?;
     let result_type = Type::from_js_value(&mut cx, js_result_type.upcast())?;
 
-    let (tx, rx) = mpsc::channel::<anyhow::Result<(Value, Vec<RefUpdate>)>>();
+    if options.thread == CallThread::Worker {
+        worker::check_args(&args).or_else(|err| cx.throw_type_error(err.to_string()))?;
+    }
 
-    gtk_dispatch::enter_js_wait();
-    gtk_dispatch::schedule(move || {
-        let _ = tx.send(handle_call(library_name, symbol_name, args, result_type));
-    });
+    let (value, ref_updates) = wait_for_dispatch(&mut cx, "Error during FFI call", |tx| {
+        let on_result = move |result| {
+            let _ = tx.send(result);
+        };
 
-    let (value, ref_updates) = wait_for_result(&mut cx, &rx)
-        .or_else(|err| cx.throw_error(format!("Error during FFI call: {err}")))?;
+        dispatch_call(
+            options,
+            library_name,
+            symbol_name,
+            args,
+            result_type,
+            on_result,
+        );
+    })?;
 
-    for (js_obj, new_value) in ref_updates {
-        let js_obj = js_obj.to_inner(&mut cx);
-        let new_js_value = new_value.to_js_value(&mut cx)?;
-        let mut prop = js_obj.prop(&mut cx, "value");
+    apply_ref_updates(&mut cx, ref_updates)?;
+    value.to_js_value(&mut cx)
+}
 
-        prop.set(new_js_value)?;
+/// Calls a native function via FFI without blocking the JavaScript thread.
+///
+/// JavaScript signature: `callAsync(library: string, symbol: string, args: Arg[], returnType: Type, options?: { thread?: "gtk" | "worker", priority?: "high" | "default" | "idle" }) => Promise<Value>`
+///
+/// Schedules the call on the GTK thread, or on a worker thread with the
+/// `thread: "worker"` option, and returns a promise that settles with the
+/// result, updating any ref parameters first. Callbacks fired during the call
+/// are delivered through their channels, as for signals.
+pub fn call_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let library_name = cx.argument::<JsString>(0)?.value(&mut cx);
+    let symbol_name = cx.argument::<JsString>(1)?.value(&mut cx);
+    let js_args = cx.argument::<JsArray>(2)?;
+    let js_result_type = cx.argument::<JsObject>(3)?;
+    let options = CallOptions::from_argument(&mut cx, 4)?;
+    let args = Arg::from_js_array(&mut cx, js_args)?;
+    let result_type = Type::from_js_value(&mut cx, js_result_type.upcast())?;
+
+    if options.thread == CallThread::Worker {
+        worker::check_args(&args).or_else(|err| cx.throw_type_error(err.to_string()))?;
     }
 
-    value.to_js_value(&mut cx)
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+
+    js_dispatch::with_context(&mut cx, || {
+        let on_result = move |result: CallResult| {
+            deferred.settle_with(&channel, move |mut cx| match result {
+                Ok((value, ref_updates)) => {
+                    apply_ref_updates(&mut cx, ref_updates)?;
+                    value.to_js_value(&mut cx)
+                }
+                Err(err) => throw_call_error(&mut cx, "Error during FFI call", err),
+            });
+        };
+
+        dispatch_call(
+            options,
+            library_name,
+            symbol_name,
+            args,
+            result_type,
+            on_result,
+        );
+    });
+
+    Ok(promise)
 }
 
-fn handle_call(
+/// Runs a call with the given options and passes its result to `on_result`.
+fn dispatch_call<F>(
+    options: CallOptions,
     library_name: String,
     symbol_name: String,
     args: Vec<Arg>,
     result_type: Type,
-) -> anyhow::Result<(Value, Vec<RefUpdate>)> {
-    let mut arg_types: Vec<libffi::Type> = Vec::with_capacity(args.len() + 1);
-    for arg in &args {
-        match &arg.type_ {
-            Type::Callback(cb) if cb.trampoline != CallbackTrampoline::Closure => {
-                arg_types.push(libffi::Type::pointer());
-                arg_types.push(libffi::Type::pointer());
-
-                if cb.trampoline == CallbackTrampoline::DrawFunc {
-                    arg_types.push(libffi::Type::pointer());
+    on_result: F,
+) where
+    F: FnOnce(CallResult) + Send + 'static,
+{
+    let priority = options.priority;
+
+    match options.thread {
+        CallThread::Gtk => gtk_dispatch::schedule_with_priority(priority, move || {
+            on_result(handle_call(library_name, symbol_name, args, result_type));
+        }),
+        CallThread::Worker if worker::has_objects(&args) => {
+            gtk_dispatch::schedule_with_priority(priority, move || match worker::lend(&args) {
+                Ok(lent) => handle_worker_call(
+                    library_name,
+                    symbol_name,
+                    args,
+                    result_type,
+                    lent,
+                    priority,
+                    on_result,
+                ),
+                Err(err) => on_result(Err(err)),
+            })
+        }
+        CallThread::Worker => handle_worker_call(
+            library_name,
+            symbol_name,
+            args,
+            result_type,
+            worker::Objects::default(),
+            priority,
+            on_result,
+        ),
+    }
+}
+
+/// Runs a call on a worker thread with the objects lent to it.
+///
+/// Objects created by the call are adopted on the GTK thread at the given
+/// priority, which then passes the result to `on_result`. Otherwise the
+/// worker passes it directly.
+fn handle_worker_call<F>(
+    library_name: String,
+    symbol_name: String,
+    args: Vec<Arg>,
+    result_type: Type,
+    lent: worker::Objects,
+    priority: Priority,
+    on_result: F,
+) where
+    F: FnOnce(CallResult) + Send + 'static,
+{
+    worker::schedule(move |scheduled| {
+        if let Err(err) = scheduled {
+            on_result(Err(err));
+            return;
+        }
+
+        let (result, created) = worker::with_lent(lent, || {
+            call_symbol(&library_name, &symbol_name, &args, &result_type)
+        });
+
+        // Objects created by a failed call are released here.
+        if created.is_empty() || result.is_err() {
+            on_result(result);
+            return;
+        }
+
+        gtk_dispatch::schedule_with_priority(priority, move || {
+            let adopted = worker::adopt(created);
+
+            on_result(result.map(|(mut value, mut ref_updates)| {
+                worker::remap_object_ids(&mut value, &adopted);
+
+                for (_, ref_value) in &mut ref_updates {
+                    worker::remap_object_ids(ref_value, &adopted);
                 }
+
+                (value, ref_updates)
+            }));
+        });
+    });
+}
+
+/// Runs an asynchronous GIO operation, finishing it on the GTK thread.
+///
+/// JavaScript signature: `callAsyncOp(library: string, startSymbol: string, finishSymbol: string, args: Arg[], resultType: Type) => Promise<Value>`
+///
+/// The start function is called with `args` followed by a `GAsyncReadyCallback`
+/// and its user data. When the operation completes, the finish function is
+/// called from that callback with the source object unless `finish.source` is
+/// false, the `GAsyncResult`, the arguments in `finish.args` and a `GError**`.
+/// The promise resolves with the result of the finish function once its refs
+/// are updated, or rejects with the `GError` it reports.
+pub fn call_async_op(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let library_name = cx.argument::<JsString>(0)?.value(&mut cx);
+    let start_symbol = cx.argument::<JsString>(1)?.value(&mut cx);
+    let finish_symbol = cx.argument::<JsString>(2)?.value(&mut cx);
+    let js_args = cx.argument::<JsArray>(3)?;
+    let js_result_type = cx.argument::<JsObject>(4)?;
+    let mut args = Arg::from_js_array(&mut cx, js_args)?;
+    let result_type = Type::from_js_value(&mut cx, js_result_type.upcast())?;
+    let finish_args = AsyncOpFinish::from_argument(&mut cx, 5)?;
+
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+    // Settled by the finish call, or by the start call if it fails.
+    let deferred = Arc::new(Mutex::new(Some(deferred)));
+
+    let finish = {
+        let library_name = library_name.clone();
+        let deferred = Arc::clone(&deferred);
+        let channel = channel.clone();
+
+        glib::Closure::new(move |values: &[glib::Value]| {
+            let result = finish_async_op(
+                library_name.clone(),
+                finish_symbol.clone(),
+                values,
+                finish_args.clone(),
+                result_type.clone(),
+            );
+
+            settle_async_op(&deferred, &channel, result);
+            None
+        })
+    };
+
+    args.push(Arg {
+        type_: Type::Callback(CallbackType {
+            trampoline: CallbackTrampoline::AsyncReady,
+            arg_types: None,
+            return_type: None,
+            source_type: None,
+            result_type: None,
+            is_async: false,
+            coalesce: None,
+        }),
+        value: Value::Closure(finish),
+        optional: false,
+    });
+
+    js_dispatch::with_context(&mut cx, || {
+        gtk_dispatch::schedule(move || {
+            if let Err(err) = handle_call(library_name, start_symbol, args, Type::Undefined) {
+                settle_async_op(&deferred, &channel, Err(err));
             }
-            _ => {
-                arg_types.push((&arg.type_).into());
-            }
+        });
+    });
+
+    Ok(promise)
+}
+
+/// Calls the finish function of an asynchronous operation with the values
+/// passed to its `GAsyncReadyCallback`.
+fn finish_async_op(
+    library_name: String,
+    finish_symbol: String,
+    values: &[glib::Value],
+    finish: AsyncOpFinish,
+    result_type: Type,
+) -> CallResult {
+    let object = |index: usize| {
+        values
+            .get(index)
+            .and_then(|value| value.get::<Option<glib::Object>>().ok().flatten())
+    };
+
+    let Some(async_result) = object(1) else {
+        bail!("{} was called without a GAsyncResult", finish_symbol);
+    };
+
+    // The source object and result are only tracked for the duration of the call.
+    let source_id = if finish.source {
+        Some(object(0).map(|source| ObjectId::new(Object::GObject(source))))
+    } else {
+        None
+    };
+    let result_id = ObjectId::new(Object::GObject(async_result));
+
+    let object_arg = |object_id: Option<ObjectId>| Arg {
+        type_: Type::GObject(GObjectType::new(true)),
+        value: object_id.map_or(Value::Null, Value::Object),
+        optional: true,
+    };
+
+    let mut args: Vec<Arg> = source_id.map(object_arg).into_iter().collect();
+    args.push(object_arg(Some(result_id)));
+    args.extend(finish.args);
+    args.push(Arg {
+        type_: Type::Error,
+        value: Value::Null,
+        optional: false,
+    });
+
+    let result = handle_call(library_name, finish_symbol, args, result_type);
+
+    GtkThreadState::with(|state| {
+        for object_id in source_id.flatten().into_iter().chain([result_id]) {
+            state.object_map.remove(&object_id.0);
+        }
+    });
+
+    result
+}
+
+fn settle_async_op(deferred: &Mutex<Option<Deferred>>, channel: &Channel, result: CallResult) {
+    let Some(deferred) = deferred.lock().unwrap().take() else {
+        return;
+    };
+
+    deferred.settle_with(channel, move |mut cx| match result {
+        Ok((value, ref_updates)) => {
+            apply_ref_updates(&mut cx, ref_updates)?;
+            value.to_js_value(&mut cx)
         }
+        Err(err) => throw_call_error(&mut cx, "Error during async operation", err),
+    });
+}
+
+/// Calls a function resolved from a GObject-Introspection typelib.
+///
+/// JavaScript signature: `callIntrospected(namespace: string, version: string, symbol: string, args: unknown[]) => Value`
+///
+/// Argument and return types, ownership transfer and nullability come from
+/// the typelib, so `args` holds plain values: the instance first for methods,
+/// and refs for out-parameters. The `GError**` of throwing functions is
+/// supplied automatically and reported as a thrown error.
+pub fn call_introspected(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let namespace = cx.argument::<JsString>(0)?.value(&mut cx);
+    let version = cx.argument::<JsString>(1)?.value(&mut cx);
+    let symbol = cx.argument::<JsString>(2)?.value(&mut cx);
+    let js_args = cx.argument::<JsArray>(3)?.to_vec(&mut cx)?;
+
+    // Resolving registers the GTypes of the function, so it runs on the GTK thread.
+    let function = {
+        let (namespace, version, symbol) = (namespace.clone(), version.clone(), symbol.clone());
+
+        run_on_gtk_thread(&mut cx, "Error resolving introspected function", move || {
+            GtkThreadState::with(|state| state.get_introspected(&namespace, &version, &symbol))
+                .map(|function| (*function).clone())
+        })?
+    };
+
+    let expected = function
+        .args
+        .iter()
+        .filter(|arg| !matches!(arg.type_, Type::Error))
+        .count();
+
+    if js_args.len() != expected {
+        return cx.throw_type_error(format!(
+            "{}.{} expects {} arguments, got {}",
+            namespace,
+            symbol,
+            expected,
+            js_args.len()
+        ));
+    }
+
+    let mut js_args = js_args.into_iter();
+    let mut args = Vec::with_capacity(function.args.len());
+
+    for arg in function.args {
+        let value = match (&arg.type_, js_args.next()) {
+            (Type::Error, _) | (_, None) => Value::Null,
+            (type_, Some(js_value)) => Value::from_js_value_with_type(&mut cx, js_value, type_)?,
+        };
+
+        args.push(Arg {
+            type_: arg.type_,
+            value,
+            optional: arg.optional,
+        });
     }
 
-    let cif = libffi::Builder::new()
-        .res((&result_type).into())
-        .args(arg_types)
-        .into_cif();
+    let (value, ref_updates) =
+        run_on_gtk_thread(&mut cx, "Error during introspected call", move || {
+            handle_call(
+                function.library,
+                function.symbol,
+                args,
+                function.return_type,
+            )
+        })?;
+
+    apply_ref_updates(&mut cx, ref_updates)?;
+    value.to_js_value(&mut cx)
+}
+
+/// Writes the values of ref (out) parameters back to their JavaScript objects.
+pub(super) fn apply_ref_updates<'a, C: Context<'a>>(
+    cx: &mut C,
+    ref_updates: Vec<RefUpdate>,
+) -> NeonResult<()> {
+    for (js_obj, new_value) in ref_updates {
+        let js_obj = js_obj.to_inner(cx);
+        let new_js_value = new_value.to_js_value(cx)?;
+
+        js_obj.set(cx, "value", new_js_value)?;
+    }
+
+    Ok(())
+}
+
+fn handle_call(
+    library_name: String,
+    symbol_name: String,
+    args: Vec<Arg>,
+    result_type: Type,
+) -> anyhow::Result<(Value, Vec<RefUpdate>)> {
+    let _activity = watchdog::GTK_THREAD.enter(|| format!("calling {symbol_name}"));
+
+    call_symbol(&library_name, &symbol_name, &args, &result_type)
+}
+
+/// Resolves a function and calls it with the CIF for its signature, using
+/// the caches of the current thread.
+fn call_symbol(
+    library_name: &str,
+    symbol_name: &str,
+    args: &[Arg],
+    result_type: &Type,
+) -> CallResult {
+    let signature = cif::Signature::new(args.iter().map(|arg| &arg.type_), result_type);
+    let (cif, symbol_ptr) = GtkThreadState::with(|state| {
+        anyhow::Ok((
+            state.get_cif(&signature),
+            state.get_symbol(library_name, symbol_name)?,
+        ))
+    })?;
+
+    call_prepared(&cif, symbol_ptr, args, result_type)
+}
 
+/// Calls a resolved function through a prepared CIF.
+///
+/// Converts the arguments, performs the call, reports a `GError` written to
+/// an error argument, and reads back the return value and ref parameters.
+pub(super) fn call_prepared(
+    cif: &libffi::Cif,
+    symbol_ptr: libffi::CodePtr,
+    args: &[Arg],
+    result_type: &Type,
+) -> anyhow::Result<(Value, Vec<RefUpdate>)> {
     let cif_args = args
-        .clone()
-        .into_iter()
-        .map(TryInto::<cif::Value>::try_into)
+        .iter()
+        .map(cif::Value::try_from)
         .collect::<anyhow::Result<Vec<cif::Value>>>()?;
 
     let mut ffi_args: Vec<libffi::Arg> = Vec::with_capacity(cif_args.len() + 1);
@@ -149,16 +740,6 @@ fn handle_call(
         }
     }
 
-    let symbol_ptr = unsafe {
-        GtkThreadState::with::<_, anyhow::Result<libffi::CodePtr>>(|state| {
-            let library = state.get_library(&library_name)?;
-            let symbol = library.get::<unsafe extern "C" fn() -> ()>(symbol_name.as_bytes())?;
-
-            let ptr = *symbol.deref() as *mut c_void;
-            Ok(libffi::CodePtr(ptr))
-        })?
-    };
-
     let result = unsafe {
         match result_type {
             Type::Undefined => {
@@ -200,19 +781,68 @@ fn handle_call(
                 cif::Value::Ptr(ptr as *mut c_void)
             }
             Type::Boolean => cif::Value::U8(cif.call::<u8>(symbol_ptr, &ffi_args)),
+            Type::Enum(_) => cif::Value::I32(cif.call::<i32>(symbol_ptr, &ffi_args)),
+            Type::Flags(_) => cif::Value::U32(cif.call::<u32>(symbol_ptr, &ffi_args)),
             Type::GObject(_) | Type::Boxed(_) => {
                 let ptr = cif.call::<*mut c_void>(symbol_ptr, &ffi_args);
                 cif::Value::Ptr(ptr)
             }
-            Type::Array(_) => {
+            Type::Array(_) | Type::HashTable(_) | Type::Variant(_) | Type::Bytes(_) => {
                 let ptr = cif.call::<*mut c_void>(symbol_ptr, &ffi_args);
                 cif::Value::Ptr(ptr)
             }
+            Type::Struct(type_) => {
+                // Struct returns have no fixed Rust type to call through, so the
+                // result is written into a buffer sized from the struct layout.
+                let mut buffer = vec![0u64; type_.size().div_ceil(8).max(1)];
+
+                ::libffi::raw::ffi_call(
+                    cif.as_raw_ptr(),
+                    Some(*symbol_ptr.as_fun()),
+                    buffer.as_mut_ptr() as *mut c_void,
+                    ffi_args.as_ptr() as *mut *mut c_void,
+                );
+
+                cif::Value::Struct(cif::OwnedPtr::from_vec(buffer))
+            }
             Type::Null => cif::Value::Void,
             _ => bail!("Unsupported return type: {:?}", result_type),
         }
     };
 
+    let error = args
+        .iter()
+        .zip(&cif_args)
+        .find_map(|(arg, cif_arg)| match (&arg.type_, cif_arg) {
+            (Type::Error, cif::Value::OwnedPtr(error_storage)) => {
+                let error_ptr = unsafe { *(error_storage.ptr as *const *mut glib::ffi::GError) };
+                (!error_ptr.is_null()).then(|| unsafe { NativeError::from_glib_full(error_ptr) })
+            }
+            _ => None,
+        });
+
+    let results = read_call_results(args, &cif_args, &result, result_type);
+
+    if let Some(error) = error {
+        // Values returned along with an error are still converted, which takes
+        // ownership of transfer-full ones, and then released.
+        if let Ok((value, ref_updates)) = &results {
+            release_objects(std::iter::once(value).chain(ref_updates.iter().map(|(_, v)| v)));
+        }
+
+        return Err(error.into());
+    }
+
+    results
+}
+
+/// Reads the return value and the values of ref parameters after a call.
+fn read_call_results(
+    args: &[Arg],
+    cif_args: &[cif::Value],
+    result: &cif::Value,
+    result_type: &Type,
+) -> anyhow::Result<(Value, Vec<RefUpdate>)> {
     let mut ref_updates = Vec::new();
 
     for (i, arg) in args.iter().enumerate() {
@@ -234,72 +864,361 @@ fn handle_call(
                     _ => {}
                 }
             }
-            let new_value = Value::from_cif_value(&cif_args[i], &arg.type_)?;
+            let resolved_type =
+                resolve_array_length(&arg.type_, args, cif_args, result, result_type)?;
+            let new_value =
+                Value::from_cif_value(&cif_args[i], resolved_type.as_ref().unwrap_or(&arg.type_))?;
             ref_updates.push((r#ref.js_obj.clone(), new_value));
         }
     }
 
-    Ok((Value::from_cif_value(&result, &result_type)?, ref_updates))
+    let resolved_type = resolve_array_length(result_type, args, cif_args, result, result_type)?;
+    let value = Value::from_cif_value(result, resolved_type.as_ref().unwrap_or(result_type))?;
+
+    Ok((value, ref_updates))
+}
+
+/// Stops tracking the objects referred to by values that do not reach
+/// JavaScript, including nested ones, releasing the references they hold.
+fn release_objects<'v>(values: impl IntoIterator<Item = &'v Value>) {
+    let mut object_ids = Vec::new();
+
+    for value in values {
+        value.collect_object_ids(&mut object_ids);
+    }
+
+    GtkThreadState::with(|state| {
+        for object_id in object_ids {
+            state.object_map.remove(&object_id.0);
+        }
+    });
+}
+
+/// Resolves the length of a sized array or bytes pointer, or a ref to one,
+/// that is read from another argument or the return value.
+///
+/// Returns a copy of the type with the length fixed, so the array can be
+/// converted on its own, or `None` if the type needs no resolving.
+fn resolve_array_length(
+    type_: &Type,
+    args: &[Arg],
+    cif_args: &[cif::Value],
+    result: &cif::Value,
+    result_type: &Type,
+) -> anyhow::Result<Option<Type>> {
+    let length = match type_ {
+        Type::Array(array_type) => array_type.length,
+        Type::Bytes(bytes_type) => bytes_type.length,
+        Type::Ref(ref_type) => {
+            let inner_type =
+                resolve_array_length(&ref_type.inner_type, args, cif_args, result, result_type)?;
+
+            return Ok(inner_type.map(|inner_type| Type::Ref(RefType::new(inner_type))));
+        }
+        _ => return Ok(None),
+    };
+
+    let length = match length {
+        Some(ArrayLength::Arg(index)) => {
+            let (arg, cif_arg) = args
+                .get(index)
+                .zip(cif_args.get(index))
+                .ok_or_else(|| anyhow::anyhow!("Array length argument {} does not exist", index))?;
+
+            read_array_length(cif_arg, &arg.type_)?
+        }
+        Some(ArrayLength::Return) => read_array_length(result, result_type)?,
+        _ => return Ok(None),
+    };
+
+    let mut type_ = type_.clone();
+
+    match &mut type_ {
+        Type::Array(array_type) => array_type.length = Some(ArrayLength::Fixed(length)),
+        Type::Bytes(bytes_type) => bytes_type.length = Some(ArrayLength::Fixed(length)),
+        _ => unreachable!(),
+    }
+
+    Ok(Some(type_))
+}
+
+fn read_array_length(cif_value: &cif::Value, type_: &Type) -> anyhow::Result<usize> {
+    let is_integer = match type_ {
+        Type::Integer(_) => true,
+        Type::Ref(ref_type) => matches!(*ref_type.inner_type, Type::Integer(_)),
+        _ => false,
+    };
+
+    if !is_integer {
+        bail!("Expected an integer array length, got {:?}", type_);
+    }
+
+    match Value::from_cif_value(cif_value, type_)? {
+        Value::Number(n) if n >= 0.0 => Ok(n as usize),
+        Value::BigInt(n) if n >= 0 => Ok(n as usize),
+        value => bail!("Expected a non-negative array length, got {:?}", value),
+    }
 }
 
-/// Executes multiple void FFI calls in a single GTK thread dispatch.
+/// Executes multiple FFI calls in a single GTK thread dispatch.
 ///
-/// JavaScript signature: `batchCall(calls: { library: string, symbol: string, args: Arg[] }[]) => void`
+/// JavaScript signature: `batchCall(calls: { library: string, symbol: string, args: Arg[], returnType?: Type }[], options?: { priority?: "high" | "default" | "idle" }) => Value[]`
 ///
 /// All calls are dispatched together to the GTK thread, reducing synchronization overhead.
-/// Only supports void return types since batched calls are typically property setters.
-pub fn batch_call(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+/// Calls return `undefined` unless they declare a return type, and the results are returned
+/// in call order. An argument whose value is `{ resultOf: n }` receives the result of the
+/// `n`th call of the batch, so dependent calls such as creating a widget and configuring it
+/// need no round trip in between. The `priority` option is the same as for `call`.
+pub fn batch_call(mut cx: FunctionContext) -> JsResult<JsArray> {
     let js_calls = cx.argument::<JsArray>(0)?;
-    let len = js_calls.len(&mut cx);
+    let options = batch_call_options(&mut cx)?;
+    let descriptors = batch_descriptors_from_js_array(&mut cx, js_calls)?;
 
-    if len == 0 {
-        return Ok(cx.undefined());
+    if descriptors.is_empty() {
+        return Ok(cx.empty_array());
     }
 
+    let (values, ref_updates) = wait_for_dispatch(&mut cx, "Error during batch FFI call", |tx| {
+        gtk_dispatch::schedule_with_priority(options.priority, move || {
+            let _ = tx.send(handle_batch_calls(descriptors));
+        });
+    })?;
+
+    batch_results_to_js(&mut cx, values, ref_updates)
+}
+
+/// Executes multiple FFI calls in a single GTK thread dispatch without blocking.
+///
+/// JavaScript signature: `batchCallAsync(calls: { library: string, symbol: string, args: Arg[], returnType?: Type }[], options?: { priority?: "high" | "default" | "idle" }) => Promise<Value[]>`
+///
+/// Behaves like `batchCall`, but returns a promise for the results instead of
+/// waiting for the GTK thread.
+pub fn batch_call_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let js_calls = cx.argument::<JsArray>(0)?;
+    let options = batch_call_options(&mut cx)?;
+    let descriptors = batch_descriptors_from_js_array(&mut cx, js_calls)?;
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+
+    js_dispatch::with_context(&mut cx, || {
+        gtk_dispatch::schedule_with_priority(options.priority, move || {
+            let result = handle_batch_calls(descriptors);
+
+            deferred.settle_with(&channel, move |mut cx| match result {
+                Ok((values, ref_updates)) => batch_results_to_js(&mut cx, values, ref_updates),
+                Err(err) => throw_call_error(&mut cx, "Error during batch FFI call", err),
+            });
+        });
+    });
+
+    Ok(promise)
+}
+
+/// Executes multiple FFI calls in the next frame of the application's windows.
+///
+/// JavaScript signature: `requestFrameCommit(calls: { library: string, symbol: string, args: Arg[], returnType?: Type }[], onPresented?: (frameTime: number) => void) => Promise<Value[]>`
+///
+/// The calls are held on the GTK thread and run along with the other commits
+/// requested for the frame in the `update` phase of its frame clock, so that
+/// all their changes are painted in the same frame. The promise settles with
+/// the results once the calls have run, and `onPresented` is called with the
+/// frame time in microseconds once the frame has been painted. Without a
+/// visible window, or if no frame comes in time, the calls run immediately.
+pub fn request_frame_commit(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let js_calls = cx.argument::<JsArray>(0)?;
+    let on_presented = match cx.argument_opt(1) {
+        Some(value) if !value.is_a::<JsUndefined, _>(&mut cx) => Some(
+            value
+                .downcast_or_throw::<JsFunction, _>(&mut cx)?
+                .root(&mut cx),
+        ),
+        _ => None,
+    };
+    let descriptors = batch_descriptors_from_js_array(&mut cx, js_calls)?;
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+
+    js_dispatch::with_context(&mut cx, || {
+        // Requested at high priority to make it into the upcoming frame.
+        gtk_dispatch::schedule_with_priority(Priority::High, move || {
+            let presented_channel = channel.clone();
+
+            frame::request_commit(
+                move || {
+                    let result = handle_batch_calls(descriptors);
+
+                    deferred.settle_with(&channel, move |mut cx| match result {
+                        Ok((values, ref_updates)) => {
+                            batch_results_to_js(&mut cx, values, ref_updates)
+                        }
+                        Err(err) => throw_call_error(&mut cx, "Error during frame commit", err),
+                    });
+                },
+                move |frame_time| {
+                    let Some(on_presented) = on_presented else {
+                        return;
+                    };
+
+                    presented_channel.send(move |mut cx| {
+                        let on_presented = on_presented.into_inner(&mut cx);
+                        let frame_time = cx.number(frame_time as f64);
+
+                        on_presented.call_with(&cx).arg(frame_time).exec(&mut cx)
+                    });
+                },
+            );
+        });
+    });
+
+    Ok(promise)
+}
+
+/// Reads the options of a batch call, whose calls always run on the GTK thread.
+fn batch_call_options(cx: &mut FunctionContext) -> NeonResult<CallOptions> {
+    let options = CallOptions::from_argument(cx, 1)?;
+
+    if options.thread == CallThread::Worker {
+        return cx.throw_type_error("Batch calls cannot run on a worker thread");
+    }
+
+    Ok(options)
+}
+
+fn batch_descriptors_from_js_array(
+    cx: &mut FunctionContext,
+    js_calls: Handle<JsArray>,
+) -> NeonResult<Vec<BatchCallDescriptor>> {
+    let len = js_calls.len(cx);
     let mut descriptors = Vec::with_capacity(len as usize);
 
     for i in 0..len {
-        let js_call = js_calls.get::<JsObject, _, _>(&mut cx, i)?;
+        let js_call = js_calls.get::<JsObject, _, _>(cx, i)?;
+
+        let library_name = js_call.get::<JsString, _, _>(cx, "library")?.value(cx);
+        let symbol_name = js_call.get::<JsString, _, _>(cx, "symbol")?.value(cx);
+        let js_args = js_call.get::<JsArray, _, _>(cx, "args")?;
+        let (args, result_refs) = batch_args_from_js_array(cx, js_args, i as usize)?;
 
-        let library_name = js_call
-            .get::<JsString, _, _>(&mut cx, "library")?
-            .value(&mut cx);
-        let symbol_name = js_call
-            .get::<JsString, _, _>(&mut cx, "symbol")?
-            .value(&mut cx);
-        let js_args = js_call.get::<JsArray, _, _>(&mut cx, "args")?;
-        let args = Arg::from_js_array(&mut cx, js_args)?;
+        let js_result_type: Option<Handle<JsObject>> = js_call.get_opt(cx, "returnType")?;
+        let result_type = match js_result_type {
+            Some(js_result_type) => Type::from_js_value(cx, js_result_type.upcast())?,
+            None => Type::Undefined,
+        };
 
         descriptors.push(BatchCallDescriptor {
             library_name,
             symbol_name,
             args,
+            result_type,
+            result_refs,
         });
     }
 
-    let (tx, rx) = mpsc::channel::<anyhow::Result<()>>();
+    Ok(descriptors)
+}
 
-    gtk_dispatch::enter_js_wait();
-    gtk_dispatch::schedule(move || {
-        let result = handle_batch_calls(descriptors);
-        let _ = tx.send(result);
-    });
+fn batch_results_to_js<'a, C: Context<'a>>(
+    cx: &mut C,
+    values: Vec<Value>,
+    ref_updates: Vec<RefUpdate>,
+) -> JsResult<'a, JsArray> {
+    apply_ref_updates(cx, ref_updates)?;
+
+    let js_values = cx.empty_array();
+
+    for (i, value) in values.into_iter().enumerate() {
+        let js_value = value.to_js_value(cx)?;
+        js_values.set(cx, i as u32, js_value)?;
+    }
+
+    Ok(js_values)
+}
+
+/// Parses the arguments of the `index`th call of a batch.
+///
+/// Arguments whose value is `{ resultOf: n }` are returned as null
+/// placeholders, along with the `(argument index, call index)` pairs to fill
+/// them from earlier results.
+fn batch_args_from_js_array(
+    cx: &mut FunctionContext,
+    js_args: Handle<JsArray>,
+    index: usize,
+) -> NeonResult<(Vec<Arg>, Vec<ResultRef>)> {
+    let js_args = js_args.to_vec(cx)?;
+    let mut args = Vec::with_capacity(js_args.len());
+    let mut result_refs = Vec::new();
+
+    for (arg_index, js_arg) in js_args.into_iter().enumerate() {
+        let obj = js_arg.downcast::<JsObject, _>(cx).or_throw(cx)?;
+        let value_prop: Handle<JsValue> = obj.prop(cx, "value").get()?;
 
-    wait_for_result(&mut cx, &rx)
-        .or_else(|err| cx.throw_error(format!("Error during batch FFI call: {err}")))?;
+        let result_of = match value_prop.downcast::<JsObject, _>(cx) {
+            Ok(value_obj) => value_obj.get_opt::<JsNumber, _, _>(cx, "resultOf")?,
+            Err(_) => None,
+        };
 
-    Ok(cx.undefined())
+        let Some(result_of) = result_of else {
+            args.push(Arg::from_js_value(cx, js_arg)?);
+            continue;
+        };
+
+        let result_index = result_of.value(cx);
+
+        if result_index.fract() != 0.0 || result_index < 0.0 || result_index >= index as f64 {
+            return cx.throw_range_error(format!(
+                "resultOf must reference an earlier call in the batch, got {} in call {}",
+                result_index, index
+            ));
+        }
+
+        let type_prop: Handle<JsValue> = obj.prop(cx, "type").get()?;
+        let optional_prop: Option<Handle<JsBoolean>> = obj.get_opt(cx, "optional")?;
+
+        args.push(Arg {
+            type_: Type::from_js_value(cx, type_prop)?,
+            value: Value::Null,
+            optional: optional_prop.map(|h| h.value(cx)).unwrap_or(false),
+        });
+        result_refs.push((arg_index, result_index as usize));
+    }
+
+    Ok((args, result_refs))
 }
 
-fn handle_batch_calls(descriptors: Vec<BatchCallDescriptor>) -> anyhow::Result<()> {
+fn handle_batch_calls(
+    descriptors: Vec<BatchCallDescriptor>,
+) -> anyhow::Result<(Vec<Value>, Vec<RefUpdate>)> {
+    let mut results: Vec<Value> = Vec::with_capacity(descriptors.len());
+    let mut ref_updates = Vec::new();
+
     for descriptor in descriptors {
-        handle_call(
+        let mut args = descriptor.args;
+
+        for (arg_index, result_index) in descriptor.result_refs {
+            args[arg_index].value = results[result_index].clone();
+        }
+
+        match handle_call(
             descriptor.library_name,
             descriptor.symbol_name,
-            descriptor.args,
-            Type::Undefined,
-        )?;
+            args,
+            descriptor.result_type,
+        ) {
+            Ok((value, call_ref_updates)) => {
+                results.push(value);
+                ref_updates.extend(call_ref_updates);
+            }
+            Err(err) => {
+                // Results and ref updates of earlier calls never reach
+                // JavaScript, so the objects they track would otherwise never
+                // be released.
+                let ref_values = ref_updates.iter().map(|(_, value)| value);
+                release_objects(results.iter().chain(ref_values));
+
+                return Err(err);
+            }
+        }
     }
 
-    Ok(())
+    Ok((results, ref_updates))
 }
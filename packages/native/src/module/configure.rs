@@ -0,0 +1,74 @@
+//! Runtime configuration.
+
+use std::time::Duration;
+
+use neon::prelude::*;
+
+use crate::{config, watchdog};
+
+/// Updates the runtime configuration.
+///
+/// JavaScript signature: `configure(options: { waitTimeout?: number, throwOnTimeout?: boolean, onWaitTimeout?: ((report: string) => void) | null, maxReentrancyDepth?: number, dispatchBudget?: number }) => void`
+///
+/// Options that are not given keep their current value. A `waitTimeout` of 0
+/// disables the watchdog, an `onWaitTimeout` of null stops reporting stalled
+/// waits, and a `maxReentrancyDepth` or `dispatchBudget` of 0 removes the
+/// limit.
+pub fn configure(mut cx: FunctionContext) -> JsResult<JsUndefined> {
+    let options = cx.argument::<JsObject>(0)?;
+
+    if let Some(wait_timeout) = count_option(&mut cx, options, "waitTimeout")? {
+        let timeout = (wait_timeout > 0).then(|| Duration::from_millis(wait_timeout as u64));
+        config::set_wait_timeout(timeout);
+    }
+
+    let throw_on_timeout: Option<Handle<JsBoolean>> = options.get_opt(&mut cx, "throwOnTimeout")?;
+    if let Some(throw_on_timeout) = throw_on_timeout {
+        config::set_throw_on_timeout(throw_on_timeout.value(&mut cx));
+    }
+
+    let on_wait_timeout: Option<Handle<JsValue>> = options.get_opt(&mut cx, "onWaitTimeout")?;
+    if let Some(on_wait_timeout) = on_wait_timeout {
+        let reporter = if on_wait_timeout.is_a::<JsNull, _>(&mut cx) {
+            None
+        } else {
+            let callback = on_wait_timeout.downcast_or_throw::<JsFunction, _>(&mut cx)?;
+            let mut channel = cx.channel();
+            channel.unref(&mut cx);
+            Some((channel, callback.root(&mut cx)))
+        };
+
+        watchdog::set_reporter(reporter);
+    }
+
+    if let Some(max_depth) = count_option(&mut cx, options, "maxReentrancyDepth")? {
+        config::set_max_reentrancy_depth((max_depth > 0).then_some(max_depth));
+    }
+
+    if let Some(dispatch_budget) = count_option(&mut cx, options, "dispatchBudget")? {
+        let budget = (dispatch_budget > 0).then(|| Duration::from_millis(dispatch_budget as u64));
+        config::set_dispatch_budget(budget);
+    }
+
+    Ok(cx.undefined())
+}
+
+fn count_option(
+    cx: &mut FunctionContext,
+    options: Handle<JsObject>,
+    key: &str,
+) -> NeonResult<Option<usize>> {
+    let value: Option<Handle<JsNumber>> = options.get_opt(cx, key)?;
+
+    let Some(value) = value else {
+        return Ok(None);
+    };
+
+    let value = value.value(cx);
+
+    if value.fract() != 0.0 || value < 0.0 || !value.is_finite() {
+        return cx.throw_range_error(format!("{key} must be a non-negative integer, got {value}"));
+    }
+
+    Ok(Some(value as usize))
+}
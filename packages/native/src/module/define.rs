@@ -0,0 +1,134 @@
+//! Pre-registered function handles.
+//!
+//! A function is defined once with its library, symbol and types, then
+//! invoked any number of times with plain values. Type descriptors are parsed
+//! and validated only at definition time, and the symbol and CIF are resolved
+//! up front, so each invocation only converts values and performs the call.
+
+use std::{ffi::c_void, sync::Arc};
+
+use libffi::middle as libffi;
+use neon::prelude::*;
+
+use crate::{arg::Arg, cif, state::GtkThreadState, types::Type, value::Value, watchdog};
+
+use super::call::{apply_ref_updates, call_prepared, run_on_gtk_thread};
+
+/// The type of a defined function's argument.
+#[derive(Debug, Clone)]
+struct ArgType {
+    type_: Type,
+    optional: bool,
+}
+
+/// A native function with its types parsed and its symbol resolved.
+#[derive(Debug)]
+struct FunctionDefinition {
+    symbol_name: String,
+    arg_types: Vec<ArgType>,
+    result_type: Type,
+    signature: cif::Signature,
+    /// Address of the resolved symbol. Libraries are never unloaded, so it
+    /// stays valid for the lifetime of the process.
+    symbol_ptr: usize,
+}
+
+/// A JavaScript handle to a defined function.
+pub struct FunctionHandle(Arc<FunctionDefinition>);
+
+impl Finalize for FunctionHandle {}
+
+/// Defines a native function for repeated invocation.
+///
+/// JavaScript signature: `define(library: string, symbol: string, argTypes: ArgType[], returnType: Type) => FunctionHandle`
+///
+/// Parses the type descriptors, resolves the symbol and prepares the CIF on
+/// the GTK thread, throwing if the symbol cannot be found.
+pub fn define(mut cx: FunctionContext) -> JsResult<JsBox<FunctionHandle>> {
+    let library_name = cx.argument::<JsString>(0)?.value(&mut cx);
+    let symbol_name = cx.argument::<JsString>(1)?.value(&mut cx);
+    let js_arg_types = cx.argument::<JsArray>(2)?.to_vec(&mut cx)?;
+    let js_result_type = cx.argument::<JsObject>(3)?;
+
+    let mut arg_types = Vec::with_capacity(js_arg_types.len());
+
+    for js_arg_type in js_arg_types {
+        let obj = js_arg_type
+            .downcast::<JsObject, _>(&mut cx)
+            .or_throw(&mut cx)?;
+        let type_prop: Handle<'_, JsValue> = obj.prop(&mut cx, "type").get()?;
+        let type_ = Type::from_js_value(&mut cx, type_prop)?;
+
+        let optional_prop: Option<Handle<JsBoolean>> = obj.get_opt(&mut cx, "optional")?;
+        let optional = optional_prop.map(|h| h.value(&mut cx)).unwrap_or(false);
+
+        arg_types.push(ArgType { type_, optional });
+    }
+
+    let result_type = Type::from_js_value(&mut cx, js_result_type.upcast())?;
+    let signature = cif::Signature::new(arg_types.iter().map(|arg| &arg.type_), &result_type);
+
+    let cif_signature = signature.clone();
+    let symbol = symbol_name.clone();
+
+    let symbol_ptr = run_on_gtk_thread(&mut cx, "Error defining function", move || {
+        GtkThreadState::with(|state| {
+            state.get_cif(&cif_signature);
+            let symbol_ptr = state.get_symbol(&library_name, &symbol)?;
+            anyhow::Ok(symbol_ptr.0 as usize)
+        })
+    })?;
+
+    let definition = FunctionDefinition {
+        symbol_name,
+        arg_types,
+        result_type,
+        signature,
+        symbol_ptr,
+    };
+
+    Ok(cx.boxed(FunctionHandle(Arc::new(definition))))
+}
+
+/// Invokes a function defined with [`define`].
+///
+/// JavaScript signature: `invoke(handle: FunctionHandle, values: unknown[]) => Value`
+///
+/// Behaves like `call` with the defined types, taking one value per argument.
+pub fn invoke(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let definition = cx.argument::<JsBox<FunctionHandle>>(0)?.0.clone();
+    let js_values = cx.argument::<JsArray>(1)?.to_vec(&mut cx)?;
+
+    if js_values.len() != definition.arg_types.len() {
+        return cx.throw_type_error(format!(
+            "{} expects {} arguments, got {}",
+            definition.symbol_name,
+            definition.arg_types.len(),
+            js_values.len()
+        ));
+    }
+
+    let mut args = Vec::with_capacity(js_values.len());
+
+    for (arg_type, js_value) in definition.arg_types.iter().zip(js_values) {
+        let value = Value::from_js_value_with_type(&mut cx, js_value, &arg_type.type_)?;
+
+        args.push(Arg {
+            type_: arg_type.type_.clone(),
+            value,
+            optional: arg_type.optional,
+        });
+    }
+
+    let (value, ref_updates) = run_on_gtk_thread(&mut cx, "Error during FFI call", move || {
+        let _activity =
+            watchdog::GTK_THREAD.enter(|| format!("calling {}", definition.symbol_name));
+        let cif = GtkThreadState::with(|state| state.get_cif(&definition.signature));
+        let symbol_ptr = libffi::CodePtr(definition.symbol_ptr as *mut c_void);
+
+        call_prepared(&cif, symbol_ptr, &args, &definition.result_type)
+    })?;
+
+    apply_ref_updates(&mut cx, ref_updates)?;
+    value.to_js_value(&mut cx)
+}
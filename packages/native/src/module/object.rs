@@ -1,10 +1,10 @@
 //! Object ID retrieval for native pointers.
 
-use std::sync::mpsc;
-
 use neon::prelude::*;
 
-use crate::{gtk_dispatch, object::ObjectId};
+use crate::object::ObjectId;
+
+use super::call::run_on_gtk_thread;
 
 /// Gets the native pointer address for an object.
 ///
@@ -14,17 +14,11 @@ use crate::{gtk_dispatch, object::ObjectId};
 /// object has been garbage collected.
 pub fn get_object_id(mut cx: FunctionContext) -> JsResult<JsNumber> {
     let object_id = cx.argument::<JsBox<ObjectId>>(0)?;
-
-    let (tx, rx) = mpsc::channel::<Option<usize>>();
     let id = *object_id.as_inner();
 
-    gtk_dispatch::schedule(move || {
-        let _ = tx.send(id.try_as_ptr());
-    });
-
-    let ptr = rx
-        .recv()
-        .or_else(|err| cx.throw_error(format!("Error receiving pointer: {err}")))?;
+    let ptr = run_on_gtk_thread(&mut cx, "Error getting object ID", move || {
+        anyhow::Ok(id.try_as_ptr())
+    })?;
 
     match ptr {
         Some(p) => Ok(cx.number(p as f64)),
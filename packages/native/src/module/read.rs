@@ -1,7 +1,6 @@
 //! Field reading from native objects.
 
 use std::ffi::{CStr, c_void};
-use std::sync::mpsc;
 
 use anyhow::bail;
 use gtk4::glib::{self, translate::FromGlibPtrNone as _};
@@ -9,12 +8,13 @@ use neon::prelude::*;
 
 use crate::{
     boxed::Boxed,
-    gtk_dispatch,
     object::{Object, ObjectId},
-    types::{FloatSize, IntegerSign, IntegerSize, Type},
+    types::{FloatSize, Type},
     value::Value,
 };
 
+use super::call::run_on_gtk_thread;
+
 /// Reads a field from a native object at the given offset.
 ///
 /// JavaScript signature: `read(objectId: ObjectId, type: Type, offset: number) => Value`
@@ -27,16 +27,10 @@ pub fn read(mut cx: FunctionContext) -> JsResult<JsValue> {
     let offset = cx.argument::<JsNumber>(2)?.value(&mut cx) as usize;
     let type_ = Type::from_js_value(&mut cx, js_type.upcast())?;
     let object_id = *object_id.as_inner();
-    let (tx, rx) = mpsc::channel::<anyhow::Result<Value>>();
-
-    gtk_dispatch::schedule(move || {
-        let _ = tx.send(handle_read(object_id, &type_, offset));
-    });
 
-    let value = rx
-        .recv()
-        .or_else(|err| cx.throw_error(format!("Error receiving read result: {err}")))?
-        .or_else(|err| cx.throw_error(format!("Error during read: {err}")))?;
+    let value = run_on_gtk_thread(&mut cx, "Error during read", move || {
+        handle_read(object_id, &type_, offset)
+    })?;
 
     value.to_js_value(&mut cx)
 }
@@ -53,10 +47,7 @@ fn handle_read(object_id: ObjectId, type_: &Type, offset: usize) -> anyhow::Resu
     let field_ptr = unsafe { (ptr as *const u8).add(offset) };
 
     match type_ {
-        Type::Integer(int_type) => {
-            let number = dispatch_integer_read!(int_type, field_ptr);
-            Ok(Value::Number(number))
-        }
+        Type::Integer(int_type) => Ok(Value::from_integer_ptr(field_ptr, int_type)),
         Type::Float(float_type) => {
             let number = match float_type.size {
                 FloatSize::_32 => unsafe { field_ptr.cast::<f32>().read_unaligned() as f64 },
@@ -69,6 +60,14 @@ fn handle_read(object_id: ObjectId, type_: &Type, offset: usize) -> anyhow::Resu
             let value = unsafe { field_ptr.cast::<u8>().read_unaligned() != 0 };
             Ok(Value::Boolean(value))
         }
+        Type::Enum(enum_type) => {
+            let raw = unsafe { field_ptr.cast::<i32>().read_unaligned() };
+            Ok(enum_type.to_value(raw))
+        }
+        Type::Flags(flags_type) => {
+            let raw = unsafe { field_ptr.cast::<u32>().read_unaligned() };
+            Ok(flags_type.to_value(raw))
+        }
         Type::String(_) => {
             let str_ptr = unsafe { field_ptr.cast::<*const i8>().read_unaligned() };
 
@@ -105,6 +104,14 @@ fn handle_read(object_id: ObjectId, type_: &Type, offset: usize) -> anyhow::Resu
             let boxed = Boxed::from_glib_none(gtype, boxed_ptr);
             Ok(Value::Object(ObjectId::new(Object::Boxed(boxed))))
         }
+        Type::HashTable(hash_table_type) => {
+            let table_ptr = unsafe { field_ptr.cast::<*mut c_void>().read_unaligned() };
+            Value::from_hash_table_ptr(table_ptr, hash_table_type, false)
+        }
+        Type::Variant(_) => {
+            let variant_ptr = unsafe { field_ptr.cast::<*mut c_void>().read_unaligned() };
+            Value::from_variant_ptr(variant_ptr, true)
+        }
         _ => bail!("Unsupported field type for read_field: {:?}", type_),
     }
 }
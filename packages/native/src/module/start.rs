@@ -2,21 +2,27 @@
 
 use std::sync::mpsc;
 
-use gtk4::{gio::ApplicationFlags, prelude::*};
-use neon::prelude::*;
+use gtk4::{gio::ApplicationFlags, glib, prelude::*};
+use neon::{object::Object as _, prelude::*};
 
 use crate::{
+    gtk_dispatch, main_context,
     object::{Object, ObjectId},
-    state::{set_gtk_thread_handle, GtkThreadState},
+    state::{GtkThreadState, set_gtk_thread_handle},
 };
 
 /// Starts the GTK application and main loop.
 ///
-/// JavaScript signature: `start(appId: string, flags?: number) => ObjectId`
+/// JavaScript signature: `start(appId: string, flags?: number, options?: { mainThread?: boolean }) => ObjectId`
 ///
 /// Creates a GTK Application with the given ID, starts the main loop on a
 /// dedicated thread, and returns the application's ObjectId. The function
 /// blocks until the application is activated.
+///
+/// With `mainThread`, GTK runs on the JS thread instead, with the GLib main
+/// context driven from the Node.js event loop. FFI calls and the callbacks
+/// they fire are then direct calls without any thread hops. The application
+/// still emits `startup` and `activate` here, and `shutdown` on `stop`.
 pub fn start(mut cx: FunctionContext) -> JsResult<JsValue> {
     let app_id = cx.argument::<JsString>(0)?.value(&mut cx);
 
@@ -30,6 +36,20 @@ pub fn start(mut cx: FunctionContext) -> JsResult<JsValue> {
         .map(ApplicationFlags::from_bits_truncate)
         .unwrap_or(ApplicationFlags::FLAGS_NONE);
 
+    let main_thread = match cx.argument_opt(2) {
+        Some(arg) => match arg.downcast::<JsObject, _>(&mut cx) {
+            Ok(options) => options
+                .get_opt::<JsBoolean, _, _>(&mut cx, "mainThread")?
+                .is_some_and(|value| value.value(&mut cx)),
+            Err(_) => false,
+        },
+        None => false,
+    };
+
+    if main_thread {
+        return start_on_main_thread(cx, app_id, flags);
+    }
+
     let (tx, rx) = mpsc::channel::<ObjectId>();
 
     let handle = std::thread::spawn(move || {
@@ -61,3 +81,42 @@ pub fn start(mut cx: FunctionContext) -> JsResult<JsValue> {
 
     Ok(cx.boxed(app_object_id).upcast())
 }
+
+fn start_on_main_thread(
+    mut cx: FunctionContext,
+    app_id: String,
+    flags: ApplicationFlags,
+) -> JsResult<JsValue> {
+    let context = glib::MainContext::default();
+
+    // The JS thread owns the default main context for the rest of the process.
+    match context.acquire() {
+        Ok(guard) => std::mem::forget(guard),
+        Err(err) => return cx.throw_error(format!("Error starting GTK on the main thread: {err}")),
+    }
+
+    gtk_dispatch::set_main_thread();
+
+    let app = gtk4::Application::builder()
+        .application_id(app_id)
+        .flags(flags)
+        .build();
+
+    let app_object_id = ObjectId::new(Object::GObject(app.clone().into()));
+
+    GtkThreadState::with(|state| {
+        state.app_hold_guard = Some(app.hold());
+    });
+
+    // As on the threaded path, activation is handled, so that GIO does not
+    // warn about an application without an `activate` handler.
+    app.connect_activate(|_| {});
+
+    app.register(None::<&gtk4::gio::Cancellable>)
+        .or_else(|err| cx.throw_error(format!("Error registering GTK application: {err}")))?;
+
+    main_context::attach(cx.channel());
+    app.activate();
+
+    Ok(cx.boxed(app_object_id).upcast())
+}
@@ -2,10 +2,11 @@
 
 use std::sync::mpsc;
 
+use gtk4::{gio, prelude::*};
 use neon::prelude::*;
 
 use crate::{
-    gtk_dispatch,
+    gtk_dispatch, js_dispatch, main_context,
     state::{GtkThreadState, join_gtk_thread},
 };
 
@@ -16,7 +17,10 @@ use crate::{
 /// Releases the application hold guard allowing the GTK main loop to exit,
 /// then joins the GTK thread to ensure clean shutdown. Also marks the dispatch
 /// system as stopped to prevent crashes from GC finalizers running after
-/// the main loop has exited.
+/// the main loop has exited. In main thread mode, emits the application's
+/// `shutdown` signal, which `g_application_run` emits on the threaded path,
+/// and stops driving the main context from the Node.js event loop instead of
+/// joining a thread.
 pub fn stop(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     let (tx, rx) = mpsc::channel::<()>();
 
@@ -33,7 +37,20 @@ pub fn stop(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     rx.recv()
         .or_else(|err| cx.throw_error(format!("Error stopping GTK thread: {err}")))?;
 
+    if gtk_dispatch::is_main_thread() {
+        js_dispatch::with_context(&mut cx, emit_shutdown);
+    }
+
     join_gtk_thread();
+    main_context::detach();
 
     Ok(cx.undefined())
 }
+
+/// Emits `shutdown` on the default application, ending its lifecycle as
+/// `g_application_run` does once its main loop exits.
+fn emit_shutdown() {
+    if let Some(app) = gio::Application::default() {
+        app.emit_by_name::<()>("shutdown", &[]);
+    }
+}
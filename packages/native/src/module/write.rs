@@ -1,17 +1,16 @@
 //! Field writing to native objects.
 
-use std::sync::mpsc;
-
 use anyhow::bail;
 use neon::prelude::*;
 
 use crate::{
-    gtk_dispatch,
     object::ObjectId,
     types::{FloatSize, IntegerSign, IntegerSize, Type},
     value::Value,
 };
 
+use super::call::run_on_gtk_thread;
+
 /// Writes a field to a native object at the given offset.
 ///
 /// JavaScript signature: `write(objectId: ObjectId, type: Type, offset: number, value: Value) => void`
@@ -26,15 +25,10 @@ pub fn write(mut cx: FunctionContext) -> JsResult<JsUndefined> {
     let type_ = Type::from_js_value(&mut cx, js_type.upcast())?;
     let value = Value::from_js_value(&mut cx, js_value)?;
     let object_id = *object_id.as_inner();
-    let (tx, rx) = mpsc::channel::<anyhow::Result<()>>();
 
-    gtk_dispatch::schedule(move || {
-        let _ = tx.send(handle_write(object_id, &type_, offset, &value));
-    });
-
-    rx.recv()
-        .or_else(|err| cx.throw_error(format!("Error receiving write result: {err}")))?
-        .or_else(|err| cx.throw_error(format!("Error during write: {err}")))?;
+    run_on_gtk_thread(&mut cx, "Error during write", move || {
+        handle_write(object_id, &type_, offset, &value)
+    })?;
 
     Ok(cx.undefined())
 }
@@ -59,6 +53,10 @@ fn handle_write(
         (Type::Integer(int_type), Value::Number(n)) => {
             dispatch_integer_write!(int_type, field_ptr, *n);
         }
+        (Type::Integer(int_type), Value::BigInt(n)) => {
+            int_type.check_range(*n)?;
+            dispatch_integer_write!(int_type, field_ptr, *n);
+        }
         (Type::Float(float_type), Value::Number(n)) => match float_type.size {
             FloatSize::_32 => unsafe { field_ptr.cast::<f32>().write_unaligned(*n as f32) },
             FloatSize::_64 => unsafe { field_ptr.cast::<f64>().write_unaligned(*n) },
@@ -66,6 +64,14 @@ fn handle_write(
         (Type::Boolean, Value::Boolean(b)) => unsafe {
             field_ptr.cast::<u8>().write_unaligned(u8::from(*b));
         },
+        (Type::Enum(enum_type), value) => {
+            let raw = enum_type.to_raw(value)?;
+            unsafe { field_ptr.cast::<i32>().write_unaligned(raw) };
+        }
+        (Type::Flags(flags_type), value) => {
+            let raw = flags_type.to_raw(value)?;
+            unsafe { field_ptr.cast::<u32>().write_unaligned(raw) };
+        }
         _ => bail!("Unsupported field type for write: {:?}", type_),
     }
 
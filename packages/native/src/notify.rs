@@ -0,0 +1,114 @@
+//! Blocking wakeups for cross-thread waits.
+//!
+//! The JS and GTK threads wait on each other while servicing re-entrant work
+//! from the opposite side. Instead of spinning, a waiting thread takes a
+//! token, checks its queues and result channel, and then sleeps until the
+//! other thread notifies it. Any notification after the token was taken ends
+//! the wait, so wakeups sent between the checks and the wait are not lost.
+
+use std::{
+    sync::{Condvar, Mutex},
+    time::Duration,
+};
+
+/// Wakes the GTK thread while it waits for a JS callback result.
+///
+/// Notified when a task is scheduled to the GTK thread and when a callback
+/// result is sent back.
+pub static GTK_THREAD: Notify = Notify::new();
+
+/// Wakes the JS thread while it waits for a GTK dispatch result.
+///
+/// Notified when a callback is queued for the JS thread and when a task has
+/// run on the GTK thread.
+pub static JS_THREAD: Notify = Notify::new();
+
+/// A generation counter that threads can sleep on until it changes.
+pub struct Notify {
+    generation: Mutex<u64>,
+    condvar: Condvar,
+}
+
+impl Notify {
+    pub const fn new() -> Self {
+        Self {
+            generation: Mutex::new(0),
+            condvar: Condvar::new(),
+        }
+    }
+
+    /// Returns a token to wait on. Take it before checking for work.
+    pub fn token(&self) -> u64 {
+        *self.generation.lock().unwrap()
+    }
+
+    /// Wakes all threads waiting on this notify.
+    pub fn notify(&self) {
+        *self.generation.lock().unwrap() += 1;
+        self.condvar.notify_all();
+    }
+
+    /// Blocks until `notify` has been called since `token` was taken.
+    pub fn wait(&self, token: u64) {
+        let mut generation = self.generation.lock().unwrap();
+
+        while *generation == token {
+            generation = self.condvar.wait(generation).unwrap();
+        }
+    }
+
+    /// Like [`Notify::wait`], but gives up after `timeout`.
+    ///
+    /// Returns `false` if the timeout elapsed without a notification.
+    pub fn wait_timeout(&self, token: u64, timeout: Duration) -> bool {
+        let generation = self.generation.lock().unwrap();
+        let (_generation, result) = self
+            .condvar
+            .wait_timeout_while(generation, timeout, |generation| *generation == token)
+            .unwrap();
+
+        !result.timed_out()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Arc, thread, time::Duration};
+
+    #[test]
+    fn wait_returns_immediately_after_notify() {
+        let notify = Notify::new();
+        let token = notify.token();
+
+        notify.notify();
+        notify.wait(token);
+
+        assert_ne!(notify.token(), token);
+    }
+
+    #[test]
+    fn wait_blocks_until_notified_from_another_thread() {
+        let notify = Arc::new(Notify::new());
+        let token = notify.token();
+
+        let notify_clone = Arc::clone(&notify);
+        let notifier = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            notify_clone.notify();
+        });
+
+        notify.wait(token);
+        notifier.join().unwrap();
+
+        assert_eq!(notify.token(), token + 1);
+    }
+
+    #[test]
+    fn wait_timeout_returns_false_without_notify() {
+        let notify = Notify::new();
+        let token = notify.token();
+
+        assert!(!notify.wait_timeout(token, Duration::from_millis(10)));
+    }
+}
@@ -17,6 +17,27 @@ impl<T> Queue<T> {
         self.items.lock().unwrap().push_back(item);
     }
 
+    /// Merges `item` into the last queued item if `merge` accepts it, or
+    /// pushes it otherwise. Returns `true` if the item was pushed.
+    ///
+    /// Only the tail is considered, so items are never merged across other
+    /// items and the queue order is preserved. `merge` hands the item back
+    /// when it does not accept it.
+    pub fn push_or_merge(&self, item: T, merge: impl FnOnce(&mut T, T) -> Result<(), T>) -> bool {
+        let mut items = self.items.lock().unwrap();
+
+        let item = match items.back_mut() {
+            Some(last) => match merge(last, item) {
+                Ok(()) => return false,
+                Err(item) => item,
+            },
+            None => item,
+        };
+
+        items.push_back(item);
+        true
+    }
+
     pub fn pop(&self) -> Option<T> {
         self.items.lock().unwrap().pop_front()
     }
@@ -69,6 +90,32 @@ mod tests {
         assert_eq!(queue.pop(), None);
     }
 
+    #[test]
+    fn push_or_merge_only_merges_into_tail() {
+        let queue: Queue<(char, i32)> = Queue::new();
+
+        let merge = |queued: &mut (char, i32), item: (char, i32)| {
+            if queued.0 == item.0 {
+                queued.1 += item.1;
+                Ok(())
+            } else {
+                Err(item)
+            }
+        };
+
+        assert!(queue.push_or_merge(('a', 1), merge));
+        assert!(!queue.push_or_merge(('a', 2), merge));
+        assert!(queue.push_or_merge(('b', 3), merge));
+        assert!(queue.push_or_merge(('a', 4), merge));
+
+        assert_eq!(queue.pop(), Some(('a', 3)));
+        assert_eq!(queue.pop(), Some(('b', 3)));
+        assert_eq!(queue.pop(), Some(('a', 4)));
+        assert!(queue.push_or_merge(('a', 5), merge));
+        assert_eq!(queue.pop(), Some(('a', 5)));
+        assert_eq!(queue.pop(), None);
+    }
+
     #[test]
     fn multi_producer_single_consumer() {
         let queue: Arc<Queue<usize>> = Arc::new(Queue::new());
@@ -7,15 +7,18 @@
 use std::{
     cell::RefCell,
     collections::{HashMap, hash_map::Entry},
+    ffi::c_void,
     mem::ManuallyDrop,
+    rc::Rc,
     sync::{Mutex, OnceLock},
     thread::JoinHandle,
 };
 
 use gtk4::gio::ApplicationHoldGuard;
+use libffi::middle as libffi;
 use libloading::os::unix::{Library, RTLD_GLOBAL, RTLD_NOW};
 
-use crate::object::Object;
+use crate::{cif, introspection::IntrospectedFunction, object::Object};
 
 static GTK_THREAD_HANDLE: OnceLock<Mutex<Option<JoinHandle<()>>>> = OnceLock::new();
 
@@ -55,6 +58,12 @@ pub struct GtkThreadState {
     /// Wrapped in ManuallyDrop to prevent unloading libraries when the thread
     /// exits. This avoids crashes from TLS destructors in unloaded libraries.
     libraries: ManuallyDrop<HashMap<String, Library>>,
+    /// Cache of resolved function pointers by library and symbol name.
+    symbols: HashMap<String, HashMap<String, libffi::CodePtr>>,
+    /// Cache of prepared CIFs by call signature.
+    cifs: HashMap<cif::Signature, Rc<libffi::Cif>>,
+    /// Cache of functions resolved from typelibs by namespace, version and name.
+    introspected: HashMap<(String, String, String), Rc<IntrospectedFunction>>,
     /// Hold guard that keeps the GTK application alive.
     pub app_hold_guard: Option<ApplicationHoldGuard>,
 }
@@ -65,6 +74,9 @@ impl Default for GtkThreadState {
             object_map: ManuallyDrop::new(HashMap::new()),
             next_object_id: 1,
             libraries: ManuallyDrop::new(HashMap::new()),
+            symbols: HashMap::new(),
+            cifs: HashMap::new(),
+            introspected: HashMap::new(),
             app_hold_guard: None,
         }
     }
@@ -122,12 +134,88 @@ impl GtkThreadState {
             }
         }
     }
+
+    /// Gets or resolves a function pointer from a library.
+    ///
+    /// Resolved pointers are cached, so repeated calls skip the library
+    /// lookup and `dlsym`. They stay valid because libraries are never
+    /// unloaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the library cannot be loaded or does not export
+    /// the symbol.
+    pub fn get_symbol(
+        &mut self,
+        library_name: &str,
+        symbol_name: &str,
+    ) -> anyhow::Result<libffi::CodePtr> {
+        if let Some(ptr) = self
+            .symbols
+            .get(library_name)
+            .and_then(|symbols| symbols.get(symbol_name))
+        {
+            return Ok(*ptr);
+        }
+
+        let library = self.get_library(library_name)?;
+        let symbol = unsafe { library.get::<unsafe extern "C" fn()>(symbol_name.as_bytes())? };
+        let ptr = libffi::CodePtr(*symbol as *mut c_void);
+
+        self.symbols
+            .entry(library_name.to_string())
+            .or_default()
+            .insert(symbol_name.to_string(), ptr);
+
+        Ok(ptr)
+    }
+
+    /// Gets or prepares the CIF for a call signature.
+    ///
+    /// CIFs are shared by every function with the same signature.
+    pub fn get_cif(&mut self, signature: &cif::Signature) -> Rc<libffi::Cif> {
+        if let Some(cif) = self.cifs.get(signature) {
+            return cif.clone();
+        }
+
+        let cif = Rc::new(signature.to_cif());
+        self.cifs.insert(signature.clone(), cif.clone());
+        cif
+    }
+
+    /// Gets or resolves a function from a GObject-Introspection typelib.
+    ///
+    /// Resolved functions are cached, so repeated calls skip the typelib
+    /// lookup and the derivation of their types.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the function cannot be resolved.
+    pub fn get_introspected(
+        &mut self,
+        namespace: &str,
+        version: &str,
+        name: &str,
+    ) -> anyhow::Result<Rc<IntrospectedFunction>> {
+        let key = (namespace.to_string(), version.to_string(), name.to_string());
+
+        if let Some(function) = self.introspected.get(&key) {
+            return Ok(function.clone());
+        }
+
+        let function = Rc::new(crate::introspection::resolve(namespace, version, name)?);
+        self.introspected.insert(key, function.clone());
+        Ok(function)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils;
+    use crate::{
+        test_utils,
+        types::{GObjectType, IntegerSign, IntegerSize, IntegerType, StringType, Type},
+    };
 
     #[test]
     fn gtk_thread_state_default_initializes_correctly() {
@@ -216,6 +304,61 @@ mod tests {
         assert!(success);
     }
 
+    #[test]
+    fn get_symbol_caches_resolved_pointers() {
+        test_utils::ensure_gtk_init();
+
+        GtkThreadState::with(|state| {
+            let ptr1 = state.get_symbol("libglib-2.0.so.0", "g_free").unwrap();
+            let ptr2 = state.get_symbol("libglib-2.0.so.0", "g_free").unwrap();
+
+            assert_eq!(ptr1.0, ptr2.0);
+            assert!(state.symbols["libglib-2.0.so.0"].contains_key("g_free"));
+        });
+    }
+
+    #[test]
+    fn get_symbol_returns_error_for_missing_symbol() {
+        test_utils::ensure_gtk_init();
+
+        let is_err = GtkThreadState::with(|state| {
+            state
+                .get_symbol("libglib-2.0.so.0", "g_nonexistent_symbol_12345")
+                .is_err()
+        });
+
+        assert!(is_err);
+    }
+
+    #[test]
+    fn get_cif_shares_cifs_between_matching_signatures() {
+        let mut state = GtkThreadState::default();
+        let string = Type::String(StringType::new(false));
+        let gobject = Type::GObject(GObjectType::new(true));
+        let int32 = Type::Integer(IntegerType::new(IntegerSize::_32, IntegerSign::Signed));
+
+        let cif1 = state.get_cif(&cif::Signature::new([&string], &Type::Undefined));
+        let cif2 = state.get_cif(&cif::Signature::new([&gobject], &Type::Undefined));
+        let cif3 = state.get_cif(&cif::Signature::new([&int32], &Type::Undefined));
+
+        assert!(Rc::ptr_eq(&cif1, &cif2));
+        assert!(!Rc::ptr_eq(&cif1, &cif3));
+        assert_eq!(state.cifs.len(), 2);
+    }
+
+    #[test]
+    fn get_introspected_caches_resolved_functions() {
+        let mut state = GtkThreadState::default();
+
+        let first = state.get_introspected("GLib", "2.0", "ascii_strup").unwrap();
+        let second = state.get_introspected("GLib", "2.0", "ascii_strup").unwrap();
+
+        assert!(Rc::ptr_eq(&first, &second));
+        assert_eq!(state.introspected.len(), 1);
+        assert!(state.get_introspected("GLib", "2.0", "no_such_function").is_err());
+        assert_eq!(state.introspected.len(), 1);
+    }
+
     #[test]
     fn manually_drop_object_map_prevents_automatic_drop() {
         let state = GtkThreadState::default();
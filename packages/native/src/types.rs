@@ -10,21 +10,35 @@ use neon::prelude::*;
 
 mod array;
 mod boxed;
+mod bytes;
 mod callback;
+mod cancellable;
+mod r#enum;
 mod float;
 mod gobject;
+mod gtype;
+mod hash_table;
 mod integer;
 mod r#ref;
 mod string;
+mod r#struct;
+mod variant;
 
 pub use array::*;
 pub use boxed::*;
+pub use bytes::*;
 pub use callback::*;
+pub use cancellable::*;
+pub use r#enum::*;
 pub use float::*;
 pub use gobject::*;
+pub use gtype::*;
+pub use hash_table::*;
 pub use integer::*;
 pub use r#ref::*;
 pub use string::*;
+pub use r#struct::*;
+pub use variant::*;
 
 /// The type of trampoline function to use for a callback.
 ///
@@ -42,6 +56,16 @@ pub enum CallbackTrampoline {
     DrawFunc,
 }
 
+/// How queued emissions of an async callback are coalesced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoalescePolicy {
+    /// A new emission replaces the one still queued.
+    Latest,
+    /// New emissions are appended to the one still queued, and delivered
+    /// together as an array of argument arrays.
+    Batch,
+}
+
 /// Type descriptor for a callback function.
 #[derive(Debug, Clone)]
 pub struct CallbackType {
@@ -55,6 +79,12 @@ pub struct CallbackType {
     pub source_type: Option<Box<Type>>,
     /// Type of the result object (for async callbacks).
     pub result_type: Option<Box<Type>>,
+    /// Whether a closure callback is queued to JavaScript without waiting
+    /// for it to run. Only allowed for callbacks that return nothing.
+    pub is_async: bool,
+    /// How emissions of an async callback that are still queued are
+    /// coalesced. Implies `is_async`.
+    pub coalesce: Option<CoalescePolicy>,
 }
 
 /// A type descriptor for values crossing the FFI boundary.
@@ -81,10 +111,26 @@ pub enum Type {
     Boxed(BoxedType),
     /// Array type.
     Array(ArrayType),
+    /// GHashTable type.
+    HashTable(HashTableType),
     /// Callback function type.
     Callback(CallbackType),
     /// Reference (out-parameter) type.
     Ref(RefType),
+    /// Struct passed or returned by value.
+    Struct(StructType),
+    /// GVariant type.
+    Variant(VariantType),
+    /// `GError**` out-parameter, allocated by the call itself.
+    Error,
+    /// GLib enum type, validated against its GType.
+    Enum(EnumType),
+    /// GLib flags type, validated against its GType.
+    Flags(FlagsType),
+    /// Binary data, as `GBytes*` or a `guint8*` buffer.
+    Bytes(BytesType),
+    /// `GCancellable*` created for a JavaScript `AbortSignal`.
+    Cancellable,
 }
 
 impl Type {
@@ -116,6 +162,7 @@ impl Type {
             "gobject" => Ok(Type::GObject(GObjectType::from_js_value(cx, value)?)),
             "boxed" => Ok(Type::Boxed(BoxedType::from_js_value(cx, value)?)),
             "array" => Ok(Type::Array(ArrayType::from_js_value(cx, obj.upcast())?)),
+            "hashtable" => Ok(Type::HashTable(HashTableType::from_js_value(cx, value)?)),
             "callback" => {
                 let trampoline_handle: Option<Handle<JsString>> = obj.get_opt(cx, "trampoline")?;
                 let trampoline_str = trampoline_handle.map(|h| h.value(cx));
@@ -157,15 +204,45 @@ impl Type {
                     None => None,
                 };
 
+                let coalesce: Option<Handle<JsString>> = obj.get_opt(cx, "coalesce")?;
+                let coalesce = match coalesce.map(|h| h.value(cx)).as_deref() {
+                    None => None,
+                    Some("latest") => Some(CoalescePolicy::Latest),
+                    Some("batch") => Some(CoalescePolicy::Batch),
+                    Some(other) => {
+                        return cx.throw_type_error(format!("Unknown coalesce policy: {other}"));
+                    }
+                };
+
+                let is_async: Option<Handle<JsBoolean>> = obj.get_opt(cx, "async")?;
+                let is_async = is_async.is_some_and(|h| h.value(cx)) || coalesce.is_some();
+
+                if is_async
+                    && return_type
+                        .as_deref()
+                        .is_some_and(|type_| !matches!(type_, Type::Undefined))
+                {
+                    return cx.throw_type_error("Async callbacks cannot return a value");
+                }
+
                 Ok(Type::Callback(CallbackType {
                     trampoline,
                     arg_types,
                     return_type,
                     source_type,
                     result_type,
+                    is_async,
+                    coalesce,
                 }))
             }
             "ref" => Ok(Type::Ref(RefType::from_js_value(cx, obj.upcast())?)),
+            "struct" => Ok(Type::Struct(StructType::from_js_value(cx, value)?)),
+            "variant" => Ok(Type::Variant(VariantType::from_js_value(cx, value)?)),
+            "error" => Ok(Type::Error),
+            "enum" => Ok(Type::Enum(EnumType::from_js_value(cx, value)?)),
+            "flags" => Ok(Type::Flags(FlagsType::from_js_value(cx, value)?)),
+            "bytes" => Ok(Type::Bytes(BytesType::from_js_value(cx, value)?)),
+            "cancellable" => Ok(Type::Cancellable),
             _ => cx.throw_type_error(format!("Unknown type: {}", type_)),
         }
     }
@@ -182,9 +259,79 @@ impl From<&Type> for ffi::Type {
             Type::GObject(type_) => type_.into(),
             Type::Boxed(type_) => type_.into(),
             Type::Array(type_) => type_.into(),
+            Type::HashTable(type_) => type_.into(),
             Type::Callback(_) => ffi::Type::pointer(),
             Type::Ref(type_) => type_.into(),
+            Type::Struct(type_) => type_.into(),
+            Type::Variant(type_) => type_.into(),
+            Type::Error => ffi::Type::pointer(),
+            Type::Enum(type_) => type_.into(),
+            Type::Flags(type_) => type_.into(),
+            Type::Bytes(type_) => type_.into(),
+            Type::Cancellable => ffi::Type::pointer(),
             Type::Undefined => ffi::Type::void(),
         }
     }
 }
+
+/// The libffi type a value is passed as, in a hashable form.
+///
+/// Type descriptors that are passed the same way map to the same key, which
+/// lets prepared CIFs be shared between functions with matching signatures.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FfiTypeKey {
+    /// No value.
+    Void,
+    /// An integer of the given size and sign.
+    Integer(IntegerSize, IntegerSign),
+    /// A floating-point number of the given size.
+    Float(FloatSize),
+    /// Any pointer.
+    Pointer,
+    /// A struct passed by value, with its field types in order.
+    Struct(Vec<FfiTypeKey>),
+}
+
+impl From<&Type> for FfiTypeKey {
+    fn from(value: &Type) -> Self {
+        match value {
+            Type::Integer(type_) => FfiTypeKey::Integer(type_.size, type_.sign),
+            Type::Float(type_) => FfiTypeKey::Float(type_.size),
+            Type::Boolean => FfiTypeKey::Integer(IntegerSize::_8, IntegerSign::Unsigned),
+            Type::Enum(_) => FfiTypeKey::Integer(IntegerSize::_32, IntegerSign::Signed),
+            Type::Flags(_) => FfiTypeKey::Integer(IntegerSize::_32, IntegerSign::Unsigned),
+            Type::Struct(type_) => FfiTypeKey::Struct(
+                type_
+                    .fields
+                    .iter()
+                    .map(|field| FfiTypeKey::from(&field.type_))
+                    .collect(),
+            ),
+            Type::Undefined => FfiTypeKey::Void,
+            Type::String(_)
+            | Type::Null
+            | Type::GObject(_)
+            | Type::Boxed(_)
+            | Type::Array(_)
+            | Type::HashTable(_)
+            | Type::Callback(_)
+            | Type::Ref(_)
+            | Type::Variant(_)
+            | Type::Error
+            | Type::Bytes(_)
+            | Type::Cancellable => FfiTypeKey::Pointer,
+        }
+    }
+}
+
+impl From<&FfiTypeKey> for ffi::Type {
+    fn from(value: &FfiTypeKey) -> Self {
+        match value {
+            FfiTypeKey::Void => ffi::Type::void(),
+            FfiTypeKey::Integer(size, sign) => (&IntegerType::new(*size, *sign)).into(),
+            FfiTypeKey::Float(size) => (&FloatType::new(*size)).into(),
+            FfiTypeKey::Pointer => ffi::Type::pointer(),
+            FfiTypeKey::Struct(fields) => ffi::Type::structure(fields.iter().map(ffi::Type::from)),
+        }
+    }
+}
@@ -3,7 +3,7 @@
 use libffi::middle as ffi;
 use neon::prelude::*;
 
-use crate::types::Type;
+use crate::types::{IntegerSize, Type, is_pointer_storable, type_layout};
 
 /// The underlying list implementation for an array type.
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -15,6 +15,53 @@ pub enum ListType {
     GList,
     /// GLib singly-linked list.
     GSList,
+    /// `GPtrArray`, with items stored as pointers.
+    PtrArray,
+    /// `GArray`, with items stored inline.
+    GArray,
+    /// `GByteArray`, with 8-bit integer items.
+    ByteArray,
+}
+
+/// Where the length of a sized C array comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayLength {
+    /// The array always has this many items.
+    Fixed(usize),
+    /// The length is passed in, or written to, the argument at this index.
+    Arg(usize),
+    /// The length is the function's return value.
+    Return,
+}
+
+impl ArrayLength {
+    /// Reads the `length` (fixed) or `lengthArg` (argument index, or
+    /// `"return"`) property of a sized type descriptor.
+    pub fn from_js_object(
+        cx: &mut FunctionContext,
+        obj: Handle<JsObject>,
+    ) -> NeonResult<Option<ArrayLength>> {
+        let length: Option<Handle<JsNumber>> = obj.get_opt(cx, "length")?;
+
+        if let Some(length) = length {
+            return Ok(Some(ArrayLength::Fixed(length.value(cx) as usize)));
+        }
+
+        let length_arg: Option<Handle<JsValue>> = obj.get_opt(cx, "lengthArg")?;
+
+        let Some(length_arg) = length_arg else {
+            return Ok(None);
+        };
+
+        if let Ok(index) = length_arg.downcast::<JsNumber, _>(cx) {
+            return Ok(Some(ArrayLength::Arg(index.value(cx) as usize)));
+        }
+
+        match length_arg.downcast::<JsString, _>(cx) {
+            Ok(source) if source.value(cx) == "return" => Ok(Some(ArrayLength::Return)),
+            _ => cx.throw_type_error("lengthArg must be an argument index or \"return\""),
+        }
+    }
 }
 
 /// Type descriptor for array types.
@@ -26,6 +73,11 @@ pub struct ArrayType {
     pub list_type: ListType,
     /// Whether the array memory is borrowed from the callee.
     pub is_borrowed: bool,
+    /// The length of a sized C array, if it is not null-terminated.
+    ///
+    /// Items of sized arrays are stored inline, and their ownership follows
+    /// the item type; `is_borrowed` only covers the array itself.
+    pub length: Option<ArrayLength>,
 }
 
 impl ArrayType {
@@ -35,6 +87,7 @@ impl ArrayType {
             item_type: Box::new(item_type),
             list_type: ListType::Array,
             is_borrowed: false,
+            length: None,
         }
     }
 
@@ -52,16 +105,45 @@ impl ArrayType {
         let list_type = match list_type_str.map(|s| s.value(cx)).as_deref() {
             Some("glist") => ListType::GList,
             Some("gslist") => ListType::GSList,
+            Some("gptrarray") => ListType::PtrArray,
+            Some("garray") => ListType::GArray,
+            Some("gbytearray") => ListType::ByteArray,
             _ => ListType::Array,
         };
 
+        let is_supported_item = match list_type {
+            ListType::PtrArray => is_pointer_storable(&item_type),
+            ListType::GArray => type_layout(&item_type).is_some(),
+            ListType::ByteArray => {
+                matches!(&item_type, Type::Integer(int_type) if int_type.size == IntegerSize::_8)
+            }
+            ListType::Array | ListType::GList | ListType::GSList => true,
+        };
+
+        if !is_supported_item {
+            return cx.throw_type_error(format!(
+                "Unsupported item type for {:?}: {:?}",
+                list_type, item_type
+            ));
+        }
+
         let is_borrowed: Option<Handle<JsBoolean>> = obj.get_opt(cx, "borrowed")?;
         let is_borrowed = is_borrowed.map(|b| b.value(cx)).unwrap_or(false);
 
+        let length = ArrayLength::from_js_object(cx, obj)?;
+
+        if length.is_some() && type_layout(&item_type).is_none() {
+            return cx.throw_type_error(format!(
+                "Unsupported item type for sized array: {:?}",
+                item_type
+            ));
+        }
+
         Ok(ArrayType {
             item_type: Box::new(item_type),
             list_type,
             is_borrowed,
+            length,
         })
     }
 }
@@ -1,10 +1,10 @@
 //! Boxed type descriptor.
 
-use gtk4::glib::{self, translate::FromGlib as _};
+use gtk4::glib;
 use libffi::middle as ffi;
 use neon::prelude::*;
 
-use crate::state::GtkThreadState;
+use crate::types::resolve_gtype;
 
 /// Type descriptor for GLib boxed types.
 ///
@@ -81,48 +81,14 @@ impl BoxedType {
     /// First tries to look up the type by name. If not registered, tries to
     /// load it dynamically from the specified library.
     pub fn get_gtype(&self) -> Option<glib::Type> {
-        if let Some(gtype) = glib::Type::from_name(&self.type_) {
-            return Some(gtype);
-        }
-
-        let lib_name = self.lib.as_ref()?;
-        let get_type_fn = self
-            .get_type_fn
-            .clone()
-            .unwrap_or_else(|| type_name_to_get_type_fn(&self.type_));
-
-        GtkThreadState::with(|state| {
-            let library = state.get_library(lib_name).ok()?;
-            let symbol = unsafe {
-                library
-                    .get::<unsafe extern "C" fn() -> glib::ffi::GType>(get_type_fn.as_bytes())
-                    .ok()?
-            };
-            let gtype_raw = unsafe { symbol() };
-            let gtype = unsafe { glib::Type::from_glib(gtype_raw) };
-            Some(gtype)
-        })
+        resolve_gtype(
+            &self.type_,
+            self.lib.as_deref(),
+            self.get_type_fn.as_deref(),
+        )
     }
 }
 
-fn type_name_to_get_type_fn(type_name: &str) -> String {
-    let mut result = String::new();
-
-    for c in type_name.chars() {
-        if c.is_uppercase() {
-            if !result.is_empty() {
-                result.push('_');
-            }
-            result.push(c.to_ascii_lowercase());
-        } else {
-            result.push(c);
-        }
-    }
-
-    result.push_str("_get_type");
-    result
-}
-
 impl From<&BoxedType> for ffi::Type {
     fn from(_value: &BoxedType) -> Self {
         ffi::Type::pointer()
@@ -0,0 +1,72 @@
+//! Bytes type descriptor.
+
+use libffi::middle as ffi;
+use neon::prelude::*;
+
+use crate::types::ArrayLength;
+
+/// How binary data is represented on the native side.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BytesKind {
+    /// A reference-counted `GBytes*`.
+    #[default]
+    GBytes,
+    /// A raw `guint8*` buffer, sized by a length.
+    Pointer,
+}
+
+/// Type descriptor for binary data.
+///
+/// On the JavaScript side, bytes are `Buffer`, `Uint8Array` or `ArrayBuffer`
+/// values. Returned `GBytes` are exposed as externally backed buffers that
+/// keep the native data alive until they are garbage collected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BytesType {
+    /// The native representation of the data.
+    pub kind: BytesKind,
+    /// Whether the data is borrowed from the callee.
+    pub is_borrowed: bool,
+    /// The length of a `guint8*` buffer.
+    pub length: Option<ArrayLength>,
+}
+
+impl BytesType {
+    /// Creates a new bytes type descriptor.
+    pub fn new(kind: BytesKind, is_borrowed: bool, length: Option<ArrayLength>) -> Self {
+        BytesType {
+            kind,
+            is_borrowed,
+            length,
+        }
+    }
+
+    /// Parses a bytes type from a JavaScript object.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NeonResult` error if the object is malformed or the kind
+    /// is unknown.
+    pub fn from_js_value(cx: &mut FunctionContext, value: Handle<JsValue>) -> NeonResult<Self> {
+        let obj = value.downcast::<JsObject, _>(cx).or_throw(cx)?;
+
+        let kind: Option<Handle<JsString>> = obj.get_opt(cx, "kind")?;
+        let kind = match kind.map(|k| k.value(cx)).as_deref() {
+            None | Some("gbytes") => BytesKind::GBytes,
+            Some("pointer") => BytesKind::Pointer,
+            Some(other) => return cx.throw_type_error(format!("Unknown bytes kind: {}", other)),
+        };
+
+        let is_borrowed: Option<Handle<JsBoolean>> = obj.get_opt(cx, "borrowed")?;
+        let is_borrowed = is_borrowed.map(|b| b.value(cx)).unwrap_or(false);
+
+        let length = ArrayLength::from_js_object(cx, obj)?;
+
+        Ok(Self::new(kind, is_borrowed, length))
+    }
+}
+
+impl From<&BytesType> for ffi::Type {
+    fn from(_value: &BytesType) -> Self {
+        ffi::Type::pointer()
+    }
+}
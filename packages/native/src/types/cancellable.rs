@@ -0,0 +1,182 @@
+//! JavaScript `AbortSignal` bridged to a `GCancellable`.
+
+use std::{
+    fmt,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use gtk4::{gio, glib, prelude::*};
+use neon::prelude::*;
+
+use crate::gtk_dispatch;
+
+/// The state shared between an `AbortSignal` and the `GCancellable` created for it.
+#[derive(Default)]
+struct Link {
+    /// Whether the signal has aborted.
+    aborted: AtomicBool,
+    /// The cancellable, once created on the GTK thread.
+    ///
+    /// Only a weak reference is kept, so the cancellable is freed along with
+    /// the operation that uses it.
+    cancellable: Mutex<Option<glib::WeakRef<gio::Cancellable>>>,
+    /// The signal, until its abort listener is added.
+    signal: Mutex<Option<Root<JsObject>>>,
+    /// The signal and its abort listener, until the listener is removed.
+    listener: Mutex<Option<(Root<JsObject>, Root<JsFunction>)>>,
+}
+
+impl Link {
+    /// Records the abort on the JS thread and cancels the cancellable on the
+    /// GTK thread.
+    fn abort(self: &Arc<Self>) {
+        self.aborted.store(true, Ordering::Release);
+
+        let link = Arc::clone(self);
+        gtk_dispatch::schedule(move || {
+            let cancellable = link
+                .cancellable
+                .lock()
+                .unwrap()
+                .as_ref()
+                .and_then(glib::WeakRef::upgrade);
+
+            if let Some(cancellable) = cancellable {
+                cancellable.cancel();
+            }
+        });
+    }
+
+    /// Adds the abort listener to the signal, or aborts right away if the
+    /// signal aborted before the listener could be added.
+    fn add_listener<'a, C: Context<'a>>(self: &Arc<Self>, cx: &mut C) -> NeonResult<()> {
+        let Some(signal) = self.signal.lock().unwrap().take() else {
+            return Ok(());
+        };
+
+        let signal = signal.into_inner(cx);
+        let aborted: Option<Handle<JsBoolean>> = signal.get_opt(cx, "aborted")?;
+
+        if aborted.is_some_and(|aborted| aborted.value(cx)) {
+            self.abort();
+            return Ok(());
+        }
+
+        let link = Arc::clone(self);
+        let listener = JsFunction::new(cx, move |mut cx| {
+            link.abort();
+            Ok(cx.undefined())
+        })?;
+
+        let add_event_listener: Handle<JsFunction> = signal.get(cx, "addEventListener")?;
+        let event = cx.string("abort");
+
+        add_event_listener
+            .call_with(cx)
+            .this(signal)
+            .arg(event)
+            .arg(listener)
+            .exec(cx)?;
+
+        *self.listener.lock().unwrap() = Some((signal.root(cx), listener.root(cx)));
+        Ok(())
+    }
+
+    fn remove_listener<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<()> {
+        let Some((signal, listener)) = self.listener.lock().unwrap().take() else {
+            return Ok(());
+        };
+
+        let signal = signal.into_inner(cx);
+        let listener = listener.into_inner(cx);
+        let remove_event_listener: Handle<JsFunction> = signal.get(cx, "removeEventListener")?;
+        let event = cx.string("abort");
+
+        remove_event_listener
+            .call_with(cx)
+            .this(signal)
+            .arg(event)
+            .arg(listener)
+            .exec(cx)
+    }
+}
+
+/// A JavaScript `AbortSignal` passed where a `GCancellable` is expected.
+///
+/// The `GCancellable` is created on the GTK thread when the call is made, and
+/// only then is an abort listener added to the signal, so that a call failing
+/// before it leaves no listener behind. Aborting the signal cancels the
+/// cancellable on the GTK thread. Once the cancellable is freed, after the
+/// asynchronous operation using it has completed, the listener is removed.
+#[derive(Clone)]
+pub struct AbortSignal {
+    link: Arc<Link>,
+    /// Channel for removing the abort listener on the JavaScript thread.
+    channel: Channel,
+}
+
+impl fmt::Debug for AbortSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AbortSignal")
+            .field("aborted", &self.link.aborted.load(Ordering::Acquire))
+            .finish_non_exhaustive()
+    }
+}
+
+impl AbortSignal {
+    /// Wraps a JavaScript `AbortSignal`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NeonResult` error if the value is not an event target.
+    pub fn from_js_value<'a, C: Context<'a>>(
+        cx: &mut C,
+        value: Handle<JsValue>,
+    ) -> NeonResult<Self> {
+        let signal = value.downcast::<JsObject, _>(cx).or_throw(cx)?;
+        let aborted: Option<Handle<JsBoolean>> = signal.get_opt(cx, "aborted")?;
+        let aborted = aborted.is_some_and(|aborted| aborted.value(cx));
+        let _: Handle<JsFunction> = signal.get(cx, "addEventListener")?;
+
+        let link = Arc::new(Link {
+            aborted: AtomicBool::new(aborted),
+            signal: Mutex::new((!aborted).then(|| signal.root(cx))),
+            ..Default::default()
+        });
+
+        let mut channel = cx.channel();
+        channel.unref(cx);
+
+        Ok(AbortSignal { link, channel })
+    }
+
+    /// Creates the `GCancellable` for a call. Must be called on the GTK thread.
+    ///
+    /// The cancellable starts out cancelled if the signal has already aborted,
+    /// and the abort listener is added to the signal on the JS thread.
+    pub fn create_cancellable(&self) -> gio::Cancellable {
+        let cancellable = gio::Cancellable::new();
+        *self.link.cancellable.lock().unwrap() = Some(cancellable.downgrade());
+
+        if self.link.aborted.load(Ordering::Acquire) {
+            cancellable.cancel();
+        }
+
+        let link = Arc::clone(&self.link);
+        self.channel.send(move |mut cx| link.add_listener(&mut cx));
+
+        // Sent through the same channel, so the listener is removed after it
+        // has been added.
+        let link = Arc::clone(&self.link);
+        let channel = self.channel.clone();
+
+        cancellable.add_weak_ref_notify(move || {
+            channel.send(move |mut cx| link.remove_listener(&mut cx));
+        });
+
+        cancellable
+    }
+}
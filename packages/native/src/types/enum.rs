@@ -0,0 +1,414 @@
+//! Enum and flags type descriptors.
+
+use anyhow::bail;
+use gtk4::glib;
+use libffi::middle as ffi;
+use neon::prelude::*;
+
+use crate::{types::resolve_gtype, value::Value};
+
+/// Type descriptor for GLib enums (`GEnum`).
+///
+/// Values are validated against the values registered for the enum's GType.
+/// JavaScript may pass a number, a nick (e.g. `"vertical"`) or a full name
+/// (e.g. `"GTK_ORIENTATION_VERTICAL"`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumType {
+    /// The GLib type name (e.g., "GtkOrientation").
+    pub type_: String,
+    /// Optional library name for dynamic type lookup.
+    pub lib: Option<String>,
+    /// Optional explicit get_type function name (when naive transformation doesn't work).
+    pub get_type_fn: Option<String>,
+    /// Whether values are returned to JavaScript as nicks instead of numbers.
+    pub returns_nick: bool,
+}
+
+impl EnumType {
+    /// Creates a new enum type descriptor.
+    pub fn new(
+        type_: String,
+        lib: Option<String>,
+        get_type_fn: Option<String>,
+        returns_nick: bool,
+    ) -> Self {
+        EnumType {
+            type_,
+            lib,
+            get_type_fn,
+            returns_nick,
+        }
+    }
+
+    /// Parses an enum type from a JavaScript object.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NeonResult` error if the object is malformed.
+    pub fn from_js_value(cx: &mut FunctionContext, value: Handle<JsValue>) -> NeonResult<Self> {
+        let (type_, lib, get_type_fn, returns_nick) = parse_registered_type(cx, value)?;
+        Ok(Self::new(type_, lib, get_type_fn, returns_nick))
+    }
+
+    /// Gets the GLib type for this enum.
+    ///
+    /// Resolved the same way as [`BoxedType::get_gtype`](crate::types::BoxedType::get_gtype).
+    pub fn get_gtype(&self) -> Option<glib::Type> {
+        resolve_gtype(
+            &self.type_,
+            self.lib.as_deref(),
+            self.get_type_fn.as_deref(),
+        )
+    }
+
+    /// Gets the enum class describing the registered values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type cannot be resolved or is not an enum.
+    pub fn enum_class(&self) -> anyhow::Result<glib::EnumClass> {
+        self.get_gtype()
+            .and_then(glib::EnumClass::with_type)
+            .ok_or_else(|| anyhow::anyhow!("Unknown enum type: {}", self.type_))
+    }
+
+    /// Converts a JavaScript value to the enum's integer value.
+    ///
+    /// Numbers must be defined by the enum; strings are looked up by nick
+    /// first and then by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not defined by the enum.
+    pub fn to_raw(&self, value: &Value) -> anyhow::Result<i32> {
+        let class = self.enum_class()?;
+
+        let enum_value = match value {
+            Value::Number(n)
+                if n.fract() == 0.0 && *n >= i32::MIN as f64 && *n <= i32::MAX as f64 =>
+            {
+                class.value(*n as i32)
+            }
+            Value::Number(_) => None,
+            Value::String(s) => class.value_by_nick(s).or_else(|| class.value_by_name(s)),
+            _ => bail!("Expected a Number or String for enum type, got {:?}", value),
+        };
+
+        match enum_value {
+            Some(enum_value) => Ok(enum_value.value()),
+            None => bail!("Value {:?} is not defined by enum {}", value, self.type_),
+        }
+    }
+
+    /// Converts the enum's integer value to a JavaScript value.
+    ///
+    /// Returns the nick when `returns_nick` is set and the value is defined,
+    /// and the number otherwise.
+    pub fn to_value(&self, raw: i32) -> Value {
+        if self.returns_nick
+            && let Ok(class) = self.enum_class()
+            && let Some(enum_value) = class.value(raw)
+        {
+            return Value::String(enum_value.nick().to_string());
+        }
+
+        Value::Number(raw as f64)
+    }
+}
+
+/// Type descriptor for GLib flags (`GFlags`).
+///
+/// Values are validated against the bits registered for the flags' GType.
+/// JavaScript may pass a number, a nick or name, a `|`-separated list of
+/// nicks or names, or an array of any of these.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlagsType {
+    /// The GLib type name (e.g., "GtkStateFlags").
+    pub type_: String,
+    /// Optional library name for dynamic type lookup.
+    pub lib: Option<String>,
+    /// Optional explicit get_type function name (when naive transformation doesn't work).
+    pub get_type_fn: Option<String>,
+    /// Whether values are returned to JavaScript as arrays of nicks instead of numbers.
+    pub returns_nick: bool,
+}
+
+impl FlagsType {
+    /// Creates a new flags type descriptor.
+    pub fn new(
+        type_: String,
+        lib: Option<String>,
+        get_type_fn: Option<String>,
+        returns_nick: bool,
+    ) -> Self {
+        FlagsType {
+            type_,
+            lib,
+            get_type_fn,
+            returns_nick,
+        }
+    }
+
+    /// Parses a flags type from a JavaScript object.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NeonResult` error if the object is malformed.
+    pub fn from_js_value(cx: &mut FunctionContext, value: Handle<JsValue>) -> NeonResult<Self> {
+        let (type_, lib, get_type_fn, returns_nick) = parse_registered_type(cx, value)?;
+        Ok(Self::new(type_, lib, get_type_fn, returns_nick))
+    }
+
+    /// Gets the GLib type for these flags.
+    ///
+    /// Resolved the same way as [`BoxedType::get_gtype`](crate::types::BoxedType::get_gtype).
+    pub fn get_gtype(&self) -> Option<glib::Type> {
+        resolve_gtype(
+            &self.type_,
+            self.lib.as_deref(),
+            self.get_type_fn.as_deref(),
+        )
+    }
+
+    /// Gets the flags class describing the registered bits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the type cannot be resolved or is not a flags type.
+    pub fn flags_class(&self) -> anyhow::Result<glib::FlagsClass> {
+        self.get_gtype()
+            .and_then(glib::FlagsClass::with_type)
+            .ok_or_else(|| anyhow::anyhow!("Unknown flags type: {}", self.type_))
+    }
+
+    /// Converts a JavaScript value to the flags' integer value.
+    ///
+    /// Accepts a number, a nick or name, a `|`-separated list of nicks or
+    /// names, or an array of any of these. Every bit must be defined by the
+    /// flags type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value sets a bit the flags type does not define.
+    pub fn to_raw(&self, value: &Value) -> anyhow::Result<u32> {
+        let class = self.flags_class()?;
+        let raw = flags_bits(&class, value)?;
+        let mask = class.values().iter().fold(0, |mask, v| mask | v.value());
+
+        if raw & !mask != 0 {
+            bail!("Value {:?} is not defined by flags {}", value, self.type_);
+        }
+
+        Ok(raw)
+    }
+
+    /// Converts the flags' integer value to a JavaScript value.
+    ///
+    /// Returns an array of the nicks of the set flags when `returns_nick` is
+    /// set and every bit is defined, and the number otherwise.
+    pub fn to_value(&self, raw: u32) -> Value {
+        if !self.returns_nick {
+            return Value::Number(raw as f64);
+        }
+
+        let Ok(class) = self.flags_class() else {
+            return Value::Number(raw as f64);
+        };
+
+        let mut remaining = raw;
+        let mut nicks = Vec::new();
+
+        for flags_value in class.values() {
+            let bits = flags_value.value();
+
+            if bits != 0 && remaining & bits == bits {
+                nicks.push(Value::String(flags_value.nick().to_string()));
+                remaining &= !bits;
+            }
+        }
+
+        if remaining != 0 {
+            return Value::Number(raw as f64);
+        }
+
+        Value::Array(nicks)
+    }
+}
+
+fn flags_bits(class: &glib::FlagsClass, value: &Value) -> anyhow::Result<u32> {
+    match value {
+        Value::Number(n) if n.fract() == 0.0 && *n >= 0.0 && *n <= u32::MAX as f64 => Ok(*n as u32),
+        Value::Number(_) => bail!("Value {:?} is not a valid flags value", value),
+        Value::String(s) => s.split('|').map(str::trim).try_fold(0, |bits, name| {
+            class
+                .value_by_nick(name)
+                .or_else(|| class.value_by_name(name))
+                .map(|v| bits | v.value())
+                .ok_or_else(|| anyhow::anyhow!("Unknown flag {:?} for {}", name, class.type_()))
+        }),
+        Value::Array(items) => items
+            .iter()
+            .try_fold(0, |bits, item| Ok(bits | flags_bits(class, item)?)),
+        _ => bail!(
+            "Expected a Number, String or Array for flags type, got {:?}",
+            value
+        ),
+    }
+}
+
+/// Parses the `innerType`, `lib`, `getTypeFn` and `nick` properties shared by
+/// enum and flags descriptors.
+fn parse_registered_type(
+    cx: &mut FunctionContext,
+    value: Handle<JsValue>,
+) -> NeonResult<(String, Option<String>, Option<String>, bool)> {
+    let obj = value.downcast::<JsObject, _>(cx).or_throw(cx)?;
+
+    let type_: Handle<JsString> = obj.prop(cx, "innerType").get()?;
+    let type_ = type_.value(cx);
+
+    let lib: Option<Handle<JsString>> = obj.get_opt(cx, "lib")?;
+    let lib = lib.map(|s| s.value(cx));
+
+    let get_type_fn: Option<Handle<JsString>> = obj.get_opt(cx, "getTypeFn")?;
+    let get_type_fn = get_type_fn.map(|s| s.value(cx));
+
+    let returns_nick: Option<Handle<JsBoolean>> = obj.get_opt(cx, "nick")?;
+    let returns_nick = returns_nick.map(|b| b.value(cx)).unwrap_or(false);
+
+    Ok((type_, lib, get_type_fn, returns_nick))
+}
+
+impl From<&EnumType> for ffi::Type {
+    fn from(_value: &EnumType) -> Self {
+        ffi::Type::i32()
+    }
+}
+
+impl From<&FlagsType> for ffi::Type {
+    fn from(_value: &FlagsType) -> Self {
+        ffi::Type::u32()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gtk4::prelude::StaticType as _;
+
+    use super::*;
+    use crate::types::Type;
+
+    fn orientation(returns_nick: bool) -> EnumType {
+        gtk4::Orientation::static_type();
+        EnumType::new("GtkOrientation".to_string(), None, None, returns_nick)
+    }
+
+    fn state_flags(returns_nick: bool) -> FlagsType {
+        gtk4::StateFlags::static_type();
+        FlagsType::new("GtkStateFlags".to_string(), None, None, returns_nick)
+    }
+
+    #[test]
+    fn enum_accepts_numbers_nicks_and_names() {
+        let type_ = orientation(false);
+
+        assert_eq!(type_.to_raw(&Value::Number(1.0)).unwrap(), 1);
+        assert_eq!(type_.to_raw(&Value::String("vertical".into())).unwrap(), 1);
+        assert_eq!(
+            type_
+                .to_raw(&Value::String("GTK_ORIENTATION_HORIZONTAL".into()))
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn enum_rejects_undefined_values() {
+        let type_ = orientation(false);
+
+        assert!(type_.to_raw(&Value::Number(7.0)).is_err());
+        assert!(type_.to_raw(&Value::Number(0.5)).is_err());
+        assert!(type_.to_raw(&Value::Number(2f64.powi(40))).is_err());
+        assert!(type_.to_raw(&Value::Number(-1e20)).is_err());
+        assert!(type_.to_raw(&Value::String("diagonal".into())).is_err());
+        assert!(type_.to_raw(&Value::Boolean(true)).is_err());
+    }
+
+    #[test]
+    fn enum_returns_nick_when_requested() {
+        assert!(matches!(orientation(false).to_value(1), Value::Number(n) if n == 1.0));
+        assert!(matches!(orientation(true).to_value(1), Value::String(s) if s == "vertical"));
+        assert!(matches!(orientation(true).to_value(42), Value::Number(n) if n == 42.0));
+    }
+
+    #[test]
+    fn flags_combine_nicks_names_and_arrays() {
+        let type_ = state_flags(false);
+        let active = gtk4::StateFlags::ACTIVE.bits();
+        let prelight = gtk4::StateFlags::PRELIGHT.bits();
+
+        assert_eq!(
+            type_
+                .to_raw(&Value::String("active|GTK_STATE_FLAG_PRELIGHT".into()))
+                .unwrap(),
+            active | prelight
+        );
+        assert_eq!(
+            type_
+                .to_raw(&Value::Array(vec![
+                    Value::String("active".into()),
+                    Value::Number(prelight as f64),
+                ]))
+                .unwrap(),
+            active | prelight
+        );
+    }
+
+    #[test]
+    fn flags_reject_undefined_bits() {
+        let type_ = state_flags(false);
+
+        assert!(type_.to_raw(&Value::Number((1u32 << 31) as f64)).is_err());
+        assert!(type_.to_raw(&Value::String("active|bogus".into())).is_err());
+    }
+
+    #[test]
+    fn callback_returns_reject_undefined_values() {
+        let enum_type = Type::Enum(orientation(false));
+        let flags_type = Type::Flags(state_flags(false));
+
+        let err = Value::Number(7.0)
+            .into_glib_value_with_default(Some(&enum_type))
+            .unwrap_err();
+        assert!(err.to_string().contains("GtkOrientation"));
+        assert!(err.to_string().contains("7"));
+
+        assert!(
+            Value::String("active|bogus".into())
+                .into_glib_value_with_default(Some(&flags_type))
+                .is_err()
+        );
+        assert!(
+            Value::Undefined
+                .into_glib_value_with_default(Some(&enum_type))
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn flags_return_nicks_when_requested() {
+        let raw = (gtk4::StateFlags::ACTIVE | gtk4::StateFlags::FOCUSED).bits();
+
+        assert!(matches!(state_flags(false).to_value(raw), Value::Number(n) if n == raw as f64));
+
+        let Value::Array(nicks) = state_flags(true).to_value(raw) else {
+            panic!("Expected an array of nicks");
+        };
+
+        assert!(matches!(
+            nicks.as_slice(),
+            [Value::String(a), Value::String(b)] if a == "active" && b == "focused"
+        ));
+    }
+}
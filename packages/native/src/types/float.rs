@@ -4,7 +4,7 @@ use libffi::middle as ffi;
 use neon::prelude::*;
 
 /// Size of a floating point type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FloatSize {
     /// 32-bit float (f32).
     _32,
@@ -0,0 +1,55 @@
+//! GType resolution for named types.
+
+use gtk4::glib::{self, translate::FromGlib as _};
+
+use crate::state::GtkThreadState;
+
+/// Resolves a GType by name.
+///
+/// First tries to look up the type by name. If not registered, calls the
+/// type's `get_type` function from the specified library, which registers it.
+/// The function name is derived from the type name unless given explicitly.
+pub fn resolve_gtype(
+    type_name: &str,
+    lib: Option<&str>,
+    get_type_fn: Option<&str>,
+) -> Option<glib::Type> {
+    if let Some(gtype) = glib::Type::from_name(type_name) {
+        return Some(gtype);
+    }
+
+    let lib_name = lib?;
+    let get_type_fn = get_type_fn
+        .map(str::to_string)
+        .unwrap_or_else(|| type_name_to_get_type_fn(type_name));
+
+    GtkThreadState::with(|state| {
+        let library = state.get_library(lib_name).ok()?;
+        let symbol = unsafe {
+            library
+                .get::<unsafe extern "C" fn() -> glib::ffi::GType>(get_type_fn.as_bytes())
+                .ok()?
+        };
+        let gtype_raw = unsafe { symbol() };
+        let gtype = unsafe { glib::Type::from_glib(gtype_raw) };
+        Some(gtype)
+    })
+}
+
+fn type_name_to_get_type_fn(type_name: &str) -> String {
+    let mut result = String::new();
+
+    for c in type_name.chars() {
+        if c.is_uppercase() {
+            if !result.is_empty() {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+
+    result.push_str("_get_type");
+    result
+}
@@ -0,0 +1,75 @@
+//! Hash table type descriptor.
+
+use libffi::middle as ffi;
+use neon::prelude::*;
+
+use crate::types::Type;
+
+/// Type descriptor for `GHashTable*` values.
+///
+/// Keys and values are stored directly in the table's pointer slots, so only
+/// pointer-sized types are supported: strings, integers and booleans (packed
+/// with `GINT_TO_POINTER`), GObjects and boxed types.
+#[derive(Debug, Clone)]
+pub struct HashTableType {
+    /// The type of the table keys.
+    pub key_type: Box<Type>,
+    /// The type of the table values.
+    pub value_type: Box<Type>,
+    /// Whether the table is borrowed from (or lent to) the callee.
+    pub is_borrowed: bool,
+}
+
+impl HashTableType {
+    /// Creates a new hash table type with the given key and value types.
+    pub fn new(key_type: Type, value_type: Type, is_borrowed: bool) -> Self {
+        HashTableType {
+            key_type: Box::new(key_type),
+            value_type: Box::new(value_type),
+            is_borrowed,
+        }
+    }
+
+    /// Parses a hash table type from a JavaScript object.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NeonResult` error if the object is malformed or the key or
+    /// value type cannot be stored in a hash table.
+    pub fn from_js_value(cx: &mut FunctionContext, value: Handle<JsValue>) -> NeonResult<Self> {
+        let obj = value.downcast::<JsObject, _>(cx).or_throw(cx)?;
+        let key_type_value: Handle<'_, JsValue> = obj.prop(cx, "keyType").get()?;
+        let key_type = Type::from_js_value(cx, key_type_value)?;
+        let value_type_value: Handle<'_, JsValue> = obj.prop(cx, "valueType").get()?;
+        let value_type = Type::from_js_value(cx, value_type_value)?;
+
+        for type_ in [&key_type, &value_type] {
+            if !is_pointer_storable(type_) {
+                return cx.throw_type_error(format!(
+                    "Unsupported hash table key or value type: {:?}",
+                    type_
+                ));
+            }
+        }
+
+        let is_borrowed: Option<Handle<JsBoolean>> = obj.get_opt(cx, "borrowed")?;
+        let is_borrowed = is_borrowed.map(|b| b.value(cx)).unwrap_or(false);
+
+        Ok(Self::new(key_type, value_type, is_borrowed))
+    }
+}
+
+/// Returns whether values of a type can be stored in a pointer-sized slot of
+/// a `GHashTable` or `GPtrArray`.
+pub fn is_pointer_storable(type_: &Type) -> bool {
+    matches!(
+        type_,
+        Type::String(_) | Type::Integer(_) | Type::Boolean | Type::GObject(_) | Type::Boxed(_)
+    )
+}
+
+impl From<&HashTableType> for ffi::Type {
+    fn from(_value: &HashTableType) -> Self {
+        ffi::Type::pointer()
+    }
+}
@@ -4,7 +4,7 @@ use libffi::middle as ffi;
 use neon::prelude::*;
 
 /// Size of an integer type in bits.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum IntegerSize {
     /// 8-bit integer.
     _8,
@@ -36,7 +36,7 @@ impl IntegerSize {
 }
 
 /// Signedness of an integer type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum IntegerSign {
     /// Unsigned integer.
     Unsigned,
@@ -63,22 +63,73 @@ impl IntegerSign {
 }
 
 /// Type descriptor for integer types.
+///
+/// Integers are exchanged as JavaScript numbers, which are only exact up to
+/// 2^53. With `is_bigint` set, they are returned as `BigInt`s instead, so
+/// 64-bit values keep their full precision. `BigInt`s are accepted as input
+/// either way.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct IntegerType {
     /// The size of the integer.
     pub size: IntegerSize,
     /// The signedness of the integer.
     pub sign: IntegerSign,
+    /// Whether values are returned to JavaScript as `BigInt`s.
+    pub is_bigint: bool,
 }
 
 impl IntegerType {
     /// Creates a new integer type with the given size and sign.
     pub fn new(size: IntegerSize, sign: IntegerSign) -> Self {
-        IntegerType { size, sign }
+        IntegerType {
+            size,
+            sign,
+            is_bigint: false,
+        }
+    }
+
+    /// Creates a new integer type whose values are returned as `BigInt`s.
+    pub fn bigint(size: IntegerSize, sign: IntegerSign) -> Self {
+        IntegerType {
+            is_bigint: true,
+            ..Self::new(size, sign)
+        }
+    }
+
+    /// Returns the smallest and largest values of the integer type.
+    pub fn range(&self) -> (i128, i128) {
+        let bits = match self.size {
+            IntegerSize::_8 => 8,
+            IntegerSize::_16 => 16,
+            IntegerSize::_32 => 32,
+            IntegerSize::_64 => 64,
+        };
+
+        match self.sign {
+            IntegerSign::Unsigned => (0, (1 << bits) - 1),
+            IntegerSign::Signed => (-(1 << (bits - 1)), (1 << (bits - 1)) - 1),
+        }
+    }
+
+    /// Checks that a `BigInt` value fits the integer type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the value and the type's range if it does not fit.
+    pub fn check_range(&self, n: i128) -> anyhow::Result<()> {
+        let (min, max) = self.range();
+
+        if n < min || n > max {
+            anyhow::bail!("BigInt {n} is out of range for an integer between {min} and {max}");
+        }
+
+        Ok(())
     }
 
     /// Parses an integer type from a JavaScript object.
     ///
+    /// The optional `bigint` property selects `BigInt` results.
+    ///
     /// # Errors
     ///
     /// Returns a `NeonResult` error if the object is malformed.
@@ -89,7 +140,12 @@ impl IntegerType {
         let size = IntegerSize::from_js_value(cx, size_prop)?;
         let sign = IntegerSign::from_js_value(cx, sign_prop)?;
 
-        Ok(Self::new(size, sign))
+        let is_bigint: Option<Handle<JsBoolean>> = obj.get_opt(cx, "bigint")?;
+
+        match is_bigint.map(|b| b.value(cx)) {
+            Some(true) => Ok(Self::bigint(size, sign)),
+            _ => Ok(Self::new(size, sign)),
+        }
     }
 }
 
@@ -107,3 +163,30 @@ impl From<&IntegerType> for ffi::Type {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_range_accepts_boundaries() {
+        let uint64 = IntegerType::new(IntegerSize::_64, IntegerSign::Unsigned);
+        let int8 = IntegerType::new(IntegerSize::_8, IntegerSign::Signed);
+
+        assert!(uint64.check_range(0).is_ok());
+        assert!(uint64.check_range(u64::MAX as i128).is_ok());
+        assert!(int8.check_range(-128).is_ok());
+        assert!(int8.check_range(127).is_ok());
+    }
+
+    #[test]
+    fn check_range_rejects_values_that_would_truncate() {
+        let uint64 = IntegerType::new(IntegerSize::_64, IntegerSign::Unsigned);
+        let int32 = IntegerType::new(IntegerSize::_32, IntegerSign::Signed);
+
+        assert!(uint64.check_range(1 << 64).is_err());
+        assert!(uint64.check_range(-1).is_err());
+        assert!(int32.check_range(i32::MAX as i128 + 1).is_err());
+        assert!(int32.check_range(i32::MIN as i128 - 1).is_err());
+    }
+}
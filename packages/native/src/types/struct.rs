@@ -0,0 +1,213 @@
+//! Struct (by-value) type descriptor.
+
+use std::ffi::c_void;
+
+use libffi::middle as ffi;
+use neon::prelude::*;
+
+use crate::types::{FloatSize, IntegerSize, Type};
+
+/// A named field of a struct type.
+#[derive(Debug, Clone)]
+pub struct StructField {
+    /// The field name, used as the property key on the JavaScript object.
+    pub name: String,
+    /// The type of the field.
+    pub type_: Type,
+}
+
+/// Type descriptor for C structs passed or returned by value.
+///
+/// Fields are laid out following the C ABI rules: each field is aligned to
+/// its natural alignment and the total size is padded to the alignment of
+/// the widest field.
+#[derive(Debug, Clone)]
+pub struct StructType {
+    /// The fields of the struct, in declaration order.
+    pub fields: Vec<StructField>,
+}
+
+impl StructType {
+    /// Creates a new struct type with the given fields.
+    pub fn new(fields: Vec<StructField>) -> Self {
+        StructType { fields }
+    }
+
+    /// Parses a struct type from a JavaScript object.
+    ///
+    /// The object must have a `fields` array of `{ name, type }` objects.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NeonResult` error if the object is malformed or a field
+    /// type cannot be stored inline in a struct.
+    pub fn from_js_value(cx: &mut FunctionContext, value: Handle<JsValue>) -> NeonResult<Self> {
+        let obj = value.downcast::<JsObject, _>(cx).or_throw(cx)?;
+        let fields_prop: Handle<'_, JsArray> = obj.prop(cx, "fields").get()?;
+        let fields_vec = fields_prop.to_vec(cx)?;
+        let mut fields = Vec::with_capacity(fields_vec.len());
+
+        for item in fields_vec {
+            let field_obj = item.downcast::<JsObject, _>(cx).or_throw(cx)?;
+            let name: Handle<'_, JsString> = field_obj.prop(cx, "name").get()?;
+            let name = name.value(cx);
+            let type_prop: Handle<'_, JsValue> = field_obj.prop(cx, "type").get()?;
+            let type_ = Type::from_js_value(cx, type_prop)?;
+
+            if type_layout(&type_).is_none() {
+                return cx.throw_type_error(format!(
+                    "Unsupported type for struct field '{}': {:?}",
+                    name, type_
+                ));
+            }
+
+            fields.push(StructField { name, type_ });
+        }
+
+        Ok(Self::new(fields))
+    }
+
+    /// Returns the byte offset of each field.
+    pub fn field_offsets(&self) -> Vec<usize> {
+        let mut offsets = Vec::with_capacity(self.fields.len());
+        let mut offset: usize = 0;
+
+        for field in &self.fields {
+            let (size, align) = type_layout(&field.type_).unwrap_or((0, 1));
+            offset = offset.next_multiple_of(align);
+            offsets.push(offset);
+            offset += size;
+        }
+
+        offsets
+    }
+
+    /// Returns the alignment of the struct in bytes.
+    pub fn alignment(&self) -> usize {
+        self.fields
+            .iter()
+            .filter_map(|field| type_layout(&field.type_))
+            .map(|(_, align)| align)
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Returns the size of the struct in bytes, including trailing padding.
+    pub fn size(&self) -> usize {
+        let end = self
+            .fields
+            .iter()
+            .zip(self.field_offsets())
+            .map(|(field, offset)| offset + type_layout(&field.type_).map_or(0, |(size, _)| size))
+            .max()
+            .unwrap_or(0);
+
+        end.next_multiple_of(self.alignment())
+    }
+}
+
+/// Returns the size and alignment of a type stored inline in a struct or a
+/// sized C array, or `None` if the type cannot be stored inline.
+pub fn type_layout(type_: &Type) -> Option<(usize, usize)> {
+    let pointer = std::mem::size_of::<*mut c_void>();
+
+    match type_ {
+        Type::Integer(int_type) => {
+            let size = match int_type.size {
+                IntegerSize::_8 => 1,
+                IntegerSize::_16 => 2,
+                IntegerSize::_32 => 4,
+                IntegerSize::_64 => 8,
+            };
+            Some((size, size))
+        }
+        Type::Float(float_type) => match float_type.size {
+            FloatSize::_32 => Some((4, 4)),
+            FloatSize::_64 => Some((8, 8)),
+        },
+        Type::Boolean => Some((1, 1)),
+        Type::Enum(_) | Type::Flags(_) => Some((4, 4)),
+        Type::GObject(_) | Type::Boxed(_) | Type::String(_) => Some((pointer, pointer)),
+        Type::Struct(struct_type) => Some((struct_type.size(), struct_type.alignment())),
+        _ => None,
+    }
+}
+
+impl From<&StructType> for ffi::Type {
+    fn from(value: &StructType) -> Self {
+        ffi::Type::structure(
+            value
+                .fields
+                .iter()
+                .map(|field| ffi::Type::from(&field.type_)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FloatType, IntegerSign, IntegerType};
+
+    fn field(name: &str, type_: Type) -> StructField {
+        StructField {
+            name: name.to_string(),
+            type_,
+        }
+    }
+
+    fn float32() -> Type {
+        Type::Float(FloatType::new(FloatSize::_32))
+    }
+
+    #[test]
+    fn layout_of_homogeneous_floats() {
+        let rgba = StructType::new(vec![
+            field("red", float32()),
+            field("green", float32()),
+            field("blue", float32()),
+            field("alpha", float32()),
+        ]);
+
+        assert_eq!(rgba.field_offsets(), vec![0, 4, 8, 12]);
+        assert_eq!(rgba.size(), 16);
+        assert_eq!(rgba.alignment(), 4);
+    }
+
+    #[test]
+    fn layout_inserts_padding_between_fields() {
+        let mixed = StructType::new(vec![
+            field("flag", Type::Boolean),
+            field(
+                "value",
+                Type::Integer(IntegerType::new(IntegerSize::_64, IntegerSign::Signed)),
+            ),
+            field("small", Type::Boolean),
+        ]);
+
+        assert_eq!(mixed.field_offsets(), vec![0, 8, 16]);
+        assert_eq!(mixed.size(), 24);
+        assert_eq!(mixed.alignment(), 8);
+    }
+
+    #[test]
+    fn layout_of_nested_structs() {
+        let point = StructType::new(vec![field("x", float32()), field("y", float32())]);
+        let rect = StructType::new(vec![
+            field("origin", Type::Struct(point.clone())),
+            field("size", Type::Struct(point)),
+        ]);
+
+        assert_eq!(rect.field_offsets(), vec![0, 8]);
+        assert_eq!(rect.size(), 16);
+        assert_eq!(rect.alignment(), 4);
+    }
+
+    #[test]
+    fn empty_struct_has_zero_size() {
+        let empty = StructType::new(vec![]);
+
+        assert_eq!(empty.size(), 0);
+        assert_eq!(empty.alignment(), 1);
+    }
+}
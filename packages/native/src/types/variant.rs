@@ -0,0 +1,61 @@
+//! GVariant type descriptor.
+
+use gtk4::glib;
+use libffi::middle as ffi;
+use neon::prelude::*;
+
+/// Type descriptor for `GVariant*` values.
+///
+/// The optional signature is a GVariant type string (for example `"a{sv}"`)
+/// used to convert JavaScript values. Without a signature, the variant type
+/// is inferred from the JavaScript value.
+#[derive(Debug, Clone)]
+pub struct VariantType {
+    /// The GVariant type used to build variants from JavaScript values.
+    pub signature: Option<glib::VariantType>,
+    /// Whether a returned variant is borrowed from the callee.
+    pub is_borrowed: bool,
+}
+
+impl VariantType {
+    /// Creates a new variant type with the given signature.
+    pub fn new(signature: Option<glib::VariantType>, is_borrowed: bool) -> Self {
+        VariantType {
+            signature,
+            is_borrowed,
+        }
+    }
+
+    /// Parses a variant type from a JavaScript object.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NeonResult` error if the object is malformed or the
+    /// signature is not a valid, definite GVariant type string.
+    pub fn from_js_value(cx: &mut FunctionContext, value: Handle<JsValue>) -> NeonResult<Self> {
+        let obj = value.downcast::<JsObject, _>(cx).or_throw(cx)?;
+
+        let signature: Option<Handle<JsString>> = obj.get_opt(cx, "signature")?;
+        let signature = match signature.map(|s| s.value(cx)) {
+            Some(signature) => match glib::VariantType::new(&signature) {
+                Ok(variant_type) if variant_type.is_definite() => Some(variant_type),
+                _ => {
+                    return cx
+                        .throw_type_error(format!("Invalid GVariant type string: {}", signature));
+                }
+            },
+            None => None,
+        };
+
+        let is_borrowed: Option<Handle<JsBoolean>> = obj.get_opt(cx, "borrowed")?;
+        let is_borrowed = is_borrowed.map(|b| b.value(cx)).unwrap_or(false);
+
+        Ok(Self::new(signature, is_borrowed))
+    }
+}
+
+impl From<&VariantType> for ffi::Type {
+    fn from(_value: &VariantType) -> Self {
+        ffi::Type::pointer()
+    }
+}
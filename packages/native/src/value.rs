@@ -34,19 +34,163 @@ impl Drop for GListGuard {
         }
     }
 }
+
+/// Releases a reference to a `GHashTable` when dropped.
+pub struct HashTableGuard {
+    ptr: *mut glib::ffi::GHashTable,
+    should_unref: bool,
+}
+
+impl HashTableGuard {
+    /// Creates a guard that unrefs `ptr` on drop if `should_unref` is set.
+    pub fn new(ptr: *mut c_void, should_unref: bool) -> Self {
+        Self {
+            ptr: ptr as *mut glib::ffi::GHashTable,
+            should_unref,
+        }
+    }
+}
+
+impl Drop for HashTableGuard {
+    fn drop(&mut self) {
+        if self.should_unref && !self.ptr.is_null() {
+            unsafe {
+                glib::ffi::g_hash_table_unref(self.ptr);
+            }
+        }
+    }
+}
+
+/// Releases a reference to a `GPtrArray`, `GArray` or `GByteArray` when dropped.
+pub struct ArrayGuard {
+    ptr: *mut c_void,
+    list_type: ListType,
+    should_unref: bool,
+}
+
+impl ArrayGuard {
+    /// Creates a guard that unrefs `ptr`, an array of the given list type, on
+    /// drop if `should_unref` is set.
+    pub fn new(ptr: *mut c_void, list_type: ListType, should_unref: bool) -> Self {
+        Self {
+            ptr,
+            list_type,
+            should_unref,
+        }
+    }
+}
+
+impl Drop for ArrayGuard {
+    fn drop(&mut self) {
+        if !self.should_unref || self.ptr.is_null() {
+            return;
+        }
+
+        unsafe {
+            match self.list_type {
+                ListType::PtrArray => glib::ffi::g_ptr_array_unref(self.ptr as *mut _),
+                ListType::GArray => glib::ffi::g_array_unref(self.ptr as *mut _),
+                ListType::ByteArray => glib::ffi::g_byte_array_unref(self.ptr as *mut _),
+                ListType::Array | ListType::GList | ListType::GSList => {}
+            }
+        }
+    }
+}
 use gtk4::{
     glib,
-    glib::translate::{FromGlibPtrFull as _, FromGlibPtrNone as _, ToGlibPtr as _},
+    glib::prelude::StaticType as _,
+    glib::translate::{
+        FromGlibPtrFull as _, FromGlibPtrNone as _, ToGlibPtr as _, ToGlibPtrMut as _,
+    },
+};
+use neon::{
+    handle::Root,
+    object::Object as _,
+    prelude::*,
+    types::{JsBigInt, buffer::TypedArray as _},
 };
-use neon::{handle::Root, object::Object as _, prelude::*};
 
 use crate::{
     boxed::Boxed,
     cif,
     object::{Object, ObjectId},
-    types::{Callback, FloatSize, IntegerSign, IntegerSize, Type},
+    types::{
+        AbortSignal, ArrayLength, ArrayType, BytesKind, BytesType, Callback, FloatSize,
+        HashTableType, IntegerSign, IntegerSize, IntegerType, ListType, StructType, Type,
+        type_layout,
+    },
+    variant,
 };
 
+fn is_js_map<'a, C: Context<'a>>(cx: &mut C, obj: Handle<JsObject>) -> NeonResult<bool> {
+    let map_constructor: Handle<JsFunction> = cx.global("Map")?;
+    let constructor: Handle<JsValue> = obj.get(cx, "constructor")?;
+
+    Ok(constructor.strict_equals(cx, map_constructor))
+}
+
+/// Returns the key/value pairs of a JavaScript `Map` or plain object.
+///
+/// Plain object keys are returned as strings.
+fn js_entries<'a, C: Context<'a>>(
+    cx: &mut C,
+    obj: Handle<JsObject>,
+) -> NeonResult<Vec<(Handle<'a, JsValue>, Handle<'a, JsValue>)>> {
+    let mut entries = Vec::new();
+
+    if is_js_map(cx, obj)? {
+        let array: Handle<JsObject> = cx.global("Array")?;
+        let array_from: Handle<JsFunction> = array.get(cx, "from")?;
+        let js_entries = array_from
+            .call(cx, array, [obj.upcast::<JsValue>()])?
+            .downcast::<JsArray, _>(cx)
+            .or_throw(cx)?
+            .to_vec(cx)?;
+
+        for js_entry in js_entries {
+            let js_entry = js_entry.downcast::<JsArray, _>(cx).or_throw(cx)?;
+            let js_key: Handle<JsValue> = js_entry.get(cx, 0u32)?;
+            let js_value: Handle<JsValue> = js_entry.get(cx, 1u32)?;
+            entries.push((js_key, js_value));
+        }
+    } else {
+        let js_keys = obj.get_own_property_names(cx)?.to_vec(cx)?;
+
+        for js_key in js_keys {
+            let key = js_key.downcast::<JsString, _>(cx).or_throw(cx)?;
+            let js_value: Handle<JsValue> = obj.get(cx, key)?;
+            entries.push((key.upcast(), js_value));
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Copies the contents of a `Buffer`, `Uint8Array` or `ArrayBuffer` into a
+/// `GBytes`, or returns `None` for any other JavaScript value.
+fn js_bytes<'a, C: Context<'a>>(cx: &mut C, value: Handle<JsValue>) -> Option<glib::Bytes> {
+    if let Ok(array) = value.downcast::<JsTypedArray<u8>, _>(cx) {
+        return Some(glib::Bytes::from(array.as_slice(cx)));
+    }
+
+    if let Ok(buffer) = value.downcast::<JsArrayBuffer, _>(cx) {
+        return Some(glib::Bytes::from(buffer.as_slice(cx)));
+    }
+
+    None
+}
+
+/// Returns whether an item of `type_` stored in an owned container belongs to
+/// the caller, rather than only the container itself.
+fn owns_pointer_item(type_: &Type) -> bool {
+    match type_ {
+        Type::String(type_) => !type_.is_borrowed,
+        Type::GObject(type_) => !type_.is_borrowed,
+        Type::Boxed(type_) => !type_.is_borrowed,
+        _ => false,
+    }
+}
+
 /// A reference wrapper for out-parameters in FFI calls.
 ///
 /// Holds a boxed value and a reference to the JavaScript object that will be
@@ -87,21 +231,45 @@ impl Ref {
 
         Ok(Ref::new(value, Arc::new(js_obj_root)))
     }
+
+    /// Converts a JavaScript value to a Ref, parsing the inner value against
+    /// the expected inner type.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NeonResult` error if the value cannot be converted.
+    pub fn from_js_value_with_type<'a, C: Context<'a>>(
+        cx: &mut C,
+        value: Handle<JsValue>,
+        inner_type: &Type,
+    ) -> NeonResult<Self> {
+        let obj = value.downcast::<JsObject, _>(cx).or_throw(cx)?;
+        let js_obj_root = obj.root(cx);
+        let value_prop: Handle<JsValue> = obj.get(cx, "value")?;
+        let value = Value::from_js_value_with_type(cx, value_prop, inner_type)?;
+
+        Ok(Ref::new(value, Arc::new(js_obj_root)))
+    }
 }
 
 /// Represents a value that can be passed between JavaScript and native code.
 ///
 /// This enum covers all the value types that can cross the FFI boundary:
-/// - Primitive types (numbers, strings, booleans)
+/// - Primitive types (numbers, `BigInt`s, strings, booleans)
 /// - Null and undefined
 /// - Native objects (GObject instances, boxed types)
 /// - Arrays of values
+/// - Hash tables of key/value pairs
 /// - Callbacks (JavaScript functions callable from native code)
 /// - References (for out-parameters)
+/// - Structs passed by value
+/// - Binary data
 #[derive(Debug, Clone)]
 pub enum Value {
     /// A numeric value (all JavaScript numbers are f64).
     Number(f64),
+    /// An integer exchanged losslessly as a JavaScript `BigInt`.
+    BigInt(i128),
     /// A UTF-8 string value.
     String(String),
     /// A boolean value.
@@ -114,18 +282,28 @@ pub enum Value {
     Undefined,
     /// An array of values.
     Array(Vec<Value>),
+    /// Hash table entries as key/value pairs.
+    HashTable(Vec<(Value, Value)>),
     /// A JavaScript callback function.
     Callback(Callback),
     /// A reference wrapper for out-parameters.
     Ref(Ref),
+    /// A struct passed by value, as field name and value pairs in declaration order.
+    Struct(Vec<(String, Value)>),
+    /// Binary data, from a `Buffer`, `Uint8Array` or `ArrayBuffer`.
+    Bytes(glib::Bytes),
+    /// A JavaScript `AbortSignal` to cancel an operation with.
+    AbortSignal(AbortSignal),
+    /// A closure implemented natively, passed where an async ready callback is expected.
+    Closure(glib::Closure),
 }
 
 impl Value {
     /// Converts a JavaScript value to a [`Value`].
     ///
-    /// Handles all JavaScript types including numbers, strings, booleans,
-    /// null, undefined, arrays, boxed object IDs, functions (callbacks),
-    /// and reference objects.
+    /// Handles all JavaScript types including numbers, `BigInt`s, strings, booleans,
+    /// null, undefined, arrays, binary buffers, boxed object IDs, functions
+    /// (callbacks), and reference objects.
     ///
     /// # Errors
     ///
@@ -138,6 +316,13 @@ impl Value {
             return Ok(Value::Number(number.value(cx)));
         }
 
+        if let Ok(bigint) = value.downcast::<JsBigInt, _>(cx) {
+            return match bigint.to_i128(cx) {
+                Ok(n) => Ok(Value::BigInt(n)),
+                Err(_) => cx.throw_range_error("BigInt is too large for a 64-bit integer"),
+            };
+        }
+
         if let Ok(string) = value.downcast::<JsString, _>(cx) {
             return Ok(Value::String(string.value(cx)));
         }
@@ -165,6 +350,10 @@ impl Value {
             )?));
         }
 
+        if let Some(bytes) = js_bytes(cx, value) {
+            return Ok(Value::Bytes(bytes));
+        }
+
         if let Ok(array) = value.downcast::<JsArray, _>(cx) {
             let values = array.to_vec(cx)?;
             let vec_values = values
@@ -182,6 +371,152 @@ impl Value {
         cx.throw_type_error(format!("Unsupported JS value type: {:?}", *value))
     }
 
+    /// Converts a JavaScript value to a [`Value`] using the expected type.
+    ///
+    /// Plain objects are ambiguous without a type: they are parsed as refs by
+    /// [`Value::from_js_value`], but as field maps for struct types and as
+    /// entries for hash tables and variants. This resolves the ambiguity for
+    /// those types and refs to them, and falls back to
+    /// [`Value::from_js_value`] otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NeonResult` error if the JavaScript value type is not supported.
+    pub fn from_js_value_with_type<'a, C: Context<'a>>(
+        cx: &mut C,
+        value: Handle<JsValue>,
+        type_: &Type,
+    ) -> NeonResult<Self> {
+        match type_ {
+            Type::Struct(struct_type) => Self::from_js_struct(cx, value, struct_type),
+            Type::HashTable(hash_table_type) => {
+                Self::from_js_hash_table(cx, value, hash_table_type)
+            }
+            Type::Variant(_) => Self::from_js_variant(cx, value),
+            Type::Integer(int_type) if value.is_a::<JsBigInt, _>(cx) => {
+                let value = Self::from_js_value(cx, value)?;
+
+                if let Value::BigInt(n) = value
+                    && let Err(err) = int_type.check_range(n)
+                {
+                    return cx.throw_range_error(err.to_string());
+                }
+
+                Ok(value)
+            }
+            Type::Cancellable if value.is_a::<JsObject, _>(cx) => {
+                Ok(Value::AbortSignal(AbortSignal::from_js_value(cx, value)?))
+            }
+            Type::Ref(ref_type)
+                if value.is_a::<JsObject, _>(cx) && !value.is_a::<JsArray, _>(cx) =>
+            {
+                Ok(Value::Ref(Ref::from_js_value_with_type(
+                    cx,
+                    value,
+                    &ref_type.inner_type,
+                )?))
+            }
+            _ => Self::from_js_value(cx, value),
+        }
+    }
+
+    fn from_js_struct<'a, C: Context<'a>>(
+        cx: &mut C,
+        value: Handle<JsValue>,
+        struct_type: &StructType,
+    ) -> NeonResult<Self> {
+        if value.is_a::<JsNull, _>(cx) {
+            return Ok(Value::Null);
+        }
+
+        if value.is_a::<JsUndefined, _>(cx) {
+            return Ok(Value::Undefined);
+        }
+
+        let obj = value.downcast::<JsObject, _>(cx).or_throw(cx)?;
+        let mut fields = Vec::with_capacity(struct_type.fields.len());
+
+        for field in &struct_type.fields {
+            let field_value: Handle<JsValue> = obj.get(cx, field.name.as_str())?;
+            let field_value = Self::from_js_value_with_type(cx, field_value, &field.type_)?;
+            fields.push((field.name.clone(), field_value));
+        }
+
+        Ok(Value::Struct(fields))
+    }
+
+    /// Parses hash table entries from a JavaScript `Map` or plain object.
+    ///
+    /// Plain objects can only describe string-keyed tables.
+    fn from_js_hash_table<'a, C: Context<'a>>(
+        cx: &mut C,
+        value: Handle<JsValue>,
+        hash_table_type: &HashTableType,
+    ) -> NeonResult<Self> {
+        if value.is_a::<JsNull, _>(cx) {
+            return Ok(Value::Null);
+        }
+
+        if value.is_a::<JsUndefined, _>(cx) {
+            return Ok(Value::Undefined);
+        }
+
+        let obj = value.downcast::<JsObject, _>(cx).or_throw(cx)?;
+
+        if !is_js_map(cx, obj)? && !matches!(*hash_table_type.key_type, Type::String(_)) {
+            return cx.throw_type_error("Hash tables with non-string keys must be passed as a Map");
+        }
+
+        let mut entries = Vec::new();
+
+        for (js_key, js_value) in js_entries(cx, obj)? {
+            let key = Self::from_js_value_with_type(cx, js_key, &hash_table_type.key_type)?;
+            let value = Self::from_js_value_with_type(cx, js_value, &hash_table_type.value_type)?;
+            entries.push((key, value));
+        }
+
+        Ok(Value::HashTable(entries))
+    }
+
+    /// Parses a JavaScript value to be converted to a `GVariant`.
+    ///
+    /// Arrays are parsed recursively and `Map`s or plain objects become hash
+    /// table entries, so the result can be matched against any variant type.
+    /// Binary buffers become bytes, which convert to byte arrays.
+    fn from_js_variant<'a, C: Context<'a>>(cx: &mut C, value: Handle<JsValue>) -> NeonResult<Self> {
+        if let Some(bytes) = js_bytes(cx, value) {
+            return Ok(Value::Bytes(bytes));
+        }
+
+        if let Ok(array) = value.downcast::<JsArray, _>(cx) {
+            let items = array
+                .to_vec(cx)?
+                .into_iter()
+                .map(|item| Self::from_js_variant(cx, item))
+                .collect::<NeonResult<Vec<_>>>()?;
+
+            return Ok(Value::Array(items));
+        }
+
+        if value.is_a::<JsFunction, _>(cx) || value.is_a::<JsBox<ObjectId>, _>(cx) {
+            return cx.throw_type_error("Objects and functions cannot be converted to a GVariant");
+        }
+
+        if let Ok(obj) = value.downcast::<JsObject, _>(cx) {
+            let mut entries = Vec::new();
+
+            for (js_key, js_value) in js_entries(cx, obj)? {
+                let key = Self::from_js_variant(cx, js_key)?;
+                let value = Self::from_js_variant(cx, js_value)?;
+                entries.push((key, value));
+            }
+
+            return Ok(Value::HashTable(entries));
+        }
+
+        Self::from_js_value(cx, value)
+    }
+
     /// Converts this value to a JavaScript value.
     ///
     /// # Errors
@@ -191,6 +526,7 @@ impl Value {
     pub fn to_js_value<'a, C: Context<'a>>(&self, cx: &mut C) -> NeonResult<Handle<'a, JsValue>> {
         match self {
             Value::Number(n) => Ok(cx.number(*n).upcast()),
+            Value::BigInt(n) => Ok(JsBigInt::from_i128(cx, *n).upcast()),
             Value::String(s) => Ok(cx.string(s).upcast()),
             Value::Boolean(b) => Ok(cx.boolean(*b).upcast()),
             Value::Object(id) => Ok(cx.boxed(*id).upcast()),
@@ -204,6 +540,32 @@ impl Value {
 
                 Ok(js_array.upcast())
             }
+            Value::Struct(fields) => {
+                let js_object = cx.empty_object();
+
+                for (name, field_value) in fields {
+                    let js_field = field_value.to_js_value(cx)?;
+                    js_object.set(cx, name.as_str(), js_field)?;
+                }
+
+                Ok(js_object.upcast())
+            }
+            Value::HashTable(entries) => {
+                let map_constructor: Handle<JsFunction> = cx.global("Map")?;
+                let js_map = map_constructor.construct(cx, Vec::<Handle<JsValue>>::new())?;
+                let map_set: Handle<JsFunction> = js_map.get(cx, "set")?;
+
+                for (key, value) in entries {
+                    let js_key = key.to_js_value(cx)?;
+                    let js_value = value.to_js_value(cx)?;
+                    map_set.call(cx, js_map, [js_key, js_value])?;
+                }
+
+                Ok(js_map.upcast())
+            }
+            // GBytes data is immutable and may be shared or read-only, so it
+            // is copied into the writable buffer.
+            Value::Bytes(bytes) => Ok(JsBuffer::from_slice(cx, bytes)?.upcast()),
             Value::Null => Ok(cx.null().upcast()),
             Value::Undefined => Ok(cx.undefined().upcast()),
             _ => cx.throw_type_error(format!(
@@ -213,6 +575,32 @@ impl Value {
         }
     }
 
+    /// Collects the IDs of the objects this value refers to, including those
+    /// nested in arrays, hash tables, structs and refs.
+    pub fn collect_object_ids(&self, object_ids: &mut Vec<ObjectId>) {
+        match self {
+            Value::Object(object_id) => object_ids.push(*object_id),
+            Value::Array(values) => {
+                for value in values {
+                    value.collect_object_ids(object_ids);
+                }
+            }
+            Value::HashTable(entries) => {
+                for (key, value) in entries {
+                    key.collect_object_ids(object_ids);
+                    value.collect_object_ids(object_ids);
+                }
+            }
+            Value::Struct(fields) => {
+                for (_, value) in fields {
+                    value.collect_object_ids(object_ids);
+                }
+            }
+            Value::Ref(r#ref) => r#ref.value.collect_object_ids(object_ids),
+            _ => {}
+        }
+    }
+
     /// Converts a libffi CIF value to a [`Value`] based on the expected type.
     ///
     /// This is used to convert return values and out-parameters from FFI calls
@@ -231,6 +619,23 @@ impl Value {
         match type_ {
             Type::Null => Ok(Value::Null),
             Type::Undefined => Ok(Value::Undefined),
+            Type::Integer(int_type) if int_type.is_bigint => {
+                let value = match cif_value {
+                    cif::Value::I8(v) => *v as i128,
+                    cif::Value::U8(v) => *v as i128,
+                    cif::Value::I16(v) => *v as i128,
+                    cif::Value::U16(v) => *v as i128,
+                    cif::Value::I32(v) => *v as i128,
+                    cif::Value::U32(v) => *v as i128,
+                    cif::Value::I64(v) => *v as i128,
+                    cif::Value::U64(v) => *v as i128,
+                    _ => {
+                        bail!("Expected an integer cif::Value, got {:?}", cif_value)
+                    }
+                };
+
+                Ok(Value::BigInt(value))
+            }
             Type::Integer(_) | Type::Float(_) => {
                 let number = match cif_value {
                     cif::Value::I8(v) => *v as f64,
@@ -346,7 +751,25 @@ impl Value {
                 Ok(Value::Object(ObjectId::new(boxed)))
             }
             Type::Array(array_type) => {
-                use crate::types::ListType;
+                if matches!(
+                    array_type.list_type,
+                    ListType::PtrArray | ListType::GArray | ListType::ByteArray
+                ) {
+                    let (array_ptr, is_owned) = match cif_value {
+                        cif::Value::Ptr(ptr) => (*ptr, !array_type.is_borrowed),
+                        // Arrays built for the call are released by their own guard.
+                        cif::Value::OwnedPtr(ptr) => (ptr.ptr, false),
+                        _ => {
+                            bail!(
+                                "Expected a pointer cif::Value for {:?}, got {:?}",
+                                array_type.list_type,
+                                cif_value
+                            )
+                        }
+                    };
+
+                    return Value::from_garray_ptr(array_ptr, array_type, is_owned);
+                }
 
                 if array_type.list_type == ListType::GList
                     || array_type.list_type == ListType::GSList
@@ -415,6 +838,16 @@ impl Value {
                     return Ok(Value::Array(values));
                 }
 
+                if let (Some(length), cif::Value::Ptr(ptr)) = (array_type.length, cif_value) {
+                    // Lengths read from other arguments are resolved to a fixed
+                    // length by the call before the array is converted.
+                    let ArrayLength::Fixed(length) = length else {
+                        bail!("Unresolved sized array length: {:?}", length);
+                    };
+
+                    return Value::from_sized_array_ptr(*ptr, array_type, length);
+                }
+
                 if let cif::Value::Ptr(ptr) = cif_value {
                     if ptr.is_null() {
                         return Ok(Value::Array(vec![]));
@@ -663,33 +1096,7 @@ impl Value {
                         Ok(Value::Object(ObjectId::new(Object::Boxed(boxed))))
                     }
                     Type::Integer(int_type) => {
-                        let number = match (int_type.size, int_type.sign) {
-                            (IntegerSize::_8, IntegerSign::Unsigned) => unsafe {
-                                *(ref_ptr.ptr as *const u8) as f64
-                            },
-                            (IntegerSize::_8, IntegerSign::Signed) => unsafe {
-                                *(ref_ptr.ptr as *const i8) as f64
-                            },
-                            (IntegerSize::_16, IntegerSign::Unsigned) => unsafe {
-                                *(ref_ptr.ptr as *const u16) as f64
-                            },
-                            (IntegerSize::_16, IntegerSign::Signed) => unsafe {
-                                *(ref_ptr.ptr as *const i16) as f64
-                            },
-                            (IntegerSize::_32, IntegerSign::Unsigned) => unsafe {
-                                *(ref_ptr.ptr as *const u32) as f64
-                            },
-                            (IntegerSize::_32, IntegerSign::Signed) => unsafe {
-                                *(ref_ptr.ptr as *const i32) as f64
-                            },
-                            (IntegerSize::_64, IntegerSign::Unsigned) => unsafe {
-                                *(ref_ptr.ptr as *const u64) as f64
-                            },
-                            (IntegerSize::_64, IntegerSign::Signed) => unsafe {
-                                *(ref_ptr.ptr as *const i64) as f64
-                            },
-                        };
-                        Ok(Value::Number(number))
+                        Ok(Value::from_integer_ptr(ref_ptr.ptr as *const u8, int_type))
                     }
                     Type::Float(float_type) => {
                         let number = match float_type.size {
@@ -698,6 +1105,63 @@ impl Value {
                         };
                         Ok(Value::Number(number))
                     }
+                    Type::Struct(struct_type) => {
+                        Value::from_struct_ptr(ref_ptr.ptr as *const u8, struct_type)
+                    }
+                    Type::HashTable(hash_table_type) => {
+                        let table_ptr = unsafe { *(ref_ptr.ptr as *const *mut c_void) };
+                        Value::from_hash_table_ptr(
+                            table_ptr,
+                            hash_table_type,
+                            !hash_table_type.is_borrowed,
+                        )
+                    }
+                    Type::Variant(variant_type) => {
+                        let variant_ptr = unsafe { *(ref_ptr.ptr as *const *mut c_void) };
+                        Value::from_variant_ptr(variant_ptr, variant_type.is_borrowed)
+                    }
+                    Type::Enum(enum_type) => {
+                        Ok(enum_type.to_value(unsafe { *(ref_ptr.ptr as *const i32) }))
+                    }
+                    Type::Array(array_type) => {
+                        // Callee-allocated arrays are written into pointer storage;
+                        // caller-allocated arrays are read back from their own buffer.
+                        if ref_ptr.value.is::<cif::Value>() {
+                            let array_ptr = unsafe { *(ref_ptr.ptr as *const *mut c_void) };
+                            return Value::from_cif_value(
+                                &cif::Value::Ptr(array_ptr),
+                                &type_.inner_type,
+                            );
+                        }
+
+                        match Value::from_cif_value(cif_value, &type_.inner_type)? {
+                            Value::Array(mut items) => {
+                                if let Some(ArrayLength::Fixed(length)) = array_type.length {
+                                    items.truncate(length);
+                                }
+                                Ok(Value::Array(items))
+                            }
+                            value => Ok(value),
+                        }
+                    }
+                    Type::Flags(flags_type) => {
+                        Ok(flags_type.to_value(unsafe { *(ref_ptr.ptr as *const u32) }))
+                    }
+                    Type::Bytes(bytes_type) => {
+                        // Caller-allocated buffers are read back up to their
+                        // length; otherwise the callee wrote a pointer.
+                        if let Some(buffer) = ref_ptr.value.downcast_ref::<Vec<u8>>() {
+                            let length = match bytes_type.length {
+                                Some(ArrayLength::Fixed(length)) => length.min(buffer.len()),
+                                _ => buffer.len(),
+                            };
+
+                            return Ok(Value::Bytes(glib::Bytes::from(&buffer[..length])));
+                        }
+
+                        let bytes_ptr = unsafe { *(ref_ptr.ptr as *const *mut c_void) };
+                        Value::from_bytes_ptr(bytes_ptr, bytes_type)
+                    }
                     _ => {
                         bail!(
                             "Unsupported ref inner type for reading: {:?}",
@@ -706,9 +1170,394 @@ impl Value {
                     }
                 }
             }
+            Type::Struct(struct_type) => {
+                let struct_ptr = match cif_value {
+                    cif::Value::Struct(ptr) => ptr,
+                    _ => {
+                        bail!(
+                            "Expected a struct cif::Value for Struct, got {:?}",
+                            cif_value
+                        )
+                    }
+                };
+
+                Value::from_struct_ptr(struct_ptr.ptr as *const u8, struct_type)
+            }
+            Type::HashTable(hash_table_type) => {
+                let table_ptr = match cif_value {
+                    cif::Value::Ptr(ptr) => *ptr,
+                    _ => {
+                        bail!(
+                            "Expected a pointer cif::Value for GHashTable, got {:?}",
+                            cif_value
+                        )
+                    }
+                };
+
+                Value::from_hash_table_ptr(table_ptr, hash_table_type, !hash_table_type.is_borrowed)
+            }
+            Type::Variant(variant_type) => {
+                let variant_ptr = match cif_value {
+                    cif::Value::Ptr(ptr) => *ptr,
+                    _ => {
+                        bail!(
+                            "Expected a pointer cif::Value for GVariant, got {:?}",
+                            cif_value
+                        )
+                    }
+                };
+
+                Value::from_variant_ptr(variant_ptr, variant_type.is_borrowed)
+            }
+            Type::Bytes(bytes_type) => {
+                let bytes_ptr = match cif_value {
+                    cif::Value::Ptr(ptr) => *ptr,
+                    _ => {
+                        bail!(
+                            "Expected a pointer cif::Value for bytes, got {:?}",
+                            cif_value
+                        )
+                    }
+                };
+
+                Value::from_bytes_ptr(bytes_ptr, bytes_type)
+            }
             _ => bail!("Unsupported type for cif value conversion: {:?}", type_),
         }
     }
+
+    /// Converts a `GBytes*` or `guint8*` returned by a call to bytes.
+    ///
+    /// Owned data is adopted without copying; borrowed data is copied.
+    /// A `guint8*` needs a resolved length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length of a `guint8*` is unknown.
+    fn from_bytes_ptr(ptr: *mut c_void, bytes_type: &BytesType) -> anyhow::Result<Self> {
+        let length = match (bytes_type.kind, bytes_type.length) {
+            (BytesKind::GBytes, _) => 0,
+            (BytesKind::Pointer, Some(ArrayLength::Fixed(length))) => length,
+            (BytesKind::Pointer, length) => {
+                bail!(
+                    "Cannot read a bytes pointer with unresolved length {:?}",
+                    length
+                )
+            }
+        };
+
+        if ptr.is_null() {
+            return Ok(Value::Null);
+        }
+
+        let bytes = match (bytes_type.kind, bytes_type.is_borrowed) {
+            (BytesKind::GBytes, true) => unsafe {
+                glib::Bytes::from_glib_none(ptr as *mut glib::ffi::GBytes)
+            },
+            (BytesKind::GBytes, false) => unsafe {
+                glib::Bytes::from_glib_full(ptr as *mut glib::ffi::GBytes)
+            },
+            (BytesKind::Pointer, true) => {
+                let data = unsafe { std::slice::from_raw_parts(ptr as *const u8, length) };
+                glib::Bytes::from(data)
+            }
+            (BytesKind::Pointer, false) => unsafe {
+                glib::Bytes::from_glib_full(glib::ffi::g_bytes_new_take(ptr, length))
+            },
+        };
+
+        Ok(Value::Bytes(bytes))
+    }
+
+    /// Reads a struct laid out according to `struct_type` from memory.
+    ///
+    /// Pointer fields (strings, objects, boxed types) follow the ownership
+    /// rules of their field type, just like return values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a field type is not supported inline in a struct.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to at least `struct_type.size()` readable bytes.
+    pub fn from_struct_ptr(ptr: *const u8, struct_type: &StructType) -> anyhow::Result<Self> {
+        let mut fields = Vec::with_capacity(struct_type.fields.len());
+
+        for (field, offset) in struct_type.fields.iter().zip(struct_type.field_offsets()) {
+            let field_ptr = unsafe { ptr.add(offset) };
+            let field_value = Value::from_inline_ptr(field_ptr, &field.type_)?;
+
+            fields.push((field.name.clone(), field_value));
+        }
+
+        Ok(Value::Struct(fields))
+    }
+
+    /// Reads a sized C array of `length` inline items from memory.
+    ///
+    /// Each item is converted according to the item type, including its
+    /// ownership. The array itself is freed unless it is borrowed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the item type cannot be stored inline.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to at least `length` items of the array's item type.
+    pub fn from_sized_array_ptr(
+        ptr: *mut c_void,
+        array_type: &ArrayType,
+        length: usize,
+    ) -> anyhow::Result<Self> {
+        if ptr.is_null() {
+            return Ok(Value::Array(vec![]));
+        }
+
+        let (stride, _) = type_layout(&array_type.item_type).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unsupported sized array item type: {:?}",
+                array_type.item_type
+            )
+        })?;
+
+        let values = (0..length)
+            .map(|i| {
+                let item_ptr = unsafe { (ptr as *const u8).add(i * stride) };
+                Value::from_inline_ptr(item_ptr, &array_type.item_type)
+            })
+            .collect::<anyhow::Result<Vec<Value>>>();
+
+        if !array_type.is_borrowed {
+            unsafe { glib::ffi::g_free(ptr) };
+        }
+
+        Ok(Value::Array(values?))
+    }
+
+    /// Reads the items of a `GPtrArray`, `GArray` or `GByteArray`.
+    ///
+    /// When `is_owned` is set, the array is released afterwards. Owned
+    /// pointer items are taken over according to the item type's ownership,
+    /// and the array's own free function is cleared so they are not released
+    /// twice; borrowed items are copied or referenced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the item type is not supported by the list type.
+    pub fn from_garray_ptr(
+        ptr: *mut c_void,
+        array_type: &ArrayType,
+        is_owned: bool,
+    ) -> anyhow::Result<Self> {
+        if ptr.is_null() {
+            return Ok(Value::Array(vec![]));
+        }
+
+        let _guard = ArrayGuard::new(ptr, array_type.list_type.clone(), is_owned);
+        let item_type = &*array_type.item_type;
+
+        let owns_items = is_owned && owns_pointer_item(item_type);
+
+        let values = match array_type.list_type {
+            ListType::PtrArray => {
+                let array = ptr as *mut glib::ffi::GPtrArray;
+                let (pdata, len) = unsafe { ((*array).pdata, (*array).len as usize) };
+
+                if owns_items {
+                    unsafe { glib::ffi::g_ptr_array_set_free_func(array, None) };
+                }
+
+                (0..len)
+                    .map(|i| {
+                        let item = unsafe { *pdata.add(i) };
+
+                        if owns_items {
+                            Value::from_cif_value(&cif::Value::Ptr(item), item_type)
+                        } else {
+                            Value::from_pointer_item(item, item_type)
+                        }
+                    })
+                    .collect::<anyhow::Result<Vec<Value>>>()?
+            }
+            ListType::GArray => {
+                let array = ptr as *mut glib::ffi::GArray;
+                let (data, len) = unsafe { ((*array).data as *const u8, (*array).len as usize) };
+                let size = unsafe { glib::ffi::g_array_get_element_size(array) } as usize;
+
+                if owns_items {
+                    unsafe { glib::ffi::g_array_set_clear_func(array, None) };
+                }
+
+                (0..len)
+                    .map(|i| Value::from_inline_ptr(unsafe { data.add(i * size) }, item_type))
+                    .collect::<anyhow::Result<Vec<Value>>>()?
+            }
+            ListType::ByteArray => {
+                let array = ptr as *mut glib::ffi::GByteArray;
+                let (data, len) = unsafe { ((*array).data, (*array).len as usize) };
+
+                (0..len)
+                    .map(|i| Value::Number(unsafe { *data.add(i) } as f64))
+                    .collect()
+            }
+            _ => bail!(
+                "Expected a GLib array list type, got {:?}",
+                array_type.list_type
+            ),
+        };
+
+        Ok(Value::Array(values))
+    }
+
+    /// Reads an integer of the given type from memory, as a `BigInt` if the
+    /// type asks for one.
+    pub fn from_integer_ptr(ptr: *const u8, int_type: &IntegerType) -> Self {
+        if int_type.is_bigint {
+            Value::BigInt(dispatch_integer_read!(int_type, ptr, i128))
+        } else {
+            Value::Number(dispatch_integer_read!(int_type, ptr))
+        }
+    }
+
+    /// Reads a value stored inline in a struct field or array item.
+    fn from_inline_ptr(ptr: *const u8, type_: &Type) -> anyhow::Result<Self> {
+        let value = match type_ {
+            Type::Integer(int_type) => Value::from_integer_ptr(ptr, int_type),
+            Type::Float(float_type) => Value::Number(match float_type.size {
+                FloatSize::_32 => unsafe { ptr.cast::<f32>().read_unaligned() as f64 },
+                FloatSize::_64 => unsafe { ptr.cast::<f64>().read_unaligned() },
+            }),
+            Type::Boolean => Value::Boolean(unsafe { ptr.cast::<u8>().read_unaligned() != 0 }),
+            Type::Enum(enum_type) => {
+                enum_type.to_value(unsafe { ptr.cast::<i32>().read_unaligned() })
+            }
+            Type::Flags(flags_type) => {
+                flags_type.to_value(unsafe { ptr.cast::<u32>().read_unaligned() })
+            }
+            Type::String(_) | Type::GObject(_) | Type::Boxed(_) => {
+                let item_ptr = unsafe { ptr.cast::<*mut c_void>().read_unaligned() };
+                Value::from_cif_value(&cif::Value::Ptr(item_ptr), type_)?
+            }
+            Type::Struct(nested_type) => Value::from_struct_ptr(ptr, nested_type)?,
+            _ => bail!("Unsupported inline value type: {:?}", type_),
+        };
+
+        Ok(value)
+    }
+
+    /// Reads the entries of a `GHashTable`.
+    ///
+    /// Keys and values are always copied or referenced. When `is_owned` is
+    /// set, the table is released afterwards and its destroy functions
+    /// release any keys and values that were transferred with it, as other
+    /// GObject Introspection bindings do. A null table yields no entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key or value type is not supported.
+    pub fn from_hash_table_ptr(
+        ptr: *mut c_void,
+        hash_table_type: &HashTableType,
+        is_owned: bool,
+    ) -> anyhow::Result<Self> {
+        let mut entries = Vec::new();
+
+        if ptr.is_null() {
+            return Ok(Value::HashTable(entries));
+        }
+
+        let _guard = HashTableGuard::new(ptr, is_owned);
+        let mut iter = std::mem::MaybeUninit::<glib::ffi::GHashTableIter>::uninit();
+        let mut key: glib::ffi::gpointer = std::ptr::null_mut();
+        let mut value: glib::ffi::gpointer = std::ptr::null_mut();
+
+        unsafe {
+            glib::ffi::g_hash_table_iter_init(iter.as_mut_ptr(), ptr as *mut _);
+        }
+
+        while unsafe { glib::ffi::g_hash_table_iter_next(iter.as_mut_ptr(), &mut key, &mut value) }
+            != 0
+        {
+            entries.push((
+                Value::from_pointer_item(key, &hash_table_type.key_type)?,
+                Value::from_pointer_item(value, &hash_table_type.value_type)?,
+            ));
+        }
+
+        Ok(Value::HashTable(entries))
+    }
+
+    /// Converts a `GVariant*` to a structured value.
+    ///
+    /// Borrowed variants are referenced without touching their floating
+    /// flag, since the callee still owns them. Owned variants take over the
+    /// caller's reference, sinking it first if the callee returned a floating
+    /// variant (as the `g_variant_new_*` constructors do). A null variant
+    /// yields null.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the variant cannot be converted.
+    pub fn from_variant_ptr(ptr: *mut c_void, is_borrowed: bool) -> anyhow::Result<Self> {
+        if ptr.is_null() {
+            return Ok(Value::Null);
+        }
+
+        let ptr = ptr as *mut glib::ffi::GVariant;
+
+        let variant: glib::Variant = unsafe {
+            if is_borrowed {
+                glib::Variant::from_glib_full(glib::ffi::g_variant_ref(ptr))
+            } else {
+                glib::Variant::from_glib_full(glib::ffi::g_variant_take_ref(ptr))
+            }
+        };
+
+        variant::to_value(&variant)
+    }
+
+    fn from_pointer_item(data: *mut c_void, type_: &Type) -> anyhow::Result<Self> {
+        match type_ {
+            Type::Integer(int_type) if int_type.is_bigint => match int_type.sign {
+                IntegerSign::Signed => Ok(Value::BigInt(data as isize as i128)),
+                IntegerSign::Unsigned => Ok(Value::BigInt(data as usize as i128)),
+            },
+            Type::Integer(int_type) => match int_type.sign {
+                IntegerSign::Signed => Ok(Value::Number(data as isize as f64)),
+                IntegerSign::Unsigned => Ok(Value::Number(data as usize as f64)),
+            },
+            Type::Boolean => Ok(Value::Boolean(!data.is_null())),
+            Type::String(_) => {
+                if data.is_null() {
+                    return Ok(Value::Null);
+                }
+
+                let c_str = unsafe { CStr::from_ptr(data as *const i8) };
+                Ok(Value::String(c_str.to_string_lossy().into_owned()))
+            }
+            Type::GObject(_) => {
+                if data.is_null() {
+                    return Ok(Value::Null);
+                }
+
+                let object = unsafe {
+                    glib::Object::from_glib_none(data as *mut glib::gobject_ffi::GObject)
+                };
+                Ok(Value::Object(ObjectId::new(Object::GObject(object))))
+            }
+            Type::Boxed(boxed_type) => {
+                if data.is_null() {
+                    return Ok(Value::Null);
+                }
+
+                let boxed = Boxed::from_glib_none(boxed_type.get_gtype(), data);
+                Ok(Value::Object(ObjectId::new(Object::Boxed(boxed))))
+            }
+            _ => bail!("Unsupported container item type: {:?}", type_),
+        }
+    }
 }
 
 impl Value {
@@ -723,6 +1572,28 @@ impl Value {
     /// or if extraction from the GLib value fails.
     pub fn from_glib_value(gvalue: &glib::Value, type_: &Type) -> anyhow::Result<Self> {
         match type_ {
+            Type::Integer(int_type) if int_type.is_bigint => {
+                let value = match (int_type.size, int_type.sign) {
+                    (IntegerSize::_64, IntegerSign::Signed) => gvalue
+                        .get::<i64>()
+                        .map_err(|e| anyhow::anyhow!("Failed to get i64 from GValue: {}", e))?
+                        as i128,
+                    (IntegerSize::_64, IntegerSign::Unsigned) => gvalue
+                        .get::<u64>()
+                        .map_err(|e| anyhow::anyhow!("Failed to get u64 from GValue: {}", e))?
+                        as i128,
+                    (size, sign) => {
+                        let number_type = Type::Integer(IntegerType::new(size, sign));
+
+                        match Value::from_glib_value(gvalue, &number_type)? {
+                            Value::Number(n) => n as i128,
+                            value => return Ok(value),
+                        }
+                    }
+                };
+
+                Ok(Value::BigInt(value))
+            }
             Type::Integer(int_type) => {
                 let gtype = gvalue.type_();
                 let is_enum = gtype.is_a(glib::types::Type::ENUM);
@@ -856,8 +1727,59 @@ impl Value {
                 let object_id = ObjectId::new(Object::Boxed(boxed));
                 Ok(Value::Object(object_id))
             }
+            Type::HashTable(hash_table_type) => {
+                let table_ptr = unsafe {
+                    glib::gobject_ffi::g_value_get_boxed(gvalue.to_glib_none().0 as *const _)
+                };
+
+                Value::from_hash_table_ptr(table_ptr, hash_table_type, false)
+            }
+            Type::Variant(_) => {
+                let variant: Option<glib::Variant> = gvalue
+                    .get()
+                    .map_err(|e| anyhow::anyhow!("Failed to get GVariant from GValue: {}", e))?;
+
+                match variant {
+                    Some(variant) => variant::to_value(&variant),
+                    None => Ok(Value::Null),
+                }
+            }
+            Type::Enum(enum_type) => {
+                let raw = if gvalue.type_().is_a(glib::types::Type::ENUM) {
+                    unsafe { glib::gobject_ffi::g_value_get_enum(gvalue.to_glib_none().0) }
+                } else {
+                    gvalue
+                        .get::<i32>()
+                        .map_err(|e| anyhow::anyhow!("Failed to get enum from GValue: {}", e))?
+                };
+
+                Ok(enum_type.to_value(raw))
+            }
+            Type::Flags(flags_type) => {
+                let raw = if gvalue.type_().is_a(glib::types::Type::FLAGS) {
+                    unsafe { glib::gobject_ffi::g_value_get_flags(gvalue.to_glib_none().0) }
+                } else {
+                    gvalue
+                        .get::<u32>()
+                        .map_err(|e| anyhow::anyhow!("Failed to get flags from GValue: {}", e))?
+                };
+
+                Ok(flags_type.to_value(raw))
+            }
+            Type::Bytes(_) => {
+                let bytes: Option<glib::Bytes> = gvalue
+                    .get()
+                    .map_err(|e| anyhow::anyhow!("Failed to get GBytes from GValue: {}", e))?;
+
+                Ok(bytes.map(Value::Bytes).unwrap_or(Value::Null))
+            }
             Type::Null | Type::Undefined => Ok(Value::Null),
-            Type::Array(_) | Type::Ref(_) | Type::Callback(_) => {
+            Type::Array(_)
+            | Type::Ref(_)
+            | Type::Callback(_)
+            | Type::Struct(_)
+            | Type::Error
+            | Type::Cancellable => {
                 bail!(
                     "Type {:?} should not appear in glib value conversion - this indicates a bug in the type mapping",
                     type_
@@ -869,17 +1791,92 @@ impl Value {
     /// Converts this value to a GLib Value, providing defaults for undefined values.
     ///
     /// When the value is `Undefined`, returns a sensible default based on the
-    /// expected return type (false for booleans, 0 for integers). This is used
-    /// for callback return values where JavaScript may return undefined.
-    pub fn into_glib_value_with_default(self, return_type: Option<&Type>) -> Option<glib::Value> {
-        match &self {
+    /// expected return type (false for booleans, 0 for integers, enums and
+    /// flags). This is used for callback return values where JavaScript may
+    /// return undefined.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not defined by an enum or flags return
+    /// type, or cannot be converted to a variant return type.
+    pub fn into_glib_value_with_default(
+        self,
+        return_type: Option<&Type>,
+    ) -> anyhow::Result<Option<glib::Value>> {
+        if let Some(Type::Variant(variant_type)) = return_type {
+            // The returned GValue takes its own reference to the variant, so
+            // no floating reference is handed over to the closure's caller.
+            return Ok(match self {
+                Value::Null | Value::Undefined => Some(glib::Value::from_type(glib::Type::VARIANT)),
+                _ => Some(glib::Value::from(variant::from_value(
+                    &self,
+                    variant_type.signature.as_deref(),
+                )?)),
+            });
+        }
+
+        // Enum and flags returns must carry the registered GType so the
+        // closure's caller can copy them into its own typed return value.
+        match return_type {
+            Some(Type::Enum(enum_type)) => {
+                let raw = match self {
+                    Value::Undefined => 0,
+                    _ => enum_type.to_raw(&self).map_err(|err| {
+                        anyhow::anyhow!(
+                            "Invalid value {:?} for enum {}: {}",
+                            self,
+                            enum_type.type_,
+                            err
+                        )
+                    })?,
+                };
+
+                return Ok(Some(match enum_type.get_gtype() {
+                    Some(gtype) => {
+                        let mut gvalue = glib::Value::from_type(gtype);
+                        unsafe {
+                            glib::gobject_ffi::g_value_set_enum(gvalue.to_glib_none_mut().0, raw)
+                        };
+                        gvalue
+                    }
+                    None => raw.into(),
+                }));
+            }
+            Some(Type::Flags(flags_type)) => {
+                let raw = match self {
+                    Value::Undefined => 0,
+                    _ => flags_type.to_raw(&self).map_err(|err| {
+                        anyhow::anyhow!(
+                            "Invalid value {:?} for flags {}: {}",
+                            self,
+                            flags_type.type_,
+                            err
+                        )
+                    })?,
+                };
+
+                return Ok(Some(match flags_type.get_gtype() {
+                    Some(gtype) => {
+                        let mut gvalue = glib::Value::from_type(gtype);
+                        unsafe {
+                            glib::gobject_ffi::g_value_set_flags(gvalue.to_glib_none_mut().0, raw)
+                        };
+                        gvalue
+                    }
+                    None => raw.into(),
+                }));
+            }
+            _ => {}
+        }
+
+        Ok(match &self {
             Value::Undefined => match return_type {
                 Some(Type::Boolean) => Some(false.into()),
                 Some(Type::Integer(_)) => Some(0i32.into()),
                 _ => None,
             },
             _ => self.into(),
-        }
+        })
     }
 }
 
@@ -924,6 +1921,11 @@ impl TryFrom<&glib::Value> for Value {
             let obj = unsafe { glib::Object::from_glib_none(obj_ptr) };
 
             Ok(Value::Object(ObjectId::new(Object::GObject(obj))))
+        } else if value.is_type(glib::Bytes::static_type()) {
+            match value.get::<Option<glib::Bytes>>()? {
+                Some(bytes) => Ok(Value::Bytes(bytes)),
+                None => Ok(Value::Null),
+            }
         } else if value.is_type(glib::types::Type::BOXED) {
             let boxed_ptr = value.as_ptr();
             if boxed_ptr.is_null() {
@@ -933,6 +1935,11 @@ impl TryFrom<&glib::Value> for Value {
                 let object_id = ObjectId::new(Object::Boxed(boxed));
                 Ok(Value::Object(object_id))
             }
+        } else if value.is_type(glib::types::Type::VARIANT) {
+            match value.get::<Option<glib::Variant>>()? {
+                Some(variant) => variant::to_value(&variant),
+                None => Ok(Value::Null),
+            }
         } else if value.type_().is_a(glib::types::Type::PARAM_SPEC) {
             let ps = value.get::<glib::ParamSpec>()?;
             Ok(Value::String(ps.name().to_string()))
@@ -977,8 +1984,13 @@ impl From<Value> for Option<glib::Value> {
     fn from(value: Value) -> Self {
         match value {
             Value::Number(n) => Some(n.into()),
+            Value::BigInt(n) => match i64::try_from(n) {
+                Ok(n) => Some(n.into()),
+                Err(_) => u64::try_from(n).ok().map(Into::into),
+            },
             Value::String(s) => Some(s.into()),
             Value::Boolean(b) => Some(b.into()),
+            Value::Bytes(bytes) => Some(bytes.into()),
             Value::Null | Value::Undefined => None,
             _ => None,
         }
@@ -989,11 +2001,11 @@ impl From<Value> for Option<glib::Value> {
 mod tests {
     use super::*;
     use crate::test_utils;
-    use crate::types::{ArrayType, BoxedType, GObjectType, ListType, StringType};
+    use crate::types::{BoxedType, FloatType, GObjectType, StringType, VariantType};
     use gtk4::gdk;
-    use gtk4::glib::translate::IntoGlib as _;
+    use gtk4::glib::translate::{IntoGlib as _, IntoGlibPtr as _};
+    use gtk4::glib::variant::ToVariant as _;
     use gtk4::prelude::ObjectType as _;
-    use gtk4::prelude::StaticType as _;
 
     fn get_gobject_refcount(ptr: *mut glib::gobject_ffi::GObject) -> u32 {
         if ptr.is_null() {
@@ -1238,6 +2250,7 @@ mod tests {
             item_type: Box::new(Type::GObject(gobject_type)),
             list_type: ListType::GList,
             is_borrowed: true,
+            length: None,
         };
         let type_ = Type::Array(array_type);
 
@@ -1287,6 +2300,7 @@ mod tests {
             item_type: Box::new(Type::GObject(gobject_type)),
             list_type: ListType::GList,
             is_borrowed: false,
+            length: None,
         };
         let type_ = Type::Array(array_type);
 
@@ -1310,6 +2324,7 @@ mod tests {
             item_type: Box::new(Type::GObject(gobject_type)),
             list_type: ListType::GList,
             is_borrowed: false,
+            length: None,
         };
         let type_ = Type::Array(array_type);
 
@@ -1342,6 +2357,7 @@ mod tests {
             item_type: Box::new(Type::String(string_type)),
             list_type: ListType::Array,
             is_borrowed: true,
+            length: None,
         };
         let type_ = Type::Array(array_type);
 
@@ -1389,6 +2405,7 @@ mod tests {
             item_type: Box::new(Type::String(string_type)),
             list_type: ListType::Array,
             is_borrowed: false,
+            length: None,
         };
         let type_ = Type::Array(array_type);
 
@@ -1403,6 +2420,81 @@ mod tests {
         }
     }
 
+    fn sized_array_type(item_type: Type, length: usize, is_borrowed: bool) -> Type {
+        let mut array_type = ArrayType::new(item_type);
+        array_type.is_borrowed = is_borrowed;
+        array_type.length = Some(ArrayLength::Fixed(length));
+        Type::Array(array_type)
+    }
+
+    #[test]
+    fn sized_array_reads_integer_items() {
+        let ptr = unsafe { glib::ffi::g_malloc(3 * std::mem::size_of::<i32>()) as *mut i32 };
+        unsafe {
+            *ptr = 1;
+            *ptr.add(1) = -2;
+            *ptr.add(2) = 3;
+        }
+
+        let int_type = crate::types::IntegerType::new(IntegerSize::_32, IntegerSign::Signed);
+        let type_ = sized_array_type(Type::Integer(int_type), 3, false);
+
+        let result = Value::from_cif_value(&cif::Value::Ptr(ptr as *mut c_void), &type_).unwrap();
+
+        let Value::Array(items) = result else {
+            panic!("Expected Value::Array");
+        };
+        let numbers: Vec<f64> = items
+            .iter()
+            .map(|item| match item {
+                Value::Number(n) => *n,
+                _ => panic!("Expected Value::Number"),
+            })
+            .collect();
+        assert_eq!(numbers, vec![1.0, -2.0, 3.0]);
+    }
+
+    #[test]
+    fn sized_array_reads_gobject_items_without_terminator() {
+        test_utils::ensure_gtk_init();
+
+        let first = glib::Object::new::<glib::Object>();
+        let second = glib::Object::new::<glib::Object>();
+        let items = [first.as_ptr(), second.as_ptr()];
+
+        let type_ = sized_array_type(Type::GObject(GObjectType { is_borrowed: true }), 2, true);
+
+        let result =
+            Value::from_cif_value(&cif::Value::Ptr(items.as_ptr() as *mut c_void), &type_).unwrap();
+
+        assert!(matches!(&result, Value::Array(items) if items.len() == 2));
+        assert_eq!(get_gobject_refcount(first.as_ptr()), 2);
+        assert_eq!(get_gobject_refcount(second.as_ptr()), 2);
+    }
+
+    #[test]
+    fn sized_array_null_pointer_is_empty() {
+        let type_ = sized_array_type(Type::String(StringType { is_borrowed: true }), 4, false);
+
+        let result = Value::from_cif_value(&cif::Value::Ptr(std::ptr::null_mut()), &type_);
+
+        assert!(matches!(result, Ok(Value::Array(items)) if items.is_empty()));
+    }
+
+    #[test]
+    fn sized_array_requires_resolved_length() {
+        let mut array_type = ArrayType::new(Type::Boolean);
+        array_type.length = Some(ArrayLength::Arg(1));
+        let mut value = 1u8;
+
+        let result = Value::from_cif_value(
+            &cif::Value::Ptr(&mut value as *mut u8 as *mut c_void),
+            &Type::Array(array_type),
+        );
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn from_glib_value_gobject_borrowed() {
         test_utils::ensure_gtk_init();
@@ -1469,10 +2561,7 @@ mod tests {
 
         let gvalue_i32: glib::Value = 42i32.into();
 
-        let int_type = crate::types::IntegerType {
-            size: crate::types::IntegerSize::_32,
-            sign: crate::types::IntegerSign::Signed,
-        };
+        let int_type = crate::types::IntegerType::new(IntegerSize::_32, IntegerSign::Signed);
         let type_ = Type::Integer(int_type);
 
         let result = Value::from_glib_value(&gvalue_i32, &type_);
@@ -1485,6 +2574,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn bigint_integers_decode_losslessly() {
+        let unsigned = Type::Integer(IntegerType::bigint(IntegerSize::_64, IntegerSign::Unsigned));
+        let signed = Type::Integer(IntegerType::bigint(IntegerSize::_64, IntegerSign::Signed));
+
+        let result = Value::from_cif_value(&cif::Value::U64(u64::MAX), &unsigned).unwrap();
+        assert!(matches!(result, Value::BigInt(n) if n == u64::MAX as i128));
+
+        let result = Value::from_cif_value(&cif::Value::I64(i64::MIN), &signed).unwrap();
+        assert!(matches!(result, Value::BigInt(n) if n == i64::MIN as i128));
+    }
+
+    #[test]
+    fn bigint_ref_reads_out_parameter() {
+        let int_type = IntegerType::bigint(IntegerSize::_64, IntegerSign::Signed);
+        let storage = Box::new(cif::Value::I64((1 << 53) + 1));
+        let ptr = storage.as_ptr();
+        let cif_value = cif::Value::OwnedPtr(cif::OwnedPtr {
+            ptr,
+            value: storage,
+        });
+
+        let type_ = Type::Ref(crate::types::RefType::new(Type::Integer(int_type)));
+        let result = Value::from_cif_value(&cif_value, &type_).unwrap();
+
+        assert!(matches!(result, Value::BigInt(n) if n == (1 << 53) + 1));
+    }
+
     #[test]
     fn from_glib_value_floats() {
         test_utils::ensure_gtk_init();
@@ -1505,4 +2622,429 @@ mod tests {
             panic!("Expected Value::Number");
         }
     }
+
+    #[test]
+    fn hash_table_round_trips_through_cif() {
+        test_utils::ensure_gtk_init();
+
+        let hash_table_type = HashTableType::new(
+            Type::String(StringType { is_borrowed: false }),
+            Type::Integer(crate::types::IntegerType::new(
+                IntegerSize::_32,
+                IntegerSign::Signed,
+            )),
+            true,
+        );
+        let arg = crate::arg::Arg::new(
+            Type::HashTable(hash_table_type.clone()),
+            Value::HashTable(vec![
+                (Value::String("one".to_string()), Value::Number(1.0)),
+                (Value::String("minus".to_string()), Value::Number(-7.0)),
+            ]),
+        );
+
+        let cif_value = cif::Value::try_from(&arg).unwrap();
+        let table_ptr = match &cif_value {
+            cif::Value::OwnedPtr(owned) => owned.ptr,
+            other => panic!("Expected an owned GHashTable, got {:?}", other),
+        };
+
+        let result = Value::from_hash_table_ptr(table_ptr, &hash_table_type, false).unwrap();
+        let Value::HashTable(mut entries) = result else {
+            panic!("Expected Value::HashTable");
+        };
+        entries.sort_by(|a, b| format!("{:?}", a.0).cmp(&format!("{:?}", b.0)));
+
+        assert_eq!(entries.len(), 2);
+        assert!(
+            matches!(&entries[0], (Value::String(k), Value::Number(v)) if k == "minus" && *v == -7.0)
+        );
+        assert!(
+            matches!(&entries[1], (Value::String(k), Value::Number(v)) if k == "one" && *v == 1.0)
+        );
+    }
+
+    #[test]
+    fn owned_hash_table_copies_owned_items() {
+        test_utils::ensure_gtk_init();
+
+        let table = unsafe {
+            glib::ffi::g_hash_table_new_full(
+                Some(glib::ffi::g_str_hash),
+                Some(glib::ffi::g_str_equal),
+                Some(glib::ffi::g_free),
+                Some(glib::ffi::g_free),
+            )
+        };
+
+        unsafe {
+            glib::ffi::g_hash_table_insert(
+                table,
+                glib::ffi::g_strdup(c"key".as_ptr()) as *mut _,
+                glib::ffi::g_strdup(c"value".as_ptr()) as *mut _,
+            );
+        }
+
+        let hash_table_type = HashTableType::new(
+            Type::String(StringType { is_borrowed: false }),
+            Type::String(StringType { is_borrowed: false }),
+            false,
+        );
+
+        let result =
+            Value::from_hash_table_ptr(table as *mut c_void, &hash_table_type, true).unwrap();
+        let Value::HashTable(entries) = result else {
+            panic!("Expected Value::HashTable");
+        };
+
+        assert_eq!(entries.len(), 1);
+        assert!(
+            matches!(&entries[0], (Value::String(k), Value::String(v)) if k == "key" && v == "value")
+        );
+    }
+
+    unsafe extern "C" fn unref_object(ptr: glib::ffi::gpointer) {
+        unsafe { glib::gobject_ffi::g_object_unref(ptr as *mut _) }
+    }
+
+    /// Creates a table that owns its string keys and holds a reference to
+    /// each of its object values.
+    fn new_string_to_object_table() -> *mut glib::ffi::GHashTable {
+        unsafe {
+            glib::ffi::g_hash_table_new_full(
+                Some(glib::ffi::g_str_hash),
+                Some(glib::ffi::g_str_equal),
+                Some(glib::ffi::g_free),
+                Some(unref_object),
+            )
+        }
+    }
+
+    fn insert_object(table: *mut glib::ffi::GHashTable, obj: &glib::Object) {
+        unsafe {
+            glib::ffi::g_hash_table_insert(
+                table,
+                glib::ffi::g_strdup(c"key".as_ptr()) as *mut _,
+                glib::gobject_ffi::g_object_ref(obj.as_ptr()) as *mut _,
+            );
+        }
+    }
+
+    #[test]
+    fn owned_hash_table_with_owned_keys_releases_values() {
+        test_utils::ensure_gtk_init();
+
+        let obj = glib::Object::new::<glib::Object>();
+        let initial_ref = get_gobject_refcount(obj.as_ptr());
+        let table = new_string_to_object_table();
+        insert_object(table, &obj);
+
+        let hash_table_type = HashTableType::new(
+            Type::String(StringType { is_borrowed: false }),
+            Type::GObject(GObjectType { is_borrowed: true }),
+            false,
+        );
+
+        let result =
+            Value::from_hash_table_ptr(table as *mut c_void, &hash_table_type, true).unwrap();
+        let Value::HashTable(entries) = result else {
+            panic!("Expected Value::HashTable");
+        };
+
+        assert_eq!(entries.len(), 1);
+        assert!(matches!(&entries[0], (Value::String(k), Value::Object(_)) if k == "key"));
+        assert_eq!(get_gobject_refcount(obj.as_ptr()), initial_ref + 1);
+    }
+
+    #[test]
+    fn owned_hash_table_conversion_error_leaves_items_to_the_table() {
+        test_utils::ensure_gtk_init();
+
+        let obj = glib::Object::new::<glib::Object>();
+        let initial_ref = get_gobject_refcount(obj.as_ptr());
+        let table = new_string_to_object_table();
+        insert_object(table, &obj);
+
+        let hash_table_type = HashTableType::new(
+            Type::String(StringType { is_borrowed: false }),
+            Type::Float(FloatType::new(FloatSize::_64)),
+            false,
+        );
+
+        let result = Value::from_hash_table_ptr(table as *mut c_void, &hash_table_type, true);
+
+        assert!(result.is_err());
+        assert_eq!(get_gobject_refcount(obj.as_ptr()), initial_ref);
+    }
+
+    fn list_array_type(item_type: Type, list_type: ListType, is_borrowed: bool) -> ArrayType {
+        let mut array_type = ArrayType::new(item_type);
+        array_type.list_type = list_type;
+        array_type.is_borrowed = is_borrowed;
+        array_type
+    }
+
+    #[test]
+    fn ptr_array_round_trips_through_cif() {
+        let array_type = list_array_type(
+            Type::String(StringType { is_borrowed: true }),
+            ListType::PtrArray,
+            true,
+        );
+        let arg = crate::arg::Arg::new(
+            Type::Array(array_type.clone()),
+            Value::Array(vec![
+                Value::String("first".to_string()),
+                Value::String("second".to_string()),
+            ]),
+        );
+
+        let cif_value = cif::Value::try_from(&arg).unwrap();
+        let result = Value::from_cif_value(&cif_value, &Type::Array(array_type)).unwrap();
+
+        let Value::Array(items) = result else {
+            panic!("Expected Value::Array");
+        };
+        assert!(matches!(
+            items.as_slice(),
+            [Value::String(a), Value::String(b)] if a == "first" && b == "second"
+        ));
+    }
+
+    #[test]
+    fn ptr_array_full_transfer_takes_item_references() {
+        test_utils::ensure_gtk_init();
+
+        let object = glib::Object::new::<glib::Object>();
+        let array = unsafe { glib::ffi::g_ptr_array_new() };
+        unsafe {
+            glib::gobject_ffi::g_object_ref(object.as_ptr());
+            glib::ffi::g_ptr_array_add(array, object.as_ptr() as *mut c_void);
+        }
+
+        let array_type = list_array_type(
+            Type::GObject(GObjectType { is_borrowed: false }),
+            ListType::PtrArray,
+            false,
+        );
+        let result = Value::from_cif_value(
+            &cif::Value::Ptr(array as *mut c_void),
+            &Type::Array(array_type),
+        )
+        .unwrap();
+
+        assert!(matches!(&result, Value::Array(items) if items.len() == 1));
+        assert_eq!(get_gobject_refcount(object.as_ptr()), 2);
+    }
+
+    #[test]
+    fn garray_reads_inline_items() {
+        let int_type = crate::types::IntegerType::new(IntegerSize::_16, IntegerSign::Unsigned);
+        let array_type = list_array_type(Type::Integer(int_type), ListType::GArray, false);
+        let arg = crate::arg::Arg::new(
+            Type::Array(array_type.clone()),
+            Value::Array(vec![Value::Number(3.0), Value::Number(65535.0)]),
+        );
+
+        let ptr = match cif::Value::try_from(&arg).unwrap() {
+            cif::Value::Ptr(ptr) => ptr,
+            other => panic!("Expected a transferred GArray, got {:?}", other),
+        };
+        assert_eq!(
+            unsafe { glib::ffi::g_array_get_element_size(ptr as *mut _) },
+            2
+        );
+
+        let result =
+            Value::from_cif_value(&cif::Value::Ptr(ptr), &Type::Array(array_type)).unwrap();
+
+        let Value::Array(items) = result else {
+            panic!("Expected Value::Array");
+        };
+        assert!(matches!(
+            items.as_slice(),
+            [Value::Number(a), Value::Number(b)] if *a == 3.0 && *b == 65535.0
+        ));
+    }
+
+    #[test]
+    fn byte_array_null_pointer_is_empty() {
+        let int_type = crate::types::IntegerType::new(IntegerSize::_8, IntegerSign::Unsigned);
+        let array_type = list_array_type(Type::Integer(int_type), ListType::ByteArray, false);
+
+        let result = Value::from_cif_value(
+            &cif::Value::Ptr(std::ptr::null_mut()),
+            &Type::Array(array_type),
+        );
+
+        assert!(matches!(result, Ok(Value::Array(items)) if items.is_empty()));
+    }
+
+    #[test]
+    fn hash_table_null_pointer_has_no_entries() {
+        let hash_table_type = HashTableType::new(
+            Type::String(StringType { is_borrowed: true }),
+            Type::Boolean,
+            true,
+        );
+
+        let result = Value::from_cif_value(
+            &cif::Value::Ptr(std::ptr::null_mut()),
+            &Type::HashTable(hash_table_type),
+        )
+        .unwrap();
+
+        assert!(matches!(result, Value::HashTable(entries) if entries.is_empty()));
+    }
+
+    #[test]
+    fn hash_table_holds_gobject_reference_until_released() {
+        test_utils::ensure_gtk_init();
+
+        let obj = glib::Object::new::<glib::Object>();
+        let obj_ptr = obj.as_ptr();
+        let object_value = Value::Object(ObjectId::new(Object::GObject(obj.clone())));
+        let initial_ref = get_gobject_refcount(obj_ptr);
+
+        let hash_table_type = HashTableType::new(
+            Type::String(StringType { is_borrowed: false }),
+            Type::GObject(GObjectType { is_borrowed: false }),
+            true,
+        );
+        let arg = crate::arg::Arg::new(
+            Type::HashTable(hash_table_type),
+            Value::HashTable(vec![(Value::String("object".to_string()), object_value)]),
+        );
+
+        let cif_value = cif::Value::try_from(&arg).unwrap();
+        assert_eq!(get_gobject_refcount(obj_ptr), initial_ref + 1);
+
+        drop(cif_value);
+        assert_eq!(get_gobject_refcount(obj_ptr), initial_ref);
+    }
+
+    #[test]
+    fn variant_full_transfer_sinks_floating_ref() {
+        let variant_ptr = unsafe { glib::ffi::g_variant_new_int32(7) };
+        unsafe { glib::ffi::g_variant_ref(variant_ptr) };
+
+        let is_floating_before = unsafe { glib::ffi::g_variant_is_floating(variant_ptr) != 0 };
+        assert!(is_floating_before);
+
+        let type_ = Type::Variant(VariantType::new(None, false));
+        let cif_value = cif::Value::Ptr(variant_ptr as *mut c_void);
+        let result = Value::from_cif_value(&cif_value, &type_);
+
+        assert!(matches!(result, Ok(Value::Number(n)) if n == 7.0));
+
+        let is_floating_after = unsafe { glib::ffi::g_variant_is_floating(variant_ptr) != 0 };
+        assert!(!is_floating_after);
+
+        unsafe { glib::ffi::g_variant_unref(variant_ptr) };
+    }
+
+    #[test]
+    fn variant_borrowed_keeps_callee_reference() {
+        let variant = "borrowed".to_variant();
+        let variant_ptr = variant.as_ptr();
+
+        let type_ = Type::Variant(VariantType::new(None, true));
+        let cif_value = cif::Value::Ptr(variant_ptr as *mut c_void);
+        let result = Value::from_cif_value(&cif_value, &type_);
+
+        assert!(matches!(result, Ok(Value::String(s)) if s == "borrowed"));
+        assert_eq!(variant.str(), Some("borrowed"));
+    }
+
+    #[test]
+    fn variant_null_returns_null_value() {
+        let type_ = Type::Variant(VariantType::new(None, false));
+        let cif_value = cif::Value::Ptr(std::ptr::null_mut());
+        let result = Value::from_cif_value(&cif_value, &type_);
+
+        assert!(matches!(result, Ok(Value::Null)));
+    }
+
+    #[test]
+    fn from_glib_value_variant() {
+        let variant = glib::Variant::tuple_from_iter([1i32.to_variant(), 2i32.to_variant()]);
+        let gvalue: glib::Value = variant.into();
+
+        let type_ = Type::Variant(VariantType::new(None, true));
+        let result = Value::from_glib_value(&gvalue, &type_);
+
+        let Ok(Value::Array(items)) = result else {
+            panic!("Expected an Array value");
+        };
+        assert!(
+            matches!(items.as_slice(), [Value::Number(a), Value::Number(b)] if *a == 1.0 && *b == 2.0)
+        );
+        assert!(matches!(Value::try_from(&gvalue), Ok(Value::Array(_))));
+    }
+
+    #[test]
+    fn gbytes_full_transfer_adopts_reference() {
+        let bytes = glib::Bytes::from(&[1u8, 2, 3]);
+        let bytes_ptr = bytes.clone().into_glib_ptr();
+
+        let type_ = Type::Bytes(BytesType::new(BytesKind::GBytes, false, None));
+        let result = Value::from_cif_value(&cif::Value::Ptr(bytes_ptr as *mut c_void), &type_);
+
+        let Ok(Value::Bytes(result)) = result else {
+            panic!("Expected Value::Bytes");
+        };
+        assert_eq!(result.as_ptr(), bytes_ptr);
+        assert_eq!(&*result, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn owned_bytes_pointer_is_adopted_without_copying() {
+        let ptr = unsafe { glib::ffi::g_malloc(3) as *mut u8 };
+        unsafe { std::ptr::copy_nonoverlapping([4u8, 5, 6].as_ptr(), ptr, 3) };
+
+        let type_ = Type::Bytes(BytesType::new(
+            BytesKind::Pointer,
+            false,
+            Some(ArrayLength::Fixed(3)),
+        ));
+        let result = Value::from_cif_value(&cif::Value::Ptr(ptr as *mut c_void), &type_);
+
+        let Ok(Value::Bytes(result)) = result else {
+            panic!("Expected Value::Bytes");
+        };
+        assert_eq!(result.as_ref().as_ptr(), ptr as *const u8);
+        assert_eq!(&*result, &[4, 5, 6]);
+    }
+
+    #[test]
+    fn bytes_pointer_requires_resolved_length() {
+        let data = [1u8, 2, 3];
+        let type_ = Type::Bytes(BytesType::new(
+            BytesKind::Pointer,
+            true,
+            Some(ArrayLength::Arg(1)),
+        ));
+
+        let result = Value::from_cif_value(&cif::Value::Ptr(data.as_ptr() as *mut c_void), &type_);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn borrowed_bytes_pointer_round_trips_through_cif() {
+        let type_ = BytesType::new(BytesKind::Pointer, true, Some(ArrayLength::Fixed(2)));
+        let arg = crate::arg::Arg::new(
+            Type::Bytes(type_),
+            Value::Bytes(glib::Bytes::from(&[7u8, 8])),
+        );
+
+        let cif_value = cif::Value::try_from(&arg).unwrap();
+        let cif::Value::OwnedPtr(owned_ptr) = &cif_value else {
+            panic!("Expected cif::Value::OwnedPtr");
+        };
+        let result =
+            Value::from_cif_value(&cif::Value::Ptr(owned_ptr.ptr), &Type::Bytes(type_)).unwrap();
+
+        assert!(matches!(result, Value::Bytes(bytes) if *bytes == [7, 8]));
+    }
 }
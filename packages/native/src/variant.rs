@@ -0,0 +1,409 @@
+//! Conversion between [`Value`] and `GVariant`.
+//!
+//! Values are converted to variants using a GVariant type string when one is
+//! available, or by inferring the variant type from the value otherwise:
+//!
+//! - Booleans become `b`, strings become `s`
+//! - Integral numbers become `i` (or `x` when outside the 32-bit range),
+//!   other numbers become `d`
+//! - `BigInt`s become `x` (or `t` when outside the signed 64-bit range)
+//! - Arrays become `a*` when all items share a type, `av` otherwise
+//! - Bytes become `ay`, sharing the data without copying it
+//! - Maps and plain objects become `a{sv}`
+//!
+//! Variants are converted back to structured values: containers become
+//! arrays, dictionaries become hash tables, and `Nothing` becomes null.
+
+use anyhow::bail;
+use gtk4::glib::{
+    self, VariantClass, VariantTy,
+    variant::{FromVariant, Handle, ObjectPath, Signature, ToVariant as _},
+};
+
+use crate::value::Value;
+
+/// Converts a [`Value`] to a `GVariant`.
+///
+/// When `type_` is `None`, the variant type is inferred from the value.
+///
+/// # Errors
+///
+/// Returns an error if the value does not match the variant type, or if no
+/// variant type can be inferred for it.
+pub fn from_value(value: &Value, type_: Option<&VariantTy>) -> anyhow::Result<glib::Variant> {
+    let Some(type_) = type_ else {
+        return infer_from_value(value);
+    };
+
+    if type_.is_variant() {
+        return Ok(glib::Variant::from_variant(&infer_from_value(value)?));
+    }
+
+    if type_.is_maybe() {
+        return match value {
+            Value::Null | Value::Undefined => Ok(glib::Variant::from_none(type_.element())),
+            _ => Ok(glib::Variant::from_some(&from_value(
+                value,
+                Some(type_.element()),
+            )?)),
+        };
+    }
+
+    if type_.is_array() {
+        let element = type_.element();
+
+        if let Value::Bytes(bytes) = value
+            && element == VariantTy::BYTE
+        {
+            return Ok(glib::Variant::from_bytes_with_type(bytes, type_));
+        }
+
+        let children = if element.is_dict_entry() {
+            let Value::HashTable(entries) = value else {
+                bail!(
+                    "Expected a Map or object for GVariant type '{}', got {:?}",
+                    type_.as_str(),
+                    value
+                );
+            };
+
+            entries
+                .iter()
+                .map(|(key, value)| {
+                    Ok(glib::Variant::from_dict_entry(
+                        &from_value(key, Some(element.key()))?,
+                        &from_value(value, Some(element.value()))?,
+                    ))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?
+        } else {
+            let Value::Array(items) = value else {
+                bail!(
+                    "Expected an Array for GVariant type '{}', got {:?}",
+                    type_.as_str(),
+                    value
+                );
+            };
+
+            items
+                .iter()
+                .map(|item| from_value(item, Some(element)))
+                .collect::<anyhow::Result<Vec<_>>>()?
+        };
+
+        return Ok(glib::Variant::array_from_iter_with_type(element, children));
+    }
+
+    if type_.is_tuple() || type_.is_dict_entry() {
+        let Value::Array(items) = value else {
+            bail!(
+                "Expected an Array for GVariant type '{}', got {:?}",
+                type_.as_str(),
+                value
+            );
+        };
+
+        let child_types = if type_.is_dict_entry() {
+            vec![type_.key(), type_.value()]
+        } else {
+            type_.tuple_types().collect::<Vec<_>>()
+        };
+
+        if child_types.len() != items.len() {
+            bail!(
+                "Expected {} items for GVariant type '{}', got {}",
+                child_types.len(),
+                type_.as_str(),
+                items.len()
+            );
+        }
+
+        let children = items
+            .iter()
+            .zip(child_types)
+            .map(|(child, child_type)| from_value(child, Some(child_type)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        if type_.is_dict_entry() {
+            return Ok(glib::Variant::from_dict_entry(&children[0], &children[1]));
+        }
+
+        return Ok(glib::Variant::tuple_from_iter(children));
+    }
+
+    match (type_.as_str(), value) {
+        ("b", Value::Boolean(b)) => Ok(b.to_variant()),
+        ("y", _) => Ok(integer::<u8>(value, type_)?.to_variant()),
+        ("n", _) => Ok(integer::<i16>(value, type_)?.to_variant()),
+        ("q", _) => Ok(integer::<u16>(value, type_)?.to_variant()),
+        ("i", _) => Ok(integer::<i32>(value, type_)?.to_variant()),
+        ("u", _) => Ok(integer::<u32>(value, type_)?.to_variant()),
+        ("x", _) => Ok(integer::<i64>(value, type_)?.to_variant()),
+        ("t", _) => Ok(integer::<u64>(value, type_)?.to_variant()),
+        ("h", _) => Ok(Handle(integer::<i32>(value, type_)?).to_variant()),
+        ("d", Value::Number(n)) => Ok(n.to_variant()),
+        ("s", Value::String(s)) => Ok(s.to_variant()),
+        ("o", Value::String(s)) => Ok(ObjectPath::try_from(s.as_str())?.to_variant()),
+        ("g", Value::String(s)) => Ok(Signature::try_from(s.as_str())?.to_variant()),
+        _ => bail!(
+            "Expected a value matching GVariant type '{}', got {:?}",
+            type_.as_str(),
+            value
+        ),
+    }
+}
+
+/// Converts an integral number or `BigInt` to the integer type of a
+/// variant, checking that it fits.
+fn integer<T: TryFrom<i128>>(value: &Value, type_: &VariantTy) -> anyhow::Result<T> {
+    let n = match value {
+        Value::Number(n) if n.is_finite() && n.fract() == 0.0 => *n as i128,
+        Value::BigInt(n) => *n,
+        _ => bail!(
+            "Expected an integer for GVariant type '{}', got {:?}",
+            type_.as_str(),
+            value
+        ),
+    };
+
+    T::try_from(n).map_err(|_| {
+        anyhow::anyhow!(
+            "{} is out of range for GVariant type '{}'",
+            n,
+            type_.as_str()
+        )
+    })
+}
+
+fn infer_from_value(value: &Value) -> anyhow::Result<glib::Variant> {
+    match value {
+        Value::Boolean(b) => Ok(b.to_variant()),
+        Value::Number(n) if n.fract() == 0.0 && *n >= i32::MIN as f64 && *n <= i32::MAX as f64 => {
+            Ok((*n as i32).to_variant())
+        }
+        Value::Number(n) if n.fract() == 0.0 && *n >= i64::MIN as f64 && *n < i64::MAX as f64 => {
+            Ok((*n as i64).to_variant())
+        }
+        Value::Number(n) => Ok(n.to_variant()),
+        Value::BigInt(n) => match i64::try_from(*n) {
+            Ok(n) => Ok(n.to_variant()),
+            Err(_) => Ok(u64::try_from(*n)?.to_variant()),
+        },
+        Value::String(s) => Ok(s.to_variant()),
+        Value::Array(items) => {
+            let children = items
+                .iter()
+                .map(infer_from_value)
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            match children.first() {
+                Some(first) if children.iter().all(|child| child.type_() == first.type_()) => Ok(
+                    glib::Variant::array_from_iter_with_type(first.type_(), &children),
+                ),
+                _ => Ok(glib::Variant::array_from_iter_with_type(
+                    VariantTy::VARIANT,
+                    children.iter().map(glib::Variant::from_variant),
+                )),
+            }
+        }
+        Value::HashTable(_) => from_value(value, Some(VariantTy::VARDICT)),
+        Value::Bytes(bytes) => Ok(glib::Variant::from_bytes_with_type(
+            bytes,
+            VariantTy::BYTE_STRING,
+        )),
+        _ => bail!("Cannot infer a GVariant type for {:?}", value),
+    }
+}
+
+/// Converts a `GVariant` to a structured [`Value`].
+///
+/// # Errors
+///
+/// Returns an error if the variant has an unknown class.
+pub fn to_value(variant: &glib::Variant) -> anyhow::Result<Value> {
+    let value = match variant.classify() {
+        VariantClass::Boolean => Value::Boolean(get::<bool>(variant)?),
+        VariantClass::Byte => Value::Number(get::<u8>(variant)? as f64),
+        VariantClass::Int16 => Value::Number(get::<i16>(variant)? as f64),
+        VariantClass::Uint16 => Value::Number(get::<u16>(variant)? as f64),
+        VariantClass::Int32 => Value::Number(get::<i32>(variant)? as f64),
+        VariantClass::Uint32 => Value::Number(get::<u32>(variant)? as f64),
+        VariantClass::Int64 => Value::Number(get::<i64>(variant)? as f64),
+        VariantClass::Uint64 => Value::Number(get::<u64>(variant)? as f64),
+        VariantClass::Handle => Value::Number(get::<Handle>(variant)?.0 as f64),
+        VariantClass::Double => Value::Number(get::<f64>(variant)?),
+        VariantClass::String | VariantClass::ObjectPath | VariantClass::Signature => {
+            Value::String(variant.str().unwrap_or_default().to_string())
+        }
+        VariantClass::Variant => match variant.as_variant() {
+            Some(child) => to_value(&child)?,
+            None => Value::Null,
+        },
+        VariantClass::Maybe => match variant.as_maybe() {
+            Some(child) => to_value(&child)?,
+            None => Value::Null,
+        },
+        VariantClass::Array if variant.type_().element().is_dict_entry() => {
+            let entries = variant
+                .iter()
+                .map(|entry| {
+                    Ok((
+                        to_value(&entry.child_value(0))?,
+                        to_value(&entry.child_value(1))?,
+                    ))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            Value::HashTable(entries)
+        }
+        VariantClass::Array | VariantClass::Tuple | VariantClass::DictEntry => {
+            let items = variant
+                .iter()
+                .map(|child| to_value(&child))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            Value::Array(items)
+        }
+        class => bail!("Unsupported GVariant class: {:?}", class),
+    };
+
+    Ok(value)
+}
+
+fn get<T: FromVariant>(variant: &glib::Variant) -> anyhow::Result<T> {
+    variant.get::<T>().ok_or_else(|| {
+        anyhow::anyhow!(
+            "Failed to read GVariant of type '{}'",
+            variant.type_().as_str()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant_type(signature: &str) -> glib::VariantType {
+        glib::VariantType::new(signature).unwrap()
+    }
+
+    #[test]
+    fn converts_tuples_with_signature() {
+        let value = Value::Array(vec![Value::Number(1.0), Value::String("two".to_string())]);
+        let variant = from_value(&value, Some(&variant_type("(is)"))).unwrap();
+
+        assert_eq!(variant.type_().as_str(), "(is)");
+        assert_eq!(variant.print(false).as_str(), "(1, 'two')");
+    }
+
+    #[test]
+    fn converts_vardict_with_signature() {
+        let value = Value::HashTable(vec![
+            (Value::String("count".to_string()), Value::Number(3.0)),
+            (Value::String("enabled".to_string()), Value::Boolean(true)),
+        ]);
+        let variant = from_value(&value, Some(&variant_type("a{sv}"))).unwrap();
+
+        assert_eq!(variant.type_().as_str(), "a{sv}");
+        assert_eq!(variant.n_children(), 2);
+    }
+
+    #[test]
+    fn infers_variant_types() {
+        let cases = [
+            (Value::Boolean(true), "b"),
+            (Value::Number(42.0), "i"),
+            (Value::Number(5_000_000_000.0), "x"),
+            (Value::Number(1.5), "d"),
+            (Value::BigInt(1), "x"),
+            (Value::BigInt(u64::MAX as i128), "t"),
+            (Value::String("text".to_string()), "s"),
+            (
+                Value::Array(vec![
+                    Value::String("a".to_string()),
+                    Value::String("b".to_string()),
+                ]),
+                "as",
+            ),
+            (
+                Value::Array(vec![Value::Number(1.0), Value::String("b".to_string())]),
+                "av",
+            ),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(from_value(&value, None).unwrap().type_().as_str(), expected);
+        }
+    }
+
+    #[test]
+    fn rejects_mismatched_values() {
+        assert!(from_value(&Value::String("1".to_string()), Some(&variant_type("i"))).is_err());
+        assert!(
+            from_value(
+                &Value::Array(vec![Value::Number(1.0)]),
+                Some(&variant_type("(ii)"))
+            )
+            .is_err()
+        );
+        assert!(
+            from_value(
+                &Value::String("not a path".to_string()),
+                Some(&variant_type("o"))
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_integers_outside_the_variant_type() {
+        let cases = [
+            (Value::Number(2f64.powi(40)), "i"),
+            (Value::Number(256.0), "y"),
+            (Value::Number(-1.0), "u"),
+            (Value::Number(1.5), "i"),
+            (Value::BigInt(1 << 64), "t"),
+            (Value::BigInt(i64::MIN as i128 - 1), "x"),
+        ];
+
+        for (value, type_) in cases {
+            assert!(from_value(&value, Some(&variant_type(type_))).is_err());
+        }
+
+        let max = from_value(&Value::Number(i32::MAX as f64), Some(&variant_type("i"))).unwrap();
+        assert_eq!(max.get::<i32>(), Some(i32::MAX));
+    }
+
+    #[test]
+    fn round_trips_nested_containers() {
+        let value = Value::Array(vec![
+            Value::Array(vec![Value::Number(1.0), Value::Null]),
+            Value::HashTable(vec![(
+                Value::String("key".to_string()),
+                Value::Array(vec![Value::String("a".to_string())]),
+            )]),
+        ]);
+        let variant = from_value(&value, Some(&variant_type("(amia{sv})"))).unwrap();
+
+        let Value::Array(items) = to_value(&variant).unwrap() else {
+            panic!("Expected an Array value");
+        };
+
+        assert!(matches!(&items[0], Value::Array(inner)
+            if matches!(inner.as_slice(), [Value::Number(n), Value::Null] if *n == 1.0)));
+        assert!(matches!(&items[1], Value::HashTable(entries)
+            if matches!(entries.as_slice(), [(Value::String(key), Value::Array(_))] if key == "key")));
+    }
+
+    #[test]
+    fn converts_bytes_to_byte_arrays() {
+        let value = Value::Bytes(glib::Bytes::from(&[1u8, 2, 3]));
+
+        let variant = from_value(&value, Some(&variant_type("ay"))).unwrap();
+        assert_eq!(variant.type_().as_str(), "ay");
+        assert_eq!(variant.data(), &[1, 2, 3]);
+
+        let inferred = from_value(&value, None).unwrap();
+        assert_eq!(inferred.type_().as_str(), "ay");
+    }
+}
@@ -0,0 +1,198 @@
+//! Detection of stuck cross-thread waits.
+//!
+//! While the watchdog is enabled, each thread records what it is doing as a
+//! stack of activities, such as the symbol being called or the callback being
+//! run. A thread waiting for the other one sleeps for at most the configured
+//! timeout. When the timeout is exceeded, the wait is reported with the
+//! activities of both threads, and is abandoned if configured to throw.
+//!
+//! Reports are passed to the JS function set with [`set_reporter`], as a
+//! queued callback, so they reach JS whichever thread is waiting. They are
+//! dropped while no function is set.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use neon::prelude::*;
+
+use crate::{config, js_dispatch, notify::Notify, value::Value};
+
+/// Activities of the JS thread.
+pub static JS_THREAD: Activities = Activities::new("JS");
+
+/// Activities of the GTK thread.
+pub static GTK_THREAD: Activities = Activities::new("GTK");
+
+/// The JS function stalled waits are reported to.
+struct Reporter {
+    channel: Channel,
+    callback: Arc<Root<JsFunction>>,
+}
+
+static REPORTER: Mutex<Option<Reporter>> = Mutex::new(None);
+
+/// Sets the JS function stalled waits are reported to, with the channel used
+/// to wake the JS thread for it. `None` stops reporting them.
+pub fn set_reporter(reporter: Option<(Channel, Root<JsFunction>)>) {
+    let reporter = reporter.map(|(channel, callback)| Reporter {
+        channel,
+        callback: Arc::new(callback),
+    });
+
+    *REPORTER.lock().unwrap() = reporter;
+}
+
+fn report(message: String) {
+    if let Some(reporter) = REPORTER.lock().unwrap().as_ref() {
+        js_dispatch::queue_with_wakeup(
+            &reporter.channel,
+            reporter.callback.clone(),
+            vec![Value::String(message)],
+            false,
+        );
+    }
+}
+
+/// A stack of what a thread is currently doing, innermost last.
+pub struct Activities {
+    name: &'static str,
+    stack: Mutex<Vec<String>>,
+}
+
+impl Activities {
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            stack: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Records an activity until the returned guard is dropped.
+    ///
+    /// The description is only built while the watchdog is enabled.
+    #[must_use]
+    pub fn enter(&self, describe: impl FnOnce() -> String) -> Option<ActivityGuard<'_>> {
+        config::wait_timeout()?;
+        self.stack.lock().unwrap().push(describe());
+        Some(ActivityGuard(self))
+    }
+
+    fn describe(&self) -> String {
+        let stack = self.stack.lock().unwrap();
+
+        if stack.is_empty() {
+            "idle".to_string()
+        } else {
+            stack.join(" > ")
+        }
+    }
+}
+
+/// Removes an activity from its stack when dropped.
+pub struct ActivityGuard<'a>(&'a Activities);
+
+impl Drop for ActivityGuard<'_> {
+    fn drop(&mut self) {
+        self.0.stack.lock().unwrap().pop();
+    }
+}
+
+/// A wait of one thread for the other, timed by the watchdog.
+pub struct Wait {
+    activities: &'static Activities,
+    started: Instant,
+    reported: bool,
+}
+
+impl Wait {
+    /// Starts timing a wait of the thread with the given activities.
+    pub fn new(activities: &'static Activities) -> Self {
+        Self {
+            activities,
+            started: Instant::now(),
+            reported: false,
+        }
+    }
+
+    /// Sleeps until `notify` is notified after `token` was taken.
+    ///
+    /// Once the wait has lasted longer than the configured timeout, it is
+    /// reported, and an error is returned if waits should be abandoned.
+    pub fn sleep(&mut self, notify: &Notify, token: u64) -> anyhow::Result<()> {
+        let Some(timeout) = config::wait_timeout() else {
+            notify.wait(token);
+            return Ok(());
+        };
+
+        let elapsed = self.started.elapsed();
+
+        if elapsed < timeout {
+            notify.wait_timeout(token, timeout - elapsed);
+            return Ok(());
+        }
+
+        if !self.reported {
+            self.reported = true;
+            report(self.report(elapsed));
+        }
+
+        if config::throw_on_timeout() {
+            anyhow::bail!(
+                "Timed out after {}ms waiting for the {} thread",
+                elapsed.as_millis(),
+                self.other().name
+            );
+        }
+
+        notify.wait(token);
+        Ok(())
+    }
+
+    fn other(&self) -> &'static Activities {
+        if std::ptr::eq(self.activities, &JS_THREAD) {
+            &GTK_THREAD
+        } else {
+            &JS_THREAD
+        }
+    }
+
+    fn report(&self, elapsed: Duration) -> String {
+        format!(
+            "The {} thread has been waiting for the {} thread for {}ms\n  JS thread: {}\n  GTK thread: {}",
+            self.activities.name,
+            self.other().name,
+            elapsed.as_millis(),
+            JS_THREAD.describe(),
+            GTK_THREAD.describe()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_joins_activities_innermost_last() {
+        let activities = Activities::new("test");
+        {
+            let mut stack = activities.stack.lock().unwrap();
+            stack.push("calling gtk_widget_show".to_string());
+            stack.push("waiting for a JS callback".to_string());
+        }
+
+        assert_eq!(
+            activities.describe(),
+            "calling gtk_widget_show > waiting for a JS callback"
+        );
+    }
+
+    #[test]
+    fn describe_reports_idle_without_activities() {
+        let activities = Activities::new("test");
+
+        assert_eq!(activities.describe(), "idle");
+    }
+}
@@ -0,0 +1,288 @@
+//! Running native calls on a pool of worker threads.
+//!
+//! Calls made with the `thread: "worker"` option run on a GLib thread pool
+//! instead of the GTK thread, so that slow synchronous functions such as file
+//! I/O do not block rendering. Arguments and results are converted with the
+//! same code as on the GTK thread.
+//!
+//! Objects are tracked in the state of the GTK thread, and each worker has a
+//! state of its own. Objects passed to a worker call are therefore lent to the
+//! worker for the duration of the call, which is only allowed for types that
+//! are safe to use from any thread. Objects returned by the call are handed
+//! back to the GTK thread to be tracked there under new IDs.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+    thread,
+};
+
+use anyhow::bail;
+use gtk4::glib::{self, prelude::*};
+
+use crate::{
+    arg::Arg,
+    notify,
+    object::{Object, ObjectId},
+    state::GtkThreadState,
+    types::Type,
+    value::Value,
+};
+
+/// Types whose instances may be used from a worker while the GTK thread
+/// holds them too, including subtypes and implementations.
+///
+/// Boxed types are only included if copying them takes a reference, so that
+/// the worker operates on the same instance.
+const THREAD_SAFE_TYPES: &[&str] = &["GFile", "GCancellable", "GBytes", "GRegex"];
+
+static POOL: OnceLock<glib::ThreadPool> = OnceLock::new();
+
+/// Objects moved between the GTK thread and a worker, keyed by their ID on
+/// the thread they come from.
+#[derive(Default)]
+pub struct Objects(Vec<(usize, Object)>);
+
+// SAFETY: Objects lent to a worker are of thread-safe types, and objects
+// returned by a worker are no longer used there once handed back.
+unsafe impl Send for Objects {}
+
+impl Objects {
+    /// Returns whether there are no objects.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Schedules a task to be executed on a worker thread.
+///
+/// The task is passed `Ok(())` when it runs on a worker, which then wakes the
+/// JS thread if it is waiting for its result. If the task cannot be handed to
+/// the pool, it is run on the calling thread with the error instead, so that
+/// it can report it.
+pub fn schedule<F>(task: F)
+where
+    F: FnOnce(anyhow::Result<()>) + Send + 'static,
+{
+    let pool = POOL.get_or_init(|| {
+        let max_threads = thread::available_parallelism().map_or(4, |n| n.get() as u32);
+        glib::ThreadPool::shared(Some(max_threads)).expect("Failed to create worker thread pool")
+    });
+
+    let task = Arc::new(Mutex::new(Some(task)));
+    let worker_task = task.clone();
+
+    let pushed = pool.push(move || {
+        if let Some(task) = worker_task.lock().unwrap().take() {
+            task(Ok(()));
+        }
+
+        notify::JS_THREAD.notify();
+    });
+
+    if let Err(err) = pushed
+        && let Some(task) = task.lock().unwrap().take()
+    {
+        task(Err(anyhow::anyhow!(
+            "Failed to run the call on a worker thread: {err}"
+        )));
+    }
+}
+
+/// Checks that arguments can be passed to a worker call.
+///
+/// Callbacks are invoked on the thread that calls them, so they cannot be
+/// passed to a function running on a worker.
+pub fn check_args(args: &[Arg]) -> anyhow::Result<()> {
+    if args
+        .iter()
+        .any(|arg| matches!(arg.type_, Type::Callback(_)))
+    {
+        bail!("Callbacks cannot be passed to a call on a worker thread");
+    }
+
+    Ok(())
+}
+
+/// Returns whether any of the arguments refers to an object.
+pub fn has_objects(args: &[Arg]) -> bool {
+    !object_ids(args).is_empty()
+}
+
+/// Takes references to the objects in the arguments to lend them to a
+/// worker. Must be called on the GTK thread.
+///
+/// # Errors
+///
+/// Returns an error if an object has been garbage collected or is of a type
+/// that is not thread-safe.
+pub fn lend(args: &[Arg]) -> anyhow::Result<Objects> {
+    GtkThreadState::with(|state| {
+        let objects = object_ids(args)
+            .into_iter()
+            .map(|object_id| {
+                let Some(object) = state.object_map.get(&object_id.0) else {
+                    bail!("Object has been garbage collected");
+                };
+
+                if !is_thread_safe(object) {
+                    bail!(
+                        "Objects of type {} cannot be passed to a call on a worker thread",
+                        object_type(object).map_or("unknown", |type_| type_.name())
+                    );
+                }
+
+                Ok((object_id.0, object.clone()))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Objects(objects))
+    })
+}
+
+/// Runs `f` on a worker with the lent objects tracked under their IDs.
+///
+/// Returns the result of `f` along with the objects it created, which must be
+/// adopted on the GTK thread before they reach JavaScript.
+pub fn with_lent<R>(lent: Objects, f: impl FnOnce() -> R) -> (R, Objects) {
+    let lent_ids: Vec<usize> = lent.0.iter().map(|(id, _)| *id).collect();
+
+    GtkThreadState::with(|state| {
+        // Objects created by `f` must not take the IDs of lent objects.
+        if let Some(max_id) = lent_ids.iter().max() {
+            state.next_object_id = state.next_object_id.max(max_id + 1);
+        }
+
+        state.object_map.extend(lent.0);
+    });
+
+    let result = f();
+
+    let created = GtkThreadState::with(|state| {
+        for id in &lent_ids {
+            state.object_map.remove(id);
+        }
+
+        state.object_map.drain().collect()
+    });
+
+    (result, Objects(created))
+}
+
+/// Tracks objects created on a worker on the GTK thread, returning their new
+/// IDs by their IDs on the worker.
+pub fn adopt(objects: Objects) -> HashMap<usize, ObjectId> {
+    objects
+        .0
+        .into_iter()
+        .map(|(id, object)| (id, ObjectId::new(object)))
+        .collect()
+}
+
+/// Replaces worker object IDs in a value with their adopted IDs.
+pub fn remap_object_ids(value: &mut Value, adopted: &HashMap<usize, ObjectId>) {
+    match value {
+        Value::Object(object_id) => {
+            if let Some(adopted_id) = adopted.get(&object_id.0) {
+                *object_id = *adopted_id;
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                remap_object_ids(value, adopted);
+            }
+        }
+        Value::HashTable(entries) => {
+            for (key, value) in entries {
+                remap_object_ids(key, adopted);
+                remap_object_ids(value, adopted);
+            }
+        }
+        Value::Struct(fields) => {
+            for (_, value) in fields {
+                remap_object_ids(value, adopted);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn object_ids(args: &[Arg]) -> Vec<ObjectId> {
+    let mut object_ids = Vec::new();
+
+    for arg in args {
+        arg.value.collect_object_ids(&mut object_ids);
+    }
+
+    object_ids
+}
+
+fn object_type(object: &Object) -> Option<glib::Type> {
+    match object {
+        Object::GObject(obj) => Some(obj.type_()),
+        Object::Boxed(boxed) => boxed.type_(),
+    }
+}
+
+fn is_thread_safe(object: &Object) -> bool {
+    let Some(type_) = object_type(object) else {
+        return false;
+    };
+
+    THREAD_SAFE_TYPES
+        .iter()
+        .filter_map(|name| glib::Type::from_name(*name))
+        .any(|thread_safe_type| type_.is_a(thread_safe_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boxed::Boxed, test_utils};
+    use std::ffi::c_void;
+
+    #[test]
+    fn remap_object_ids_replaces_nested_ids() {
+        let adopted = HashMap::from([(1, ObjectId(10)), (2, ObjectId(20))]);
+        let mut value = Value::Array(vec![
+            Value::Object(ObjectId(1)),
+            Value::Struct(vec![("field".to_string(), Value::Object(ObjectId(2)))]),
+            Value::Object(ObjectId(3)),
+        ]);
+
+        remap_object_ids(&mut value, &adopted);
+
+        let Value::Array(values) = value else {
+            panic!("Expected an array");
+        };
+
+        assert!(matches!(values[0], Value::Object(ObjectId(10))));
+        assert!(
+            matches!(&values[1], Value::Struct(fields) if matches!(fields[0].1, Value::Object(ObjectId(20))))
+        );
+        assert!(matches!(values[2], Value::Object(ObjectId(3))));
+    }
+
+    #[test]
+    fn is_thread_safe_accepts_listed_types_and_subtypes() {
+        test_utils::ensure_gtk_init();
+
+        let file = gtk4::gio::File::for_path("/tmp");
+        let bytes = glib::Bytes::from_static(b"data");
+
+        assert!(is_thread_safe(&Object::GObject(file.upcast())));
+        assert!(is_thread_safe(&Object::Boxed(Boxed::from_glib_none(
+            Some(glib::Bytes::static_type()),
+            bytes.as_ptr() as *mut c_void,
+        ))));
+    }
+
+    #[test]
+    fn is_thread_safe_rejects_other_types() {
+        test_utils::ensure_gtk_init();
+
+        let label = gtk4::Label::new(None);
+
+        assert!(!is_thread_safe(&Object::GObject(label.upcast())));
+    }
+}
@@ -1,6 +1,6 @@
 import { describe, expect, it } from "vitest";
-import { batchCall, call } from "../index.js";
-import { GTK_LIB } from "./utils.js";
+import { batchCall, call, getObjectId } from "../index.js";
+import { GTK_LIB, getRefCount } from "./utils.js";
 
 describe("batchCall", () => {
     it("executes multiple void calls in a single dispatch", () => {
@@ -132,4 +132,113 @@ describe("batchCall", () => {
         expect(text1).toBe("Updated 1");
         expect(text2).toBe("Updated 2");
     });
+
+    it("returns the results of calls that declare a return type", () => {
+        const [label, text] = batchCall([
+            {
+                library: GTK_LIB,
+                symbol: "gtk_label_new",
+                args: [{ type: { type: "string" }, value: "Created" }],
+                returnType: { type: "gobject" },
+            },
+            {
+                library: GTK_LIB,
+                symbol: "gtk_label_get_text",
+                args: [{ type: { type: "gobject", borrowed: true }, value: { resultOf: 0 } }],
+                returnType: { type: "string", borrowed: true },
+            },
+        ]);
+
+        expect(label).toBeDefined();
+        expect(text).toBe("Created");
+    });
+
+    it("passes earlier results to later calls", () => {
+        const [box, label] = batchCall([
+            {
+                library: GTK_LIB,
+                symbol: "gtk_box_new",
+                args: [
+                    { type: { type: "int", size: 32 }, value: 0 },
+                    { type: { type: "int", size: 32 }, value: 0 },
+                ],
+                returnType: { type: "gobject" },
+            },
+            {
+                library: GTK_LIB,
+                symbol: "gtk_label_new",
+                args: [{ type: { type: "string" }, value: "Child" }],
+                returnType: { type: "gobject" },
+            },
+            {
+                library: GTK_LIB,
+                symbol: "gtk_box_append",
+                args: [
+                    { type: { type: "gobject", borrowed: true }, value: { resultOf: 0 } },
+                    { type: { type: "gobject", borrowed: true }, value: { resultOf: 1 } },
+                ],
+            },
+        ]);
+
+        const parent = call(
+            GTK_LIB,
+            "gtk_widget_get_parent",
+            [{ type: { type: "gobject", borrowed: true }, value: label }],
+            { type: "gobject", borrowed: true },
+        );
+
+        expect(getObjectId(parent)).toBe(getObjectId(box));
+    });
+
+    it("returns undefined for void calls", () => {
+        const label = call(GTK_LIB, "gtk_label_new", [{ type: { type: "string" }, value: "Test" }], {
+            type: "gobject",
+        });
+
+        const results = batchCall([
+            {
+                library: GTK_LIB,
+                symbol: "gtk_label_set_text",
+                args: [
+                    { type: { type: "gobject" }, value: label },
+                    { type: { type: "string" }, value: "Void" },
+                ],
+            },
+        ]);
+
+        expect(results).toEqual([undefined]);
+    });
+
+    it("releases objects nested in earlier results when a call fails", () => {
+        const window = call(GTK_LIB, "gtk_window_new", [], { type: "gobject" });
+        const initialRefCount = getRefCount(window);
+
+        expect(() =>
+            batchCall([
+                {
+                    library: GTK_LIB,
+                    symbol: "gtk_window_list_toplevels",
+                    args: [],
+                    returnType: { type: "array", itemType: { type: "gobject", borrowed: true }, listType: "glist" },
+                },
+                { library: GTK_LIB, symbol: "nonexistent_symbol", args: [] },
+            ]),
+        ).toThrow();
+
+        expect(getRefCount(window)).toBe(initialRefCount);
+        call(GTK_LIB, "gtk_window_destroy", [{ type: { type: "gobject" }, value: window }], { type: "undefined" });
+    });
+
+    it("rejects references to later calls", () => {
+        expect(() =>
+            batchCall([
+                {
+                    library: GTK_LIB,
+                    symbol: "gtk_label_get_text",
+                    args: [{ type: { type: "gobject", borrowed: true }, value: { resultOf: 0 } }],
+                    returnType: { type: "string", borrowed: true },
+                },
+            ]),
+        ).toThrow(RangeError);
+    });
 });
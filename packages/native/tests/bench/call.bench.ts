@@ -0,0 +1,54 @@
+import { bench, describe } from "vitest";
+import { batchCall, call } from "../../index.js";
+import {
+    createLabel,
+    GLIB_LIB,
+    GOBJECT_BORROWED,
+    GTK_LIB,
+    INT8,
+    INT64,
+    STRING,
+    UINT32,
+    UNDEFINED,
+} from "../utils.js";
+
+describe("call overhead", () => {
+    bench("no arguments", () => {
+        call(GLIB_LIB, "g_get_monotonic_time", [], INT64);
+    });
+
+    bench("integer argument", () => {
+        call(GLIB_LIB, "g_ascii_tolower", [{ type: INT8, value: 65 }], INT8);
+    });
+
+    bench("string argument", () => {
+        call(GLIB_LIB, "g_str_hash", [{ type: STRING, value: "hello" }], UINT32);
+    });
+
+    const label = createLabel();
+
+    bench("gobject method", () => {
+        call(
+            GTK_LIB,
+            "gtk_label_set_text",
+            [
+                { type: GOBJECT_BORROWED, value: label },
+                { type: STRING, value: "Hello" },
+            ],
+            UNDEFINED,
+        );
+    });
+
+    const calls = Array.from({ length: 100 }, (_, i) => ({
+        library: GTK_LIB,
+        symbol: "gtk_label_set_text",
+        args: [
+            { type: GOBJECT_BORROWED, value: label },
+            { type: STRING, value: `Label ${i}` },
+        ],
+    }));
+
+    bench("batch of 100 gobject methods", () => {
+        batchCall(calls);
+    });
+});
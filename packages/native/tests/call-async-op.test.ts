@@ -0,0 +1,142 @@
+import { writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { describe, expect, it } from "vitest";
+import { call, callAsyncOp, createRef } from "../index.js";
+import { BOOLEAN, GIO_LIB, GOBJECT, INT32, NULL, STRING, STRING_BORROWED, UINT32, UINT64, UNDEFINED } from "./utils.js";
+
+const G_IO_ERROR_CANCELLED = 19;
+
+function spawn(argv: string[]): unknown {
+    return call(
+        GIO_LIB,
+        "g_subprocess_newv",
+        [
+            { type: { type: "array", itemType: STRING }, value: argv },
+            { type: UINT32, value: 0 },
+            { type: { type: "error" }, value: null },
+        ],
+        GOBJECT,
+    );
+}
+
+function waitCheck(subprocess: unknown, signal: AbortSignal | null = null): Promise<unknown> {
+    return callAsyncOp(
+        GIO_LIB,
+        "g_subprocess_wait_check_async",
+        "g_subprocess_wait_check_finish",
+        [
+            { type: GOBJECT, value: subprocess },
+            { type: { type: "cancellable" }, value: signal },
+        ],
+        BOOLEAN,
+    );
+}
+
+describe("callAsyncOp", () => {
+    it("resolves with the result of the finish function", async () => {
+        await expect(waitCheck(spawn(["true"]))).resolves.toBe(true);
+    });
+
+    it("rejects with the GError reported by the finish function", async () => {
+        await expect(waitCheck(spawn(["false"]))).rejects.toMatchObject({
+            domain: "g-spawn-exit-error-quark",
+            code: 1,
+        });
+    });
+
+    it("rejects when the operation is cancelled", async () => {
+        const subprocess = spawn(["sleep", "10"]);
+        const controller = new AbortController();
+
+        const result = waitCheck(subprocess, controller.signal);
+        controller.abort();
+
+        await expect(result).rejects.toMatchObject({
+            domain: "g-io-error-quark",
+            code: G_IO_ERROR_CANCELLED,
+        });
+        call(GIO_LIB, "g_subprocess_force_exit", [{ type: GOBJECT, value: subprocess }], UNDEFINED);
+    });
+
+    it("resolves with objects returned by the finish function", async () => {
+        const path = join(tmpdir(), "gtkx-call-async-op.txt");
+        writeFileSync(path, "contents");
+
+        const file = call(GIO_LIB, "g_file_new_for_path", [{ type: STRING, value: path }], GOBJECT);
+        const info = await callAsyncOp(
+            GIO_LIB,
+            "g_file_query_info_async",
+            "g_file_query_info_finish",
+            [
+                { type: GOBJECT, value: file },
+                { type: STRING, value: "standard::name" },
+                { type: UINT32, value: 0 },
+                { type: INT32, value: 0 },
+                { type: { type: "cancellable" }, value: null },
+            ],
+            GOBJECT,
+        );
+
+        const name = call(GIO_LIB, "g_file_info_get_name", [{ type: GOBJECT, value: info }], STRING_BORROWED);
+        expect(name).toBe("gtkx-call-async-op.txt");
+    });
+
+    it("calls constructor-style finish functions without the source object", async () => {
+        const subprocess = spawn(["true"]);
+
+        // The task of g_subprocess_wait_async has the subprocess as its source,
+        // while g_task_propagate_boolean takes the result first.
+        const result = callAsyncOp(
+            GIO_LIB,
+            "g_subprocess_wait_async",
+            "g_task_propagate_boolean",
+            [
+                { type: GOBJECT, value: subprocess },
+                { type: { type: "cancellable" }, value: null },
+            ],
+            BOOLEAN,
+            { source: false },
+        );
+
+        await expect(result).resolves.toBe(true);
+    });
+
+    it("updates out-parameters of the finish function", async () => {
+        const path = join(tmpdir(), "gtkx-call-async-op-contents.txt");
+        writeFileSync(path, "loaded contents");
+
+        const file = call(GIO_LIB, "g_file_new_for_path", [{ type: STRING, value: path }], GOBJECT);
+        const contentsRef = createRef(null);
+        const lengthRef = createRef(0);
+
+        const ok = await callAsyncOp(
+            GIO_LIB,
+            "g_file_load_contents_async",
+            "g_file_load_contents_finish",
+            [
+                { type: GOBJECT, value: file },
+                { type: { type: "cancellable" }, value: null },
+            ],
+            BOOLEAN,
+            {
+                args: [
+                    {
+                        type: { type: "ref", innerType: { type: "bytes", kind: "pointer", lengthArg: 3 } },
+                        value: contentsRef,
+                    },
+                    { type: { type: "ref", innerType: UINT64 }, value: lengthRef },
+                    { type: NULL, value: null },
+                ],
+            },
+        );
+
+        expect(ok).toBe(true);
+        expect(lengthRef.value).toBe(15);
+        expect((contentsRef.value as unknown as Buffer).toString()).toBe("loaded contents");
+    });
+
+    it("rejects when the start function does not exist", async () => {
+        await expect(callAsyncOp(GIO_LIB, "nonexistent_async", "nonexistent_finish", [], UNDEFINED)).rejects.toThrow();
+    });
+});
@@ -0,0 +1,123 @@
+import { describe, expect, it, vi } from "vitest";
+import { batchCallAsync, callAsync } from "../index.js";
+import {
+    BOOLEAN,
+    connectSignal,
+    createCancellable,
+    createRef,
+    GIO_LIB,
+    GLIB_LIB,
+    GOBJECT_BORROWED,
+    GTK_LIB,
+    INT32,
+    INT64,
+    STRING,
+    UINT64,
+    UNDEFINED,
+} from "./utils.js";
+
+describe("callAsync", () => {
+    it("resolves with the return value", async () => {
+        const result = await callAsync(
+            GLIB_LIB,
+            "g_ascii_strup",
+            [
+                { type: STRING, value: "hello" },
+                { type: INT64, value: -1 },
+            ],
+            STRING,
+        );
+
+        expect(result).toBe("HELLO");
+    });
+
+    it("does not block the JavaScript thread", async () => {
+        let timerFired = false;
+        setTimeout(() => {
+            timerFired = true;
+        }, 10);
+
+        const pending = callAsync(GLIB_LIB, "g_usleep", [{ type: UINT64, value: 200_000 }], UNDEFINED);
+        await new Promise((resolve) => setTimeout(resolve, 50));
+
+        expect(timerFired).toBe(true);
+        await pending;
+    });
+
+    it("updates refs before resolving", async () => {
+        const out = createRef(0);
+
+        const result = await callAsync(
+            GLIB_LIB,
+            "g_ascii_string_to_unsigned",
+            [
+                { type: STRING, value: "42" },
+                { type: INT32, value: 10 },
+                { type: UINT64, value: 0 },
+                { type: UINT64, value: 100 },
+                { type: { type: "ref", innerType: UINT64 }, value: out },
+                { type: { type: "error" }, value: null },
+            ],
+            BOOLEAN,
+        );
+
+        expect(result).toBe(true);
+        expect(out.value).toBe(42);
+    });
+
+    it("rejects with the GError reported by the function", async () => {
+        const pending = callAsync(
+            GLIB_LIB,
+            "g_file_get_contents",
+            [
+                { type: STRING, value: "/nonexistent/path/for/gtkx" },
+                { type: { type: "ref", innerType: STRING }, value: createRef(null) },
+                { type: { type: "ref", innerType: UINT64 }, value: createRef(0) },
+                { type: { type: "error" }, value: null },
+            ],
+            BOOLEAN,
+        );
+
+        await expect(pending).rejects.toMatchObject({ domain: "g-file-error-quark" });
+    });
+
+    it("rejects when the symbol does not exist", async () => {
+        await expect(callAsync(GLIB_LIB, "g_nonexistent_function_12345", [], UNDEFINED)).rejects.toThrow();
+    });
+
+    it("services callbacks fired during the call", async () => {
+        const cancellable = createCancellable();
+        const handler = vi.fn();
+        connectSignal(cancellable, "cancelled", handler);
+
+        await callAsync(GIO_LIB, "g_cancellable_cancel", [{ type: GOBJECT_BORROWED, value: cancellable }], UNDEFINED);
+
+        await vi.waitFor(() => expect(handler).toHaveBeenCalledTimes(1));
+    });
+});
+
+describe("batchCallAsync", () => {
+    it("resolves with the results of the batch", async () => {
+        const [label, text] = await batchCallAsync([
+            {
+                library: GTK_LIB,
+                symbol: "gtk_label_new",
+                args: [{ type: STRING, value: "Async" }],
+                returnType: { type: "gobject" },
+            },
+            {
+                library: GTK_LIB,
+                symbol: "gtk_label_get_text",
+                args: [{ type: GOBJECT_BORROWED, value: { resultOf: 0 } }],
+                returnType: { type: "string", borrowed: true },
+            },
+        ]);
+
+        expect(label).toBeDefined();
+        expect(text).toBe("Async");
+    });
+
+    it("resolves with an empty array for an empty batch", async () => {
+        await expect(batchCallAsync([])).resolves.toEqual([]);
+    });
+});
@@ -1,19 +1,40 @@
 import { describe, expect, it } from "vitest";
 import { call } from "../../index.js";
+import type { Type } from "../../types.js";
 import {
+    BOOLEAN,
     createLabel,
+    createRef,
     forceGC,
+    GLIB_LIB,
     GOBJECT,
     GOBJECT_BORROWED,
     GTK_LIB,
     getRefCount,
+    INT32,
     STRING,
+    STRING_BORROWED,
     startMemoryMeasurement,
+    UINT8,
+    UINT32,
+    UINT64,
     UNDEFINED,
 } from "../utils.js";
 
 const STRING_ARRAY = { type: "array" as const, itemType: { type: "string" as const } };
 
+const getFixedArray = (values: number[], returnType: Type) =>
+    call(
+        GLIB_LIB,
+        "g_variant_get_fixed_array",
+        [
+            { type: { type: "variant", signature: "ai" }, value: values },
+            { type: { type: "ref", innerType: UINT64 }, value: createRef(0) },
+            { type: UINT64, value: 4 },
+        ],
+        returnType,
+    );
+
 describe("call - array types", () => {
     describe("string arrays", () => {
         it("passes string array argument", () => {
@@ -427,4 +448,114 @@ describe("call - array types", () => {
             expect(result).toContain("valid-class");
         });
     });
+
+    describe("GLib arrays", () => {
+        const refArray = (symbol: string, arrayType: Type, value: unknown[]) =>
+            call(GLIB_LIB, symbol, [{ type: { ...arrayType, borrowed: true } as Type, value }], arrayType);
+
+        it("round-trips a GPtrArray of strings", () => {
+            const type: Type = { type: "array", itemType: STRING_BORROWED, listType: "gptrarray" };
+
+            expect(refArray("g_ptr_array_ref", type, ["a", "b", "c"])).toEqual(["a", "b", "c"]);
+        });
+
+        it("round-trips a GPtrArray of objects", () => {
+            const label = createLabel("Test");
+            const type: Type = { type: "array", itemType: GOBJECT_BORROWED, listType: "gptrarray" };
+
+            const result = refArray("g_ptr_array_ref", type, [label]) as unknown[];
+
+            expect(result).toHaveLength(1);
+            expect(getRefCount(result[0])).toBeGreaterThanOrEqual(1);
+        });
+
+        it("round-trips a GArray of inline items", () => {
+            const type: Type = { type: "array", itemType: INT32, listType: "garray" };
+
+            expect(refArray("g_array_ref", type, [1, -2, 3])).toEqual([1, -2, 3]);
+        });
+
+        it("round-trips a GByteArray", () => {
+            const type: Type = { type: "array", itemType: UINT8, listType: "gbytearray" };
+
+            expect(refArray("g_byte_array_ref", type, [0, 127, 255])).toEqual([0, 127, 255]);
+        });
+
+        it("returns an empty array for an empty GPtrArray", () => {
+            const type: Type = { type: "array", itemType: STRING_BORROWED, listType: "gptrarray" };
+
+            expect(refArray("g_ptr_array_ref", type, [])).toEqual([]);
+        });
+
+        it("rejects item types the list type cannot store", () => {
+            const type: Type = { type: "array", itemType: { type: "variant" }, listType: "gptrarray" };
+
+            expect(() => refArray("g_ptr_array_ref", type, [])).toThrow();
+        });
+    });
+
+    describe("sized arrays", () => {
+        it("reads the length from an out-parameter", () => {
+            const result = getFixedArray([1, -2, 3], { type: "array", itemType: INT32, lengthArg: 1, borrowed: true });
+
+            expect(result).toEqual([1, -2, 3]);
+        });
+
+        it("reads a fixed number of items", () => {
+            const result = getFixedArray([1, 2, 3], { type: "array", itemType: INT32, length: 2, borrowed: true });
+
+            expect(result).toEqual([1, 2]);
+        });
+
+        it("returns an empty array for a zero length", () => {
+            const result = getFixedArray([], { type: "array", itemType: INT32, lengthArg: 1, borrowed: true });
+
+            expect(result).toEqual([]);
+        });
+
+        it("reads string items without a terminator", () => {
+            const length = createRef(0);
+
+            const result = call(
+                GLIB_LIB,
+                "g_variant_get_strv",
+                [
+                    { type: { type: "variant", signature: "as" }, value: ["a", "b", "c"] },
+                    { type: { type: "ref", innerType: UINT64 }, value: length },
+                ],
+                { type: "array", itemType: STRING_BORROWED, lengthArg: 1 },
+            );
+
+            expect(result).toEqual(["a", "b", "c"]);
+            expect(length.value).toBe(3);
+        });
+
+        it("reads a caller-allocated buffer with the length from the return value", () => {
+            const decomposed = createRef([0, 0, 0, 0]);
+
+            const length = call(
+                GLIB_LIB,
+                "g_unichar_fully_decompose",
+                [
+                    { type: UINT32, value: 0xe9 },
+                    { type: BOOLEAN, value: false },
+                    {
+                        type: { type: "ref", innerType: { type: "array", itemType: UINT32, lengthArg: "return" } },
+                        value: decomposed,
+                    },
+                    { type: UINT64, value: 4 },
+                ],
+                UINT64,
+            );
+
+            expect(length).toBe(2);
+            expect(decomposed.value).toEqual([0x65, 0x301]);
+        });
+
+        it("rejects item types that cannot be stored inline", () => {
+            expect(() =>
+                getFixedArray([1], { type: "array", itemType: { type: "variant" }, lengthArg: 1, borrowed: true }),
+            ).toThrow();
+        });
+    });
 });
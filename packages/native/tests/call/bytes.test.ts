@@ -0,0 +1,159 @@
+import { mkdtempSync, rmSync, writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { afterAll, describe, expect, it } from "vitest";
+import { call } from "../../index.js";
+import type { Type } from "../../types.js";
+import { BOOLEAN, createRef, GIO_LIB, GLIB_LIB, INT32, NULL, STRING, UINT64 } from "../utils.js";
+
+const GBYTES: Type = { type: "bytes" };
+const GBYTES_BORROWED: Type = { type: "bytes", borrowed: true };
+
+const newBytes = (data: Uint8Array) =>
+    call(
+        GLIB_LIB,
+        "g_bytes_new",
+        [
+            { type: { type: "bytes", kind: "pointer", borrowed: true }, value: data },
+            { type: UINT64, value: data.length },
+        ],
+        GBYTES,
+    );
+
+describe("call - bytes types", () => {
+    const tempDir = mkdtempSync(join(tmpdir(), "gtkx-bytes-"));
+
+    afterAll(() => {
+        rmSync(tempDir, { recursive: true, force: true });
+    });
+
+    it("returns GBytes as a Buffer", () => {
+        const result = newBytes(new Uint8Array([1, 2, 3, 255]));
+
+        expect(Buffer.isBuffer(result)).toBe(true);
+        expect([...(result as Buffer)]).toEqual([1, 2, 3, 255]);
+    });
+
+    it("passes a Buffer as GBytes", () => {
+        const size = call(
+            GLIB_LIB,
+            "g_bytes_get_size",
+            [{ type: GBYTES_BORROWED, value: Buffer.from("hello") }],
+            UINT64,
+        );
+
+        expect(size).toBe(5);
+    });
+
+    it("accepts ArrayBuffers and typed arrays alike", () => {
+        const data = new Uint8Array([9, 8, 7]);
+
+        const equal = call(
+            GLIB_LIB,
+            "g_bytes_equal",
+            [
+                { type: GBYTES_BORROWED, value: data },
+                { type: GBYTES_BORROWED, value: data.buffer },
+            ],
+            BOOLEAN,
+        );
+
+        expect(equal).toBe(true);
+    });
+
+    it("returns empty GBytes as an empty Buffer", () => {
+        const result = newBytes(new Uint8Array());
+
+        expect(Buffer.isBuffer(result)).toBe(true);
+        expect((result as Buffer).length).toBe(0);
+    });
+
+    it("returns a writable copy of GBytes data", () => {
+        const result = newBytes(new Uint8Array([1, 2, 3])) as Buffer;
+
+        result[0] = 42;
+        result.fill(7, 1);
+
+        expect([...result]).toEqual([42, 7, 7]);
+    });
+
+    it("copies read-only resource data into a writable Buffer", () => {
+        const dir = "/org/gtk/libgtk/theme/Default/";
+        const children = call(
+            GIO_LIB,
+            "g_resources_enumerate_children",
+            [
+                { type: STRING, value: dir },
+                { type: INT32, value: 0 },
+                { type: { type: "error" }, value: null },
+            ],
+            { type: "array", itemType: STRING },
+        ) as string[];
+        const name = children.find((child) => child.endsWith(".css"));
+        expect(name).toBeDefined();
+
+        const lookup = () =>
+            call(
+                GIO_LIB,
+                "g_resources_lookup_data",
+                [
+                    { type: STRING, value: `${dir}${name}` },
+                    { type: INT32, value: 0 },
+                    { type: { type: "error" }, value: null },
+                ],
+                GBYTES,
+            ) as Buffer;
+
+        const data = lookup();
+        const original = Buffer.from(data);
+        data.fill(0);
+
+        expect(lookup().equals(original)).toBe(true);
+    });
+
+    it("reads a borrowed pointer sized by an out-parameter", () => {
+        const bytes = newBytes(new Uint8Array([4, 5, 6]));
+        const sizeRef = createRef(0);
+
+        const result = call(
+            GLIB_LIB,
+            "g_bytes_get_data",
+            [
+                { type: GBYTES_BORROWED, value: bytes },
+                { type: { type: "ref", innerType: UINT64 }, value: sizeRef },
+            ],
+            { type: "bytes", kind: "pointer", borrowed: true, lengthArg: 1 },
+        );
+
+        expect(sizeRef.value).toBe(3);
+        expect([...(result as Buffer)]).toEqual([4, 5, 6]);
+    });
+
+    it("reads file contents from a pointer out-parameter", () => {
+        const path = join(tempDir, "contents.bin");
+        const contents = Buffer.alloc(1024 * 1024, 0xab);
+        writeFileSync(path, contents);
+
+        const contentsRef = createRef(null);
+        const lengthRef = createRef(0);
+
+        const ok = call(
+            GLIB_LIB,
+            "g_file_get_contents",
+            [
+                { type: STRING, value: path },
+                {
+                    type: { type: "ref", innerType: { type: "bytes", kind: "pointer", lengthArg: 2 } },
+                    value: contentsRef,
+                },
+                { type: { type: "ref", innerType: UINT64 }, value: lengthRef },
+                { type: NULL, value: null },
+            ],
+            BOOLEAN,
+        );
+
+        expect(ok).toBe(true);
+        expect(lengthRef.value).toBe(contents.length);
+        expect((contentsRef.value as unknown as Buffer).equals(contents)).toBe(true);
+    });
+});
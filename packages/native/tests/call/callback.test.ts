@@ -1,4 +1,4 @@
-import { describe, expect, it } from "vitest";
+import { describe, expect, it, vi } from "vitest";
 import { call } from "../../index.js";
 import {
     BOOLEAN,
@@ -12,6 +12,7 @@ import {
     GOBJECT_BORROWED,
     GOBJECT_LIB,
     getRefCount,
+    GTK_LIB,
     INT32,
     NULL,
     STRING,
@@ -177,6 +178,79 @@ describe("call - callback types", () => {
         });
     });
 
+    describe("async closures", () => {
+        it("delivers signal arguments without blocking the emission", async () => {
+            const cancellable = createCancellable();
+            const handler = vi.fn();
+
+            call(
+                GOBJECT_LIB,
+                "g_signal_connect_closure",
+                [
+                    { type: GOBJECT, value: cancellable },
+                    { type: STRING, value: "cancelled" },
+                    {
+                        type: { type: "callback", trampoline: "closure", argTypes: [GOBJECT_BORROWED], async: true },
+                        value: handler,
+                    },
+                    { type: BOOLEAN, value: false },
+                ],
+                UINT64,
+            );
+
+            call(GIO_LIB, "g_cancellable_cancel", [{ type: GOBJECT, value: cancellable }], UNDEFINED);
+
+            await vi.waitFor(() => expect(handler).toHaveBeenCalledTimes(1));
+            expect(handler.mock.calls[0]?.[0]).toBeDefined();
+        });
+
+        it("delivers emissions in order", async () => {
+            const button = createButton("Test");
+            const order: number[] = [];
+            let next = 0;
+
+            call(
+                GOBJECT_LIB,
+                "g_signal_connect_data",
+                [
+                    { type: GOBJECT, value: button },
+                    { type: STRING, value: "clicked" },
+                    { type: { type: "callback", async: true }, value: () => order.push(next++) },
+                    { type: NULL, value: null },
+                    { type: NULL, value: null },
+                    { type: INT32, value: 0 },
+                ],
+                UINT64,
+            );
+
+            for (let i = 0; i < 3; i++) {
+                call(GTK_LIB, "gtk_button_clicked", [{ type: GOBJECT_BORROWED, value: button }], UNDEFINED);
+            }
+
+            await vi.waitFor(() => expect(order).toEqual([0, 1, 2]));
+        });
+
+        it("throws when an async callback returns a value", () => {
+            const button = createButton("Test");
+
+            expect(() =>
+                call(
+                    GOBJECT_LIB,
+                    "g_signal_connect_data",
+                    [
+                        { type: GOBJECT, value: button },
+                        { type: STRING, value: "clicked" },
+                        { type: { type: "callback", returnType: BOOLEAN, async: true }, value: () => true },
+                        { type: NULL, value: null },
+                        { type: NULL, value: null },
+                        { type: INT32, value: 0 },
+                    ],
+                    UINT64,
+                ),
+            ).toThrow(TypeError);
+        });
+    });
+
     describe("destroy trampoline", () => {
         it("registers destroy notify callback", () => {
             const button = createButton("Test");
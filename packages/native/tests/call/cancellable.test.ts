@@ -0,0 +1,121 @@
+import { getEventListeners } from "node:events";
+import { describe, expect, it } from "vitest";
+import { call } from "../../index.js";
+import { BOOLEAN, GIO_LIB, GOBJECT, GOBJECT_BORROWED, STRING, UNDEFINED } from "../utils.js";
+
+const CANCELLABLE = { type: "cancellable" as const };
+const ASYNC_READY = {
+    type: "callback" as const,
+    trampoline: "asyncReady" as const,
+    sourceType: GOBJECT_BORROWED,
+    resultType: GOBJECT_BORROWED,
+};
+const G_IO_ERROR_CANCELLED = 19;
+
+function spawnSleep(): unknown {
+    return call(
+        GIO_LIB,
+        "g_subprocess_newv",
+        [
+            { type: { type: "array", itemType: STRING }, value: ["sleep", "10"] },
+            { type: { type: "int", size: 32, unsigned: true }, value: 0 },
+            { type: { type: "error" }, value: null },
+        ],
+        GOBJECT,
+    );
+}
+
+function waitForExit(subprocess: unknown, signal: AbortSignal | null): Promise<unknown> {
+    return new Promise((resolve) => {
+        call(
+            GIO_LIB,
+            "g_subprocess_wait_async",
+            [
+                { type: GOBJECT, value: subprocess },
+                { type: CANCELLABLE, value: signal },
+                {
+                    type: ASYNC_READY,
+                    value: (_source: unknown, result: unknown) => {
+                        try {
+                            resolve(
+                                call(
+                                    GIO_LIB,
+                                    "g_subprocess_wait_finish",
+                                    [
+                                        { type: GOBJECT, value: subprocess },
+                                        { type: GOBJECT, value: result },
+                                        { type: { type: "error" }, value: null },
+                                    ],
+                                    BOOLEAN,
+                                ),
+                            );
+                        } catch (error) {
+                            resolve(error);
+                        }
+                    },
+                },
+            ],
+            UNDEFINED,
+        );
+    });
+}
+
+function forceExit(subprocess: unknown): void {
+    call(GIO_LIB, "g_subprocess_force_exit", [{ type: GOBJECT, value: subprocess }], UNDEFINED);
+}
+
+describe("call - cancellable type", () => {
+    it("cancels an async operation when the signal aborts", async () => {
+        const subprocess = spawnSleep();
+        const controller = new AbortController();
+
+        const result = waitForExit(subprocess, controller.signal);
+        controller.abort();
+
+        expect(await result).toMatchObject({ domain: "g-io-error-quark", code: G_IO_ERROR_CANCELLED });
+        forceExit(subprocess);
+    });
+
+    it("starts the operation cancelled with an already aborted signal", async () => {
+        const subprocess = spawnSleep();
+
+        const result = waitForExit(subprocess, AbortSignal.abort());
+
+        expect(await result).toMatchObject({ code: G_IO_ERROR_CANCELLED });
+        forceExit(subprocess);
+    });
+
+    it("completes normally when the signal is not aborted", async () => {
+        const subprocess = spawnSleep();
+        const controller = new AbortController();
+
+        const result = waitForExit(subprocess, controller.signal);
+        forceExit(subprocess);
+
+        expect(await result).toBe(true);
+    });
+
+    it("accepts null for no cancellable", async () => {
+        const subprocess = spawnSleep();
+
+        const result = waitForExit(subprocess, null);
+        forceExit(subprocess);
+
+        expect(await result).toBe(true);
+    });
+
+    it("leaves no abort listener behind when the call fails", () => {
+        const controller = new AbortController();
+
+        expect(() =>
+            call(GIO_LIB, "nonexistent_symbol", [{ type: CANCELLABLE, value: controller.signal }], UNDEFINED),
+        ).toThrow();
+        expect(getEventListeners(controller.signal, "abort")).toHaveLength(0);
+    });
+
+    it("rejects values other than an AbortSignal", () => {
+        expect(() =>
+            call(GIO_LIB, "g_cancellable_is_cancelled", [{ type: CANCELLABLE, value: "signal" }], BOOLEAN),
+        ).toThrow();
+    });
+});
@@ -0,0 +1,100 @@
+import { describe, expect, it } from "vitest";
+import { call } from "../../index.js";
+import type { Type } from "../../types.js";
+import { BOOLEAN, createLabel, GOBJECT_BORROWED, GTK_LIB, UNDEFINED } from "../utils.js";
+
+const JUSTIFICATION: Type = { type: "enum", innerType: "GtkJustification", lib: GTK_LIB };
+const JUSTIFICATION_NICK: Type = { type: "enum", innerType: "GtkJustification", lib: GTK_LIB, nick: true };
+const STATE_FLAGS: Type = { type: "flags", innerType: "GtkStateFlags", lib: GTK_LIB };
+const STATE_FLAGS_NICK: Type = { type: "flags", innerType: "GtkStateFlags", lib: GTK_LIB, nick: true };
+
+const GTK_JUSTIFY_CENTER = 2;
+const GTK_STATE_FLAG_ACTIVE = 1 << 0;
+const GTK_STATE_FLAG_SELECTED = 1 << 2;
+
+const setJustify = (label: unknown, value: unknown) =>
+    call(
+        GTK_LIB,
+        "gtk_label_set_justify",
+        [
+            { type: GOBJECT_BORROWED, value: label },
+            { type: JUSTIFICATION, value },
+        ],
+        UNDEFINED,
+    );
+
+const getJustify = (label: unknown, type: Type = JUSTIFICATION) =>
+    call(GTK_LIB, "gtk_label_get_justify", [{ type: GOBJECT_BORROWED, value: label }], type);
+
+const setStateFlags = (widget: unknown, value: unknown) =>
+    call(
+        GTK_LIB,
+        "gtk_widget_set_state_flags",
+        [
+            { type: GOBJECT_BORROWED, value: widget },
+            { type: STATE_FLAGS, value },
+            { type: BOOLEAN, value: true },
+        ],
+        UNDEFINED,
+    );
+
+const getStateFlags = (widget: unknown, type: Type = STATE_FLAGS) =>
+    call(GTK_LIB, "gtk_widget_get_state_flags", [{ type: GOBJECT_BORROWED, value: widget }], type);
+
+// Widgets also carry text direction flags, so only the flags under test are compared.
+const hasFlags = (value: unknown, flags: number) => ((value as number) & flags) === flags;
+
+describe("call - enum and flags types", () => {
+    describe("enums", () => {
+        it("accepts numbers", () => {
+            const label = createLabel();
+            setJustify(label, GTK_JUSTIFY_CENTER);
+
+            expect(getJustify(label)).toBe(GTK_JUSTIFY_CENTER);
+        });
+
+        it("accepts nicks and names", () => {
+            const label = createLabel();
+
+            setJustify(label, "center");
+            expect(getJustify(label)).toBe(GTK_JUSTIFY_CENTER);
+
+            setJustify(label, "GTK_JUSTIFY_RIGHT");
+            expect(getJustify(label, JUSTIFICATION_NICK)).toBe("right");
+        });
+
+        it("rejects values the enum does not define", () => {
+            const label = createLabel();
+
+            expect(() => setJustify(label, 42)).toThrow();
+            expect(() => setJustify(label, "diagonal")).toThrow();
+        });
+    });
+
+    describe("flags", () => {
+        it("accepts numbers", () => {
+            const label = createLabel();
+            setStateFlags(label, GTK_STATE_FLAG_ACTIVE | GTK_STATE_FLAG_SELECTED);
+
+            expect(hasFlags(getStateFlags(label), GTK_STATE_FLAG_ACTIVE | GTK_STATE_FLAG_SELECTED)).toBe(true);
+        });
+
+        it("accepts separated and array forms", () => {
+            const label = createLabel();
+
+            setStateFlags(label, "active|GTK_STATE_FLAG_SELECTED");
+            expect(hasFlags(getStateFlags(label), GTK_STATE_FLAG_ACTIVE | GTK_STATE_FLAG_SELECTED)).toBe(true);
+
+            setStateFlags(label, ["selected"]);
+            expect(getStateFlags(label, STATE_FLAGS_NICK)).toContain("selected");
+            expect(getStateFlags(label, STATE_FLAGS_NICK)).not.toContain("active");
+        });
+
+        it("rejects bits the flags type does not define", () => {
+            const label = createLabel();
+
+            expect(() => setStateFlags(label, 1 << 30)).toThrow();
+            expect(() => setStateFlags(label, "active|bogus")).toThrow();
+        });
+    });
+});
@@ -0,0 +1,87 @@
+import { describe, expect, it } from "vitest";
+import { call } from "../../index.js";
+import type { Type } from "../../types.js";
+import { BOOLEAN, createRef, GLIB_LIB, NULL, STRING, UINT32, UINT64 } from "../utils.js";
+
+const ERROR: Type = { type: "error" };
+
+const G_FILE_ERROR_NOENT = 4;
+
+const getContents = (path: string) =>
+    call(
+        GLIB_LIB,
+        "g_file_get_contents",
+        [
+            { type: STRING, value: path },
+            { type: { type: "ref", innerType: STRING }, value: createRef(null) },
+            { type: { type: "ref", innerType: UINT64 }, value: createRef(0) },
+            { type: ERROR, value: null },
+        ],
+        BOOLEAN,
+    );
+
+const catchError = (fn: () => unknown) => {
+    try {
+        fn();
+    } catch (error) {
+        return error as Error & { domain: string; quark: number; code: number };
+    }
+
+    throw new Error("Expected the call to throw");
+};
+
+describe("call - GError arguments", () => {
+    it("returns normally when no error is reported", () => {
+        const result = call(
+            GLIB_LIB,
+            "g_variant_parse",
+            [
+                { type: NULL, value: null },
+                { type: STRING, value: "[1, 2]" },
+                { type: NULL, value: null },
+                { type: NULL, value: null },
+                { type: ERROR, value: null },
+            ],
+            { type: "variant" },
+        );
+
+        expect(result).toEqual([1, 2]);
+    });
+
+    it("throws a structured error when an error is reported", () => {
+        const error = catchError(() => getContents("/nonexistent/gtkx/file.txt"));
+
+        expect(error).toBeInstanceOf(Error);
+        expect(error.name).toBe("NativeError");
+        expect(error.domain).toBe("g-file-error-quark");
+        const quark = call(GLIB_LIB, "g_quark_from_string", [{ type: STRING, value: error.domain }], UINT32);
+        expect(error.quark).toBe(quark);
+        expect(error.code).toBe(G_FILE_ERROR_NOENT);
+        expect(error.message).toContain("/nonexistent/gtkx/file.txt");
+    });
+
+    it("reports errors from other domains", () => {
+        const error = catchError(() =>
+            call(
+                GLIB_LIB,
+                "g_variant_parse",
+                [
+                    { type: NULL, value: null },
+                    { type: STRING, value: "[1," },
+                    { type: NULL, value: null },
+                    { type: NULL, value: null },
+                    { type: ERROR, value: null },
+                ],
+                { type: "variant" },
+            ),
+        );
+
+        expect(error.domain).toBe("g-variant-parse-error-quark");
+        expect(typeof error.code).toBe("number");
+    });
+
+    it("can be called repeatedly after an error", () => {
+        expect(() => getContents("/nonexistent/a")).toThrow();
+        expect(() => getContents("/nonexistent/b")).toThrow();
+    });
+});
@@ -0,0 +1,115 @@
+import { describe, expect, it } from "vitest";
+import { call } from "../../index.js";
+import type { Type } from "../../types.js";
+import { BOOLEAN, GLIB_LIB, INT32, INT64, NULL, STRING, STRING_BORROWED, UINT32 } from "../utils.js";
+
+const STRING_TABLE: Type = { type: "hashtable", keyType: STRING, valueType: STRING };
+const STRING_TABLE_BORROWED: Type = { type: "hashtable", keyType: STRING, valueType: STRING, borrowed: true };
+const INT_TABLE_BORROWED: Type = { type: "hashtable", keyType: INT32, valueType: BOOLEAN, borrowed: true };
+
+describe("call - hash table types", () => {
+    describe("return values", () => {
+        it("returns an owned table as a Map", () => {
+            const result = call(
+                GLIB_LIB,
+                "g_uri_parse_params",
+                [
+                    { type: STRING, value: "name=gtkx&kind=native" },
+                    { type: INT64, value: -1 },
+                    { type: STRING, value: "&" },
+                    { type: INT32, value: 0 },
+                    { type: NULL, value: null },
+                ],
+                STRING_TABLE,
+            );
+
+            expect(result).toBeInstanceOf(Map);
+            expect(result).toEqual(
+                new Map([
+                    ["name", "gtkx"],
+                    ["kind", "native"],
+                ]),
+            );
+        });
+
+        it("returns an empty Map for an empty table", () => {
+            const result = call(
+                GLIB_LIB,
+                "g_uri_parse_params",
+                [
+                    { type: STRING, value: "" },
+                    { type: INT64, value: -1 },
+                    { type: STRING, value: "&" },
+                    { type: INT32, value: 0 },
+                    { type: NULL, value: null },
+                ],
+                STRING_TABLE,
+            );
+
+            expect(result).toEqual(new Map());
+        });
+    });
+
+    describe("arguments", () => {
+        it("accepts a Map", () => {
+            const size = call(
+                GLIB_LIB,
+                "g_hash_table_size",
+                [
+                    {
+                        type: STRING_TABLE_BORROWED,
+                        value: new Map([
+                            ["a", "1"],
+                            ["b", "2"],
+                            ["c", "3"],
+                        ]),
+                    },
+                ],
+                UINT32,
+            );
+
+            expect(size).toBe(3);
+        });
+
+        it("accepts a plain object for string keys", () => {
+            const result = call(
+                GLIB_LIB,
+                "g_hash_table_lookup",
+                [
+                    { type: STRING_TABLE_BORROWED, value: { greeting: "hello" } },
+                    { type: STRING, value: "greeting" },
+                ],
+                STRING_BORROWED,
+            );
+
+            expect(result).toBe("hello");
+        });
+
+        it("packs integer keys into pointers", () => {
+            const table = new Map([
+                [1, true],
+                [42, true],
+            ]);
+
+            const contains = (key: number) =>
+                call(
+                    GLIB_LIB,
+                    "g_hash_table_contains",
+                    [
+                        { type: INT_TABLE_BORROWED, value: table },
+                        { type: INT64, value: key },
+                    ],
+                    BOOLEAN,
+                );
+
+            expect(contains(42)).toBe(true);
+            expect(contains(7)).toBe(false);
+        });
+
+        it("rejects plain objects for non-string keys", () => {
+            expect(() =>
+                call(GLIB_LIB, "g_hash_table_size", [{ type: INT_TABLE_BORROWED, value: { 1: true } }], UINT32),
+            ).toThrow();
+        });
+    });
+});
@@ -1,10 +1,13 @@
 import { describe, expect, it } from "vitest";
 import { call } from "../../index.js";
 import {
+    BOOLEAN,
     createBox,
     createButton,
     createGrid,
     createLabel,
+    createRef,
+    GLIB_LIB,
     GOBJECT,
     GOBJECT_BORROWED,
     GOBJECT_LIB,
@@ -486,4 +489,140 @@ describe("call - integer types", () => {
             expect(spacing).toBe(15);
         });
     });
+
+    describe("BigInt", () => {
+        const INT64_BIGINT = { ...INT64, bigint: true as const };
+        const UINT64_BIGINT = { ...UINT64, bigint: true as const };
+
+        it("returns 64-bit values above 2^53 without losing precision", () => {
+            const max = 2n ** 64n - 1n;
+
+            const result = call(
+                GLIB_LIB,
+                "g_variant_get_uint64",
+                [{ type: { type: "variant", signature: "t" }, value: max }],
+                UINT64_BIGINT,
+            );
+
+            expect(result).toBe(max);
+        });
+
+        it("returns negative 64-bit values", () => {
+            const min = -(2n ** 63n);
+
+            const result = call(
+                GLIB_LIB,
+                "g_variant_get_int64",
+                [{ type: { type: "variant", signature: "x" }, value: min }],
+                INT64_BIGINT,
+            );
+
+            expect(result).toBe(min);
+        });
+
+        it("writes BigInt out-parameters", () => {
+            const numberRef = createRef(0n);
+
+            const ok = call(
+                GLIB_LIB,
+                "g_ascii_string_to_unsigned",
+                [
+                    { type: STRING, value: "9007199254740993" },
+                    { type: UINT32, value: 10 },
+                    { type: UINT64, value: 0n },
+                    { type: UINT64, value: 2n ** 64n - 1n },
+                    { type: { type: "ref", innerType: UINT64_BIGINT }, value: numberRef },
+                    { type: NULL, value: null },
+                ],
+                BOOLEAN,
+            );
+
+            expect(ok).toBe(true);
+            expect(numberRef.value).toBe(9007199254740993n);
+        });
+
+        it("accepts BigInt values for number descriptors", () => {
+            const box = createBox(0, 0);
+
+            call(
+                GTK_LIB,
+                "gtk_box_set_spacing",
+                [
+                    { type: GOBJECT, value: box },
+                    { type: INT32, value: 12n },
+                ],
+                UNDEFINED,
+            );
+
+            const spacing = call(GTK_LIB, "gtk_box_get_spacing", [{ type: GOBJECT_BORROWED, value: box }], INT32);
+
+            expect(spacing).toBe(12);
+        });
+
+        it("accepts BigInt values at the boundaries of the integer type", () => {
+            const max = call(
+                GLIB_LIB,
+                "g_variant_get_uint64",
+                [{ type: { type: "variant", signature: "t" }, value: 2n ** 64n - 1n }],
+                UINT64_BIGINT,
+            );
+            expect(max).toBe(2n ** 64n - 1n);
+
+            const box = createBox(0, 0);
+            const setSpacing = (value: bigint) =>
+                call(
+                    GTK_LIB,
+                    "gtk_box_set_spacing",
+                    [
+                        { type: GOBJECT, value: box },
+                        { type: INT32, value },
+                    ],
+                    UNDEFINED,
+                );
+
+            expect(() => setSpacing(2n ** 31n - 1n)).not.toThrow();
+            expect(() => setSpacing(0n)).not.toThrow();
+        });
+
+        it("throws a RangeError for BigInt values that do not fit the integer type", () => {
+            const box = createBox(0, 0);
+            const setSpacing = (value: bigint) =>
+                call(
+                    GTK_LIB,
+                    "gtk_box_set_spacing",
+                    [
+                        { type: GOBJECT, value: box },
+                        { type: INT32, value },
+                    ],
+                    UNDEFINED,
+                );
+
+            expect(() => setSpacing(2n ** 31n)).toThrow(RangeError);
+            expect(() => setSpacing(-(2n ** 31n) - 1n)).toThrow(RangeError);
+
+            const newVariant = (value: bigint) =>
+                call(GLIB_LIB, "g_variant_new_uint64", [{ type: UINT64, value }], { type: "variant" });
+
+            expect(() => newVariant(2n ** 64n)).toThrow(RangeError);
+            expect(() => newVariant(-1n)).toThrow(RangeError);
+        });
+
+        it("rejects out-of-range BigInt values in refs", () => {
+            expect(() =>
+                call(
+                    GLIB_LIB,
+                    "g_ascii_string_to_unsigned",
+                    [
+                        { type: STRING, value: "1" },
+                        { type: UINT32, value: 10 },
+                        { type: UINT64, value: 0n },
+                        { type: UINT64, value: 10n },
+                        { type: { type: "ref", innerType: UINT64_BIGINT }, value: createRef(2n ** 64n) },
+                        { type: NULL, value: null },
+                    ],
+                    BOOLEAN,
+                ),
+            ).toThrow(RangeError);
+        });
+    });
 });
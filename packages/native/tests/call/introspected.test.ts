@@ -0,0 +1,67 @@
+import { describe, expect, it } from "vitest";
+import { callIntrospected } from "../../index.js";
+import { createRef } from "../utils.js";
+
+const catchError = (fn: () => unknown) => {
+    try {
+        fn();
+    } catch (error) {
+        return error as Error & { domain: string; code: number };
+    }
+
+    throw new Error("Expected the call to throw");
+};
+
+describe("callIntrospected", () => {
+    it("calls functions with types from the typelib", () => {
+        const result = callIntrospected("GLib", "2.0", "ascii_strup", ["hello", -1]);
+
+        expect(result).toBe("HELLO");
+    });
+
+    it("calls constructors and methods with the instance first", () => {
+        const dateTime = callIntrospected("GLib", "2.0", "DateTime.new_utc", [2024, 6, 15, 12, 30, 0]);
+
+        expect(callIntrospected("GLib", "2.0", "DateTime.get_year", [dateTime])).toBe(2024);
+        expect(callIntrospected("GLib", "2.0", "DateTime.get_hour", [dateTime])).toBe(12);
+    });
+
+    it("writes out-parameters to refs", () => {
+        const dateTime = callIntrospected("GLib", "2.0", "DateTime.new_utc", [2024, 6, 15, 0, 0, 0]);
+        const year = createRef(0);
+        const month = createRef(0);
+        const day = createRef(0);
+
+        callIntrospected("GLib", "2.0", "DateTime.get_ymd", [dateTime, year, month, day]);
+
+        expect([year.value, month.value, day.value]).toEqual([2024, 6, 15]);
+    });
+
+    it("accepts null for nullable arguments", () => {
+        const result = callIntrospected("GLib", "2.0", "build_filenamev", [["a", "b"]]);
+
+        expect(result).toBe("a/b");
+        expect(() => callIntrospected("GLib", "2.0", "getenv", ["GTKX_UNSET_VARIABLE"])).not.toThrow();
+    });
+
+    it("throws the GError of throwing functions", () => {
+        const error = catchError(() =>
+            callIntrospected("GLib", "2.0", "file_get_contents", [
+                "/nonexistent/path/for/gtkx",
+                createRef(null),
+                createRef(0),
+            ]),
+        );
+
+        expect(error.domain).toBe("g-file-error-quark");
+    });
+
+    it("throws for unknown functions", () => {
+        expect(() => callIntrospected("GLib", "2.0", "no_such_function", [])).toThrow(/not found/);
+        expect(() => callIntrospected("NoSuchNamespace", "1.0", "anything", [])).toThrow();
+    });
+
+    it("throws for a wrong number of arguments", () => {
+        expect(() => callIntrospected("GLib", "2.0", "ascii_strup", ["hello"])).toThrow(TypeError);
+    });
+});
@@ -0,0 +1,172 @@
+import { describe, expect, it } from "vitest";
+import { call } from "../../index.js";
+import type { Type } from "../../types.js";
+import { BOOLEAN, createLabel, createRef, FLOAT32, GOBJECT_BORROWED, GTK_LIB, INT32, INT64, UNDEFINED } from "../utils.js";
+
+const LIBC = "libc.so.6";
+const GRAPHENE_LIB = "libgraphene-1.0.so.0";
+
+const DIV_T: Type = {
+    type: "struct",
+    fields: [
+        { name: "quot", type: INT32 },
+        { name: "rem", type: INT32 },
+    ],
+};
+
+const LLDIV_T: Type = {
+    type: "struct",
+    fields: [
+        { name: "quot", type: INT64 },
+        { name: "rem", type: INT64 },
+    ],
+};
+
+const POINT: Type = {
+    type: "struct",
+    fields: [
+        { name: "x", type: FLOAT32 },
+        { name: "y", type: FLOAT32 },
+    ],
+};
+
+const SIZE: Type = {
+    type: "struct",
+    fields: [
+        { name: "width", type: FLOAT32 },
+        { name: "height", type: FLOAT32 },
+    ],
+};
+
+const RECT: Type = {
+    type: "struct",
+    fields: [
+        { name: "origin", type: POINT },
+        { name: "size", type: SIZE },
+    ],
+};
+
+describe("call - struct types", () => {
+    describe("return by value", () => {
+        it("returns a struct of 32-bit integers", () => {
+            const result = call(
+                LIBC,
+                "div",
+                [
+                    { type: INT32, value: 17 },
+                    { type: INT32, value: 5 },
+                ],
+                DIV_T,
+            );
+
+            expect(result).toEqual({ quot: 3, rem: 2 });
+        });
+
+        it("returns a struct of 64-bit integers", () => {
+            const result = call(
+                LIBC,
+                "lldiv",
+                [
+                    { type: INT64, value: -100 },
+                    { type: INT64, value: 7 },
+                ],
+                LLDIV_T,
+            );
+
+            expect(result).toEqual({ quot: -14, rem: -2 });
+        });
+    });
+
+    describe("struct refs", () => {
+        it("populates a caller-allocated nested struct", () => {
+            const rectRef = createRef<unknown>(null);
+
+            call(
+                GRAPHENE_LIB,
+                "graphene_rect_init",
+                [
+                    { type: { type: "ref", innerType: RECT }, value: rectRef },
+                    { type: FLOAT32, value: 1 },
+                    { type: FLOAT32, value: 2 },
+                    { type: FLOAT32, value: 30 },
+                    { type: FLOAT32, value: 40 },
+                ],
+                UNDEFINED,
+            );
+
+            expect(rectRef.value).toEqual({
+                origin: { x: 1, y: 2 },
+                size: { width: 30, height: 40 },
+            });
+        });
+
+        it("passes a struct through a const pointer", () => {
+            const rectRef = createRef({
+                origin: { x: 0, y: 0 },
+                size: { width: 4, height: 2.5 },
+            });
+
+            const area = call(
+                GRAPHENE_LIB,
+                "graphene_rect_get_area",
+                [{ type: { type: "ref", innerType: RECT }, value: rectRef }],
+                FLOAT32,
+            );
+
+            expect(area).toBe(10);
+        });
+
+        it("reads and writes structs in the same call", () => {
+            const label = createLabel("Test");
+            const pointRef = createRef({ x: 3, y: 4 });
+            const outRef = createRef<unknown>(null);
+
+            const result = call(
+                GTK_LIB,
+                "gtk_widget_compute_point",
+                [
+                    { type: GOBJECT_BORROWED, value: label },
+                    { type: GOBJECT_BORROWED, value: label },
+                    { type: { type: "ref", innerType: POINT }, value: pointRef },
+                    { type: { type: "ref", innerType: POINT }, value: outRef },
+                ],
+                BOOLEAN,
+            );
+
+            expect(result).toBe(true);
+            expect(outRef.value).toEqual({ x: 3, y: 4 });
+        });
+    });
+
+    describe("error handling", () => {
+        it("throws when a field has the wrong type", () => {
+            expect(() =>
+                call(
+                    GRAPHENE_LIB,
+                    "graphene_rect_get_area",
+                    [
+                        {
+                            type: { type: "ref", innerType: RECT },
+                            value: createRef({ origin: { x: "0", y: 0 }, size: { width: 1, height: 1 } }),
+                        },
+                    ],
+                    FLOAT32,
+                ),
+            ).toThrow();
+        });
+
+        it("rejects unsupported field types", () => {
+            expect(() =>
+                call(
+                    LIBC,
+                    "div",
+                    [
+                        { type: INT32, value: 1 },
+                        { type: INT32, value: 1 },
+                    ],
+                    { type: "struct", fields: [{ name: "items", type: { type: "array", itemType: INT32 } }] },
+                ),
+            ).toThrow();
+        });
+    });
+});
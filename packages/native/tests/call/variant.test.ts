@@ -0,0 +1,113 @@
+import { describe, expect, it } from "vitest";
+import { call } from "../../index.js";
+import type { Type } from "../../types.js";
+import { BOOLEAN, GLIB_LIB, INT32, STRING, STRING_BORROWED, UINT64 } from "../utils.js";
+
+const VARIANT: Type = { type: "variant" };
+const VARDICT: Type = { type: "variant", signature: "a{sv}" };
+
+const typeString = (type: Type, value: unknown) =>
+    call(GLIB_LIB, "g_variant_get_type_string", [{ type, value }], STRING_BORROWED);
+
+const print = (type: Type, value: unknown) =>
+    call(
+        GLIB_LIB,
+        "g_variant_print",
+        [
+            { type, value },
+            { type: BOOLEAN, value: false },
+        ],
+        STRING,
+    );
+
+describe("call - variant types", () => {
+    describe("return values", () => {
+        it("returns a floating integer variant as a number", () => {
+            const result = call(GLIB_LIB, "g_variant_new_int32", [{ type: INT32, value: 42 }], VARIANT);
+
+            expect(result).toBe(42);
+        });
+
+        it("returns a string variant as a string", () => {
+            const result = call(GLIB_LIB, "g_variant_new_string", [{ type: STRING, value: "hello" }], VARIANT);
+
+            expect(result).toBe("hello");
+        });
+
+        it("returns a dictionary as a Map", () => {
+            const result = call(
+                GLIB_LIB,
+                "g_variant_ref",
+                [{ type: VARDICT, value: { count: 3, name: "gtkx" } }],
+                VARIANT,
+            );
+
+            expect(result).toEqual(
+                new Map<string, unknown>([
+                    ["count", 3],
+                    ["name", "gtkx"],
+                ]),
+            );
+        });
+
+        it("returns tuples as arrays", () => {
+            const result = call(
+                GLIB_LIB,
+                "g_variant_ref",
+                [{ type: { type: "variant", signature: "(isb)" }, value: [1, "two", true] }],
+                VARIANT,
+            );
+
+            expect(result).toEqual([1, "two", true]);
+        });
+    });
+
+    describe("arguments", () => {
+        it("builds a vardict from a plain object", () => {
+            const value = { enabled: true, label: "Test" };
+
+            expect(typeString(VARDICT, value)).toBe("a{sv}");
+            expect(call(GLIB_LIB, "g_variant_n_children", [{ type: VARDICT, value }], UINT64)).toBe(2);
+        });
+
+        it("builds a dictionary from a Map with non-string keys", () => {
+            const type: Type = { type: "variant", signature: "a{is}" };
+
+            expect(print(type, new Map([[1, "one"]]))).toBe("{1: 'one'}");
+        });
+
+        it("builds tuples and maybe types", () => {
+            expect(print({ type: "variant", signature: "(ii)" }, [1, 2])).toBe("(1, 2)");
+            expect(print({ type: "variant", signature: "mi" }, null)).toBe("nothing");
+            expect(print({ type: "variant", signature: "as" }, ["a", "b"])).toBe("['a', 'b']");
+        });
+
+        it("infers the variant type without a signature", () => {
+            expect(typeString(VARIANT, true)).toBe("b");
+            expect(typeString(VARIANT, 7)).toBe("i");
+            expect(typeString(VARIANT, 1.5)).toBe("d");
+            expect(typeString(VARIANT, "text")).toBe("s");
+            expect(typeString(VARIANT, ["a", "b"])).toBe("as");
+            expect(typeString(VARIANT, [1, "b"])).toBe("av");
+            expect(typeString(VARIANT, { key: "value" })).toBe("a{sv}");
+        });
+    });
+
+    describe("error handling", () => {
+        it("rejects invalid type strings", () => {
+            expect(() => typeString({ type: "variant", signature: "a{" }, [])).toThrow();
+        });
+
+        it("rejects values that do not match the type string", () => {
+            expect(() => typeString({ type: "variant", signature: "(ii)" }, [1])).toThrow();
+            expect(() => typeString({ type: "variant", signature: "i" }, "1")).toThrow();
+        });
+
+        it("rejects numbers outside the range of the type string", () => {
+            expect(() => typeString({ type: "variant", signature: "i" }, 2 ** 40)).toThrow(/out of range/);
+            expect(() => typeString({ type: "variant", signature: "y" }, 256)).toThrow(/out of range/);
+            expect(() => typeString({ type: "variant", signature: "t" }, 2n ** 64n)).toThrow(/out of range/);
+            expect(typeString({ type: "variant", signature: "i" }, 2 ** 31 - 1)).toBe("i");
+        });
+    });
+});
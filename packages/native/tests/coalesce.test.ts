@@ -0,0 +1,157 @@
+import { describe, expect, it, vi } from "vitest";
+import { batchCallAsync, call } from "../index.js";
+import {
+    BOOLEAN,
+    createCancellable,
+    GIO_LIB,
+    GOBJECT,
+    GOBJECT_BORROWED,
+    GOBJECT_LIB,
+    INT32,
+    NULL,
+    STRING,
+    UINT32,
+    UINT64,
+} from "./utils.js";
+
+const G_TYPE_OBJECT = 80;
+const ITEMS = 20;
+
+function createListStore(): unknown {
+    return call(GIO_LIB, "g_list_store_new", [{ type: UINT64, value: G_TYPE_OBJECT }], GOBJECT);
+}
+
+function connectItemsChanged(store: unknown, coalesce: "latest" | "batch", handler: (...args: unknown[]) => void) {
+    call(
+        GOBJECT_LIB,
+        "g_signal_connect_data",
+        [
+            { type: GOBJECT, value: store },
+            { type: STRING, value: "items-changed" },
+            {
+                type: { type: "callback", argTypes: [GOBJECT_BORROWED, UINT32, UINT32, UINT32], coalesce },
+                value: handler,
+            },
+            { type: NULL, value: null },
+            { type: NULL, value: null },
+            { type: INT32, value: 0 },
+        ],
+        UINT64,
+    );
+}
+
+function append(store: unknown, item: unknown) {
+    return {
+        library: GIO_LIB,
+        symbol: "g_list_store_append",
+        args: [
+            { type: GOBJECT_BORROWED, value: store },
+            { type: GOBJECT_BORROWED, value: item },
+        ],
+    };
+}
+
+// The JavaScript thread is blocked while the GTK thread runs the appends, so every emission is queued before
+// any of them can be delivered.
+async function appendWhileBlocked(appends: ReturnType<typeof append>[]): Promise<void> {
+    const done = batchCallAsync(appends);
+    Atomics.wait(new Int32Array(new SharedArrayBuffer(4)), 0, 0, 200);
+    await done;
+}
+
+function appendItems(store: unknown): Promise<void> {
+    const item = createCancellable();
+    return appendWhileBlocked(Array.from({ length: ITEMS }, () => append(store, item)));
+}
+
+describe("coalesced callbacks", () => {
+    it("delivers only the latest queued emission", async () => {
+        const store = createListStore();
+        const handler = vi.fn();
+        connectItemsChanged(store, "latest", handler);
+
+        await appendItems(store);
+
+        await vi.waitFor(() => expect(handler).toHaveBeenCalled());
+        expect(handler).toHaveBeenCalledTimes(1);
+        expect(handler).toHaveBeenCalledWith(expect.anything(), ITEMS - 1, 0, 1);
+    });
+
+    it("delivers queued emissions as a single batch in order", async () => {
+        const store = createListStore();
+        const batches: unknown[][][] = [];
+        connectItemsChanged(store, "batch", (batch) => {
+            batches.push(batch as unknown[][]);
+        });
+
+        await appendItems(store);
+
+        await vi.waitFor(() => expect(batches).not.toHaveLength(0));
+        expect(batches).toHaveLength(1);
+        expect(batches[0]?.map((args) => args[1])).toEqual(Array.from({ length: ITEMS }, (_, i) => i));
+        expect(batches[0]?.every((args) => args.length === 4)).toBe(true);
+    });
+
+    it("does not merge emissions across other queued callbacks", async () => {
+        const first = createListStore();
+        const second = createListStore();
+        const item = createCancellable();
+        const deliveries: string[] = [];
+        connectItemsChanged(first, "batch", (batch) => {
+            deliveries.push(`first:${(batch as unknown[]).length}`);
+        });
+        connectItemsChanged(second, "batch", (batch) => {
+            deliveries.push(`second:${(batch as unknown[]).length}`);
+        });
+
+        await appendWhileBlocked([
+            append(first, item),
+            append(first, item),
+            append(second, item),
+            append(first, item),
+        ]);
+
+        await vi.waitFor(() => expect(deliveries).toHaveLength(3));
+        expect(deliveries).toEqual(["first:2", "second:1", "first:1"]);
+    });
+
+    it("rejects coalesced callbacks that return a value", () => {
+        const store = createListStore();
+
+        expect(() =>
+            call(
+                GOBJECT_LIB,
+                "g_signal_connect_data",
+                [
+                    { type: GOBJECT, value: store },
+                    { type: STRING, value: "items-changed" },
+                    { type: { type: "callback", returnType: BOOLEAN, coalesce: "latest" }, value: () => true },
+                    { type: NULL, value: null },
+                    { type: NULL, value: null },
+                    { type: INT32, value: 0 },
+                ],
+                UINT64,
+            ),
+        ).toThrow(TypeError);
+    });
+
+    it("rejects unknown coalesce policies", () => {
+        const store = createListStore();
+
+        expect(() =>
+            call(
+                GOBJECT_LIB,
+                "g_signal_connect_data",
+                [
+                    { type: GOBJECT, value: store },
+                    { type: STRING, value: "items-changed" },
+                    { type: { type: "callback", coalesce: "first" as "latest" }, value: () => {} },
+                    { type: NULL, value: null },
+                    { type: NULL, value: null },
+                    { type: INT32, value: 0 },
+                ],
+                UINT64,
+            ),
+        ).toThrow(TypeError);
+    });
+});
@@ -0,0 +1,105 @@
+import { afterEach, describe, expect, it, vi } from "vitest";
+import { call, configure } from "../index.js";
+import {
+    BOOLEAN,
+    createCancellable,
+    GIO_LIB,
+    GLIB_LIB,
+    GOBJECT,
+    GOBJECT_LIB,
+    STRING,
+    UINT64,
+    UNDEFINED,
+} from "./utils.js";
+
+afterEach(() => {
+    configure({ waitTimeout: 0, throwOnTimeout: false, onWaitTimeout: null, maxReentrancyDepth: 64 });
+});
+
+function connectCancelled(cancellable: unknown, callback: () => void): void {
+    call(
+        GOBJECT_LIB,
+        "g_signal_connect_closure",
+        [
+            { type: GOBJECT, value: cancellable },
+            { type: STRING, value: "cancelled" },
+            { type: { type: "callback", trampoline: "closure" }, value: callback },
+            { type: BOOLEAN, value: false },
+        ],
+        UINT64,
+    );
+}
+
+describe("configure", () => {
+    it("rejects negative and fractional values", () => {
+        expect(() => configure({ waitTimeout: -1 })).toThrow(RangeError);
+        expect(() => configure({ maxReentrancyDepth: 1.5 })).toThrow(RangeError);
+    });
+
+    it("throws when a wait exceeds the timeout with throwOnTimeout", () => {
+        configure({ waitTimeout: 50, throwOnTimeout: true });
+
+        expect(() => call(GLIB_LIB, "g_usleep", [{ type: UINT64, value: 300000 }], UNDEFINED)).toThrow(/Timed out/);
+    });
+
+    it("completes waits exceeding the timeout without throwOnTimeout", () => {
+        configure({ waitTimeout: 50 });
+
+        expect(() => call(GLIB_LIB, "g_usleep", [{ type: UINT64, value: 100000 }], UNDEFINED)).not.toThrow();
+    });
+
+    it("reports waits exceeding the timeout to onWaitTimeout", async () => {
+        const onWaitTimeout = vi.fn();
+        configure({ waitTimeout: 50, onWaitTimeout });
+
+        call(GLIB_LIB, "g_usleep", [{ type: UINT64, value: 100000 }], UNDEFINED);
+
+        await vi.waitFor(() => expect(onWaitTimeout).toHaveBeenCalledOnce());
+        const [report] = onWaitTimeout.mock.calls[0] as [string];
+        expect(report).toMatch(/JS thread has been waiting for the GTK thread/);
+        expect(report).toContain("calling g_usleep");
+    });
+
+    it("rejects an onWaitTimeout that is not a function", () => {
+        expect(() => configure({ onWaitTimeout: "log" as unknown as () => void })).toThrow(TypeError);
+    });
+
+    it("limits nested calls from callbacks to maxReentrancyDepth", () => {
+        configure({ maxReentrancyDepth: 1 });
+
+        const cancellable = createCancellable();
+        let nestedError: unknown = null;
+
+        connectCancelled(cancellable, () => {
+            try {
+                call(GIO_LIB, "g_cancellable_is_cancelled", [{ type: GOBJECT, value: cancellable }], BOOLEAN);
+            } catch (error) {
+                nestedError = error;
+            }
+        });
+
+        call(GIO_LIB, "g_cancellable_cancel", [{ type: GOBJECT, value: cancellable }], UNDEFINED);
+
+        expect(nestedError).toBeInstanceOf(RangeError);
+    });
+
+    it("allows nested calls within maxReentrancyDepth", () => {
+        configure({ maxReentrancyDepth: 2 });
+
+        const cancellable = createCancellable();
+        let nestedResult: unknown = null;
+
+        connectCancelled(cancellable, () => {
+            nestedResult = call(
+                GIO_LIB,
+                "g_cancellable_is_cancelled",
+                [{ type: GOBJECT, value: cancellable }],
+                BOOLEAN,
+            );
+        });
+
+        call(GIO_LIB, "g_cancellable_cancel", [{ type: GOBJECT, value: cancellable }], UNDEFINED);
+
+        expect(nestedResult).toBe(true);
+    });
+});
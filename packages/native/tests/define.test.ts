@@ -0,0 +1,94 @@
+import { describe, expect, it } from "vitest";
+import { define, invoke } from "../index.js";
+import {
+    createLabel,
+    createRef,
+    GLIB_LIB,
+    GOBJECT_BORROWED,
+    GTK_LIB,
+    INT32,
+    STRING,
+    STRING_BORROWED,
+    UINT64,
+    UNDEFINED,
+} from "./utils.js";
+
+describe("define", () => {
+    it("invokes a defined function many times", () => {
+        const setText = define(
+            GTK_LIB,
+            "gtk_label_set_text",
+            [{ type: GOBJECT_BORROWED }, { type: STRING }],
+            UNDEFINED,
+        );
+        const getText = define(GTK_LIB, "gtk_label_get_text", [{ type: GOBJECT_BORROWED }], STRING_BORROWED);
+        const label = createLabel();
+
+        for (let i = 0; i < 100; i++) {
+            invoke(setText, [label, `Label ${i}`]);
+        }
+
+        expect(invoke(getText, [label])).toBe("Label 99");
+    });
+
+    it("writes ref parameters back", () => {
+        const parse = define(
+            GLIB_LIB,
+            "g_ascii_string_to_unsigned",
+            [
+                { type: STRING },
+                { type: INT32 },
+                { type: UINT64 },
+                { type: UINT64 },
+                { type: { type: "ref", innerType: UINT64 } },
+                { type: { type: "error" } },
+            ],
+            { type: "boolean" },
+        );
+        const out = createRef(0);
+
+        expect(invoke(parse, ["42", 10, 0, 100, out, null])).toBe(true);
+        expect(out.value).toBe(42);
+    });
+
+    it("accepts null for optional arguments", () => {
+        const setText = define(
+            GTK_LIB,
+            "gtk_label_set_text",
+            [{ type: GOBJECT_BORROWED }, { type: STRING, optional: true }],
+            UNDEFINED,
+        );
+
+        expect(() => invoke(setText, [createLabel(), null])).not.toThrow();
+    });
+
+    it("throws GErrors reported by the function", () => {
+        const parse = define(
+            GLIB_LIB,
+            "g_ascii_string_to_unsigned",
+            [
+                { type: STRING },
+                { type: INT32 },
+                { type: UINT64 },
+                { type: UINT64 },
+                { type: { type: "ref", innerType: UINT64 } },
+                { type: { type: "error" } },
+            ],
+            { type: "boolean" },
+        );
+
+        expect(() => invoke(parse, ["not a number", 10, 0, 100, createRef(0), null])).toThrow();
+    });
+
+    it("throws when the symbol does not exist", () => {
+        expect(() => define(GLIB_LIB, "g_nonexistent_function_12345", [], UNDEFINED)).toThrow(
+            /Error defining function/,
+        );
+    });
+
+    it("throws for a wrong number of values", () => {
+        const getText = define(GTK_LIB, "gtk_label_get_text", [{ type: GOBJECT_BORROWED }], STRING_BORROWED);
+
+        expect(() => invoke(getText, [])).toThrow(TypeError);
+    });
+});
@@ -0,0 +1,124 @@
+import { afterEach, beforeEach, describe, expect, it } from "vitest";
+import { call, type CallDescriptor, requestFrameCommit } from "../index.js";
+import {
+    BOOLEAN,
+    createLabel,
+    GDK_LIB,
+    GOBJECT,
+    GOBJECT_BORROWED,
+    GTK_LIB,
+    INT64,
+    STRING,
+    STRING_BORROWED,
+    UNDEFINED,
+} from "./utils.js";
+
+let window: unknown;
+
+beforeEach(() => {
+    window = call(GTK_LIB, "gtk_window_new", [], GOBJECT);
+    call(GTK_LIB, "gtk_window_present", [{ type: GOBJECT, value: window }], UNDEFINED);
+});
+
+afterEach(() => {
+    call(GTK_LIB, "gtk_window_destroy", [{ type: GOBJECT, value: window }], UNDEFINED);
+});
+
+function setLabel(label: unknown, text: string): CallDescriptor {
+    return {
+        library: GTK_LIB,
+        symbol: "gtk_label_set_label",
+        args: [
+            { type: GOBJECT, value: label },
+            { type: STRING, value: text },
+        ],
+    };
+}
+
+function getLabel(label: unknown): unknown {
+    return call(GTK_LIB, "gtk_label_get_label", [{ type: GOBJECT, value: label }], STRING_BORROWED);
+}
+
+describe("requestFrameCommit", () => {
+    it("runs the calls in the next frame", async () => {
+        const label = createLabel("before");
+        call(
+            GTK_LIB,
+            "gtk_window_set_child",
+            [
+                { type: GOBJECT, value: window },
+                { type: GOBJECT, value: label },
+            ],
+            UNDEFINED,
+        );
+
+        const commit = requestFrameCommit([setLabel(label, "after")]);
+
+        await expect(commit).resolves.toEqual([undefined]);
+        expect(getLabel(label)).toBe("after");
+    });
+
+    it("runs the calls in the update phase of the frame", async () => {
+        const label = createLabel("before");
+        const clock = call(GTK_LIB, "gtk_widget_get_frame_clock", [{ type: GOBJECT, value: window }], GOBJECT_BORROWED);
+        let presentedTime = 0;
+
+        const [, frameTime] = (await requestFrameCommit(
+            [
+                setLabel(label, "after"),
+                {
+                    library: GDK_LIB,
+                    symbol: "gdk_frame_clock_get_frame_time",
+                    args: [{ type: GOBJECT_BORROWED, value: clock }],
+                    returnType: INT64,
+                },
+            ],
+            (time) => {
+                presentedTime = time;
+            },
+        )) as [undefined, number];
+        await expect.poll(() => presentedTime).not.toBe(0);
+
+        expect(frameTime).toBe(presentedTime);
+        expect(getLabel(label)).toBe("after");
+    });
+
+    it("runs the calls immediately when no window is visible", async () => {
+        const label = createLabel("before");
+        call(
+            GTK_LIB,
+            "gtk_widget_set_visible",
+            [
+                { type: GOBJECT, value: window },
+                { type: BOOLEAN, value: false },
+            ],
+            UNDEFINED,
+        );
+
+        await expect(requestFrameCommit([setLabel(label, "after")])).resolves.toEqual([undefined]);
+        expect(getLabel(label)).toBe("after");
+    });
+
+    it("paints commits requested together in the same frame", async () => {
+        const first = createLabel();
+        const second = createLabel();
+        const frameTimes: number[] = [];
+
+        await Promise.all([
+            requestFrameCommit([setLabel(first, "first")], (frameTime) => frameTimes.push(frameTime)),
+            requestFrameCommit([setLabel(second, "second")], (frameTime) => frameTimes.push(frameTime)),
+        ]);
+        await expect.poll(() => frameTimes.length).toBe(2);
+
+        expect(frameTimes[0]).toBeGreaterThan(0);
+        expect(frameTimes[1]).toBe(frameTimes[0]);
+        expect(getLabel(first)).toBe("first");
+        expect(getLabel(second)).toBe("second");
+    });
+
+    it("rejects when a call fails", async () => {
+        const commit = requestFrameCommit([{ library: GTK_LIB, symbol: "nonexistent_symbol", args: [] }]);
+
+        await expect(commit).rejects.toThrow();
+    });
+});
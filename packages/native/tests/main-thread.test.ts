@@ -0,0 +1,100 @@
+import { spawnSync } from "node:child_process";
+import { fileURLToPath } from "node:url";
+import { describe, expect, it } from "vitest";
+
+const NATIVE_MODULE = fileURLToPath(new URL("../index.node", import.meta.url));
+
+const PRELUDE = `
+const native = require(process.argv[1]);
+const GTK_LIB = "libgtk-4.so.1";
+const GIO_LIB = "libgio-2.0.so.0";
+const GOBJECT_LIB = "libgobject-2.0.so.0";
+const GLIB_LIB = "libglib-2.0.so.0";
+const app = native.start("com.gtkx.main_thread_" + process.pid, 0, { mainThread: true });
+`;
+
+// Main thread mode can only be started once per process, so each case runs in a child process.
+function spawnOnMainThread(script: string) {
+    const result = spawnSync(process.execPath, ["-e", PRELUDE + script, NATIVE_MODULE], {
+        encoding: "utf8",
+        timeout: 10000,
+    });
+
+    expect(result.status, result.stderr).toBe(0);
+
+    return { output: JSON.parse(result.stdout) as unknown, stderr: result.stderr };
+}
+
+function runOnMainThread(script: string): unknown {
+    return spawnOnMainThread(script).output;
+}
+
+describe("start - main thread mode", () => {
+    it("performs FFI calls on the JavaScript thread", () => {
+        const output = runOnMainThread(`
+            const label = native.call(GTK_LIB, "gtk_label_new", [{ type: { type: "string" }, value: "Hello" }], { type: "gobject" });
+            const text = native.call(GTK_LIB, "gtk_label_get_text", [{ type: { type: "gobject" }, value: label }], { type: "string", borrowed: true });
+            native.stop();
+            console.log(JSON.stringify(text));
+        `);
+
+        expect(output).toBe("Hello");
+    });
+
+    it("invokes signal callbacks during the call that emits them", () => {
+        const output = runOnMainThread(`
+            const cancellable = native.call(GIO_LIB, "g_cancellable_new", [], { type: "gobject" });
+            let invoked = false;
+            native.call(GOBJECT_LIB, "g_signal_connect_closure", [
+                { type: { type: "gobject" }, value: cancellable },
+                { type: { type: "string" }, value: "cancelled" },
+                { type: { type: "callback", trampoline: "closure" }, value: () => { invoked = true; } },
+                { type: { type: "boolean" }, value: false },
+            ], { type: "int", size: 64, unsigned: true });
+            native.call(GIO_LIB, "g_cancellable_cancel", [{ type: { type: "gobject" }, value: cancellable }], { type: "undefined" });
+            const invokedDuringCall = invoked;
+            native.stop();
+            console.log(JSON.stringify(invokedDuringCall));
+        `);
+
+        expect(output).toBe(true);
+    });
+
+    it("dispatches GLib sources from the Node.js event loop", () => {
+        const output = runOnMainThread(`
+            const SOURCE = { type: "boxed", innerType: "GSource", lib: GOBJECT_LIB };
+            const source = native.call(GLIB_LIB, "g_timeout_source_new", [{ type: { type: "int", size: 32, unsigned: true }, value: 10 }], SOURCE);
+            native.call(GOBJECT_LIB, "g_source_set_closure", [
+                { type: SOURCE, value: source },
+                {
+                    type: { type: "callback", trampoline: "closure", returnType: { type: "boolean" } },
+                    value: () => {
+                        native.stop();
+                        console.log(JSON.stringify("fired"));
+                        return false;
+                    },
+                },
+            ], { type: "undefined" });
+            native.call(GLIB_LIB, "g_source_attach", [{ type: SOURCE, value: source }, { type: { type: "null" }, value: null }], { type: "int", size: 32, unsigned: true });
+        `);
+
+        expect(output).toBe("fired");
+    });
+
+    it("emits the application lifecycle signals", () => {
+        const { output, stderr } = spawnOnMainThread(`
+            let shutdown = false;
+            native.call(GOBJECT_LIB, "g_signal_connect_closure", [
+                { type: { type: "gobject" }, value: app },
+                { type: { type: "string" }, value: "shutdown" },
+                { type: { type: "callback", trampoline: "closure" }, value: () => { shutdown = true; } },
+                { type: { type: "boolean" }, value: false },
+            ], { type: "int", size: 64, unsigned: true });
+            native.stop();
+            console.log(JSON.stringify(shutdown));
+        `);
+
+        expect(output).toBe(true);
+        expect(stderr).not.toMatch(/activate/);
+    });
+});
@@ -0,0 +1,134 @@
+import { afterEach, describe, expect, it } from "vitest";
+import { batchCall, batchCallAsync, call, callAsync, type CallOptions, configure } from "../index.js";
+import { BOOLEAN, GLIB_LIB, GOBJECT_LIB, INT64, NULL, STRING, UINT32, UINT64, UNDEFINED } from "./utils.js";
+
+const GSOURCE = { type: "boxed" as const, innerType: "GSource", lib: GOBJECT_LIB, getTypeFn: "g_source_get_type" };
+
+afterEach(() => {
+    configure({ dispatchBudget: 0 });
+});
+
+function userName(options: CallOptions): Promise<unknown> {
+    return callAsync(GLIB_LIB, "g_get_user_name", [], STRING, options);
+}
+
+function sleep(microseconds: number, options?: CallOptions): Promise<unknown> {
+    return callAsync(GLIB_LIB, "g_usleep", [{ type: UINT64, value: microseconds }], UNDEFINED, options);
+}
+
+function monotonicTime(options: CallOptions): Promise<unknown> {
+    return callAsync(GLIB_LIB, "g_get_monotonic_time", [], INT64, options);
+}
+
+// Attaches a 1 ms timeout source at default priority that records the monotonic time of each run.
+function recordTimeouts(times: number[]): unknown {
+    const source = call(GLIB_LIB, "g_timeout_source_new", [{ type: UINT32, value: 1 }], GSOURCE);
+    const onTimeout = () => {
+        times.push(Number(process.hrtime.bigint() / 1000n));
+        return true;
+    };
+
+    call(
+        GOBJECT_LIB,
+        "g_source_set_closure",
+        [
+            { type: GSOURCE, value: source },
+            { type: { type: "callback", trampoline: "closure", returnType: BOOLEAN }, value: onTimeout },
+        ],
+        UNDEFINED,
+    );
+    call(
+        GLIB_LIB,
+        "g_source_attach",
+        [
+            { type: GSOURCE, value: source },
+            { type: NULL, value: null },
+        ],
+        UINT32,
+    );
+
+    return source;
+}
+
+describe("call - priority", () => {
+    it("runs higher priority calls first", async () => {
+        const order: string[] = [];
+        const busy = sleep(100_000, { priority: "high" });
+
+        const calls = (["idle", "default", "high"] as const).map((priority) =>
+            userName({ priority }).then(() => order.push(priority)),
+        );
+
+        await Promise.all([busy, ...calls]);
+
+        expect(order).toEqual(["high", "default", "idle"]);
+    });
+
+    it("runs synchronous calls at every priority", () => {
+        for (const priority of ["high", "default", "idle"] as const) {
+            expect(call(GLIB_LIB, "g_get_user_name", [], STRING, { priority })).toBeTypeOf("string");
+        }
+    });
+
+    it("accepts a priority for batches", async () => {
+        const calls = [{ library: GLIB_LIB, symbol: "g_get_user_name", args: [], returnType: STRING }];
+
+        expect(batchCall(calls, { priority: "high" })).toEqual([expect.any(String)]);
+        await expect(batchCallAsync(calls, { priority: "default" })).resolves.toEqual([expect.any(String)]);
+    });
+
+    it("rejects unknown priorities", () => {
+        const options = { priority: "urgent" } as unknown as CallOptions;
+
+        expect(() => call(GLIB_LIB, "g_get_user_name", [], STRING, options)).toThrow(TypeError);
+        expect(() => batchCall([], options)).toThrow(TypeError);
+    });
+
+    it("rejects batches on a worker thread", () => {
+        expect(() => batchCall([], { thread: "worker" } as CallOptions)).toThrow(TypeError);
+    });
+
+    it("runs every call when the dispatch budget is exceeded", async () => {
+        configure({ dispatchBudget: 1 });
+
+        const sleeps = Array.from({ length: 5 }, () => sleep(2_000));
+
+        await expect(Promise.all(sleeps)).resolves.toHaveLength(5);
+    });
+
+    it("lets other sources run between high priority calls when the dispatch budget is exceeded", async () => {
+        const timeouts: number[] = [];
+        const source = recordTimeouts(timeouts);
+        configure({ dispatchBudget: 1 });
+
+        const calls = Array.from({ length: 5 }, () => [
+            monotonicTime({ priority: "high" }),
+            sleep(2_000, { priority: "high" }),
+        ]).flat();
+        const times = (await Promise.all(calls)).filter((time) => typeof time === "number");
+        call(GLIB_LIB, "g_source_destroy", [{ type: GSOURCE, value: source }], UNDEFINED);
+
+        const [first, last] = [times[0] as number, times.at(-1) as number];
+        expect(timeouts.some((time) => time > first && time < last)).toBe(true);
+    });
+
+    it("runs new high priority calls ahead of default ones after the dispatch budget is exceeded", async () => {
+        configure({ dispatchBudget: 1 });
+        const order: string[] = [];
+
+        // The first call exceeds the budget, deferring the one behind it, and the GTK thread then stays busy with
+        // the default priority sleep while the JavaScript thread is blocked.
+        const busy = [sleep(50_000, { priority: "high" })];
+        busy.push(userName({ priority: "high" }).then(() => order.push("deferred high")));
+        busy.push(sleep(100_000, { priority: "default" }));
+        Atomics.wait(new Int32Array(new SharedArrayBuffer(4)), 0, 0, 90);
+
+        const calls = [
+            userName({ priority: "default" }).then(() => order.push("default")),
+            userName({ priority: "high" }).then(() => order.push("high")),
+        ];
+        await Promise.all([...busy, ...calls]);
+
+        expect(order).toEqual(["deferred high", "high", "default"]);
+    });
+});
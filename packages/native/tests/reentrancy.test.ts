@@ -0,0 +1,93 @@
+import { describe, expect, it, vi } from "vitest";
+import { alloc, callAsync, define, getObjectId, read, write } from "../index.js";
+import {
+    connectSignal,
+    createLabel,
+    FLOAT32,
+    GDK_LIB,
+    GLIB_LIB,
+    GOBJECT,
+    GTK_LIB,
+    STRING,
+    UNDEFINED,
+} from "./utils.js";
+
+/**
+ * Runs an operation while the GTK thread fires a `notify` signal whose handler
+ * has to run on the JS thread, and checks that the handler ran.
+ */
+async function expectSignalDuring(operation: () => unknown): Promise<void> {
+    const label = createLabel("before");
+    const handler = vi.fn();
+    connectSignal(label, "notify::label", handler);
+
+    const setLabel = callAsync(
+        GTK_LIB,
+        "gtk_label_set_label",
+        [
+            { type: GOBJECT, value: label },
+            { type: STRING, value: "after" },
+        ],
+        UNDEFINED,
+    );
+
+    operation();
+    await setLabel;
+
+    expect(handler).toHaveBeenCalledTimes(1);
+}
+
+describe("re-entrancy of synchronous exports", () => {
+    it("runs signal handlers fired during a read", async () => {
+        const rgba = alloc(16, "GdkRGBA", GDK_LIB);
+
+        await expectSignalDuring(() => read(rgba, FLOAT32, 0));
+    });
+
+    it("runs signal handlers fired during a write", async () => {
+        const rgba = alloc(16, "GdkRGBA", GDK_LIB);
+
+        await expectSignalDuring(() => write(rgba, FLOAT32, 0, 0.5));
+        expect(read(rgba, FLOAT32, 0)).toBe(0.5);
+    });
+
+    it("runs signal handlers fired during an alloc", async () => {
+        await expectSignalDuring(() => alloc(16, "GdkRGBA", GDK_LIB));
+    });
+
+    it("runs signal handlers fired during getObjectId", async () => {
+        const label = createLabel();
+
+        await expectSignalDuring(() => getObjectId(label));
+    });
+
+    it("runs signal handlers fired during a define", async () => {
+        await expectSignalDuring(() => define(GLIB_LIB, "g_ascii_strup", [{ type: STRING }], STRING));
+    });
+
+    it("runs signal handlers that call back into the exports", async () => {
+        const label = createLabel("before");
+        const rgba = alloc(16, "GdkRGBA", GDK_LIB);
+        let value: unknown;
+
+        connectSignal(label, "notify::label", () => {
+            write(rgba, FLOAT32, 4, 0.25);
+            value = read(rgba, FLOAT32, 4);
+        });
+
+        const setLabel = callAsync(
+            GTK_LIB,
+            "gtk_label_set_label",
+            [
+                { type: GOBJECT, value: label },
+                { type: STRING, value: "after" },
+            ],
+            UNDEFINED,
+        );
+
+        getObjectId(rgba);
+        await setLabel;
+
+        expect(value).toBe(0.25);
+    });
+});
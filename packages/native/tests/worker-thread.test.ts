@@ -0,0 +1,147 @@
+import { writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { describe, expect, it } from "vitest";
+import { call, callAsync, type Ref } from "../index.js";
+import {
+    BOOLEAN,
+    createCancellable,
+    createLabel,
+    createRef,
+    GIO_LIB,
+    GLIB_LIB,
+    GOBJECT,
+    GOBJECT_LIB,
+    INT32,
+    INT64,
+    STRING,
+    UINT64,
+    UNDEFINED,
+} from "./utils.js";
+
+const WORKER = { thread: "worker" as const };
+const G_CHECKSUM_SHA256 = 2;
+const G_FILE_ERROR_NOENT = 4;
+
+function getContents(path: string, contents: Ref<string | null>, length: Ref<number>): unknown {
+    return call(
+        GLIB_LIB,
+        "g_file_get_contents",
+        [
+            { type: STRING, value: path },
+            { type: { type: "ref", innerType: STRING }, value: contents },
+            { type: { type: "ref", innerType: UINT64 }, value: length },
+            { type: { type: "error" }, value: null },
+        ],
+        BOOLEAN,
+        WORKER,
+    );
+}
+
+function newFile(path: string): unknown {
+    return call(GIO_LIB, "g_file_new_for_path", [{ type: STRING, value: path }], GOBJECT);
+}
+
+describe("call - worker thread", () => {
+    it("calls functions on a worker thread", () => {
+        const checksum = call(
+            GLIB_LIB,
+            "g_compute_checksum_for_string",
+            [
+                { type: INT32, value: G_CHECKSUM_SHA256 },
+                { type: STRING, value: "abc" },
+                { type: INT64, value: -1 },
+            ],
+            STRING,
+            WORKER,
+        );
+
+        expect(checksum).toBe("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    });
+
+    it("updates refs and reports errors", () => {
+        const path = join(tmpdir(), "gtkx-worker-thread.txt");
+        writeFileSync(path, "contents");
+
+        const contents = createRef<string | null>(null);
+        const length = createRef(0);
+
+        expect(getContents(path, contents, length)).toBe(true);
+        expect(contents.value).toBe("contents");
+        expect(length.value).toBe(8);
+
+        expect(() => getContents("/nonexistent/gtkx/worker.txt", createRef(null), createRef(0))).toThrow(
+            expect.objectContaining({ domain: "g-file-error-quark", code: G_FILE_ERROR_NOENT }),
+        );
+    });
+
+    it("does not block calls on the GTK thread", async () => {
+        const sleeping = callAsync(GLIB_LIB, "g_usleep", [{ type: UINT64, value: 300_000 }], UNDEFINED, WORKER);
+
+        const start = performance.now();
+        const label = createLabel("responsive");
+        const elapsed = performance.now() - start;
+
+        expect(label).toBeDefined();
+        expect(elapsed).toBeLessThan(200);
+        await sleeping;
+    });
+
+    it("accepts thread-safe objects and returns new objects", async () => {
+        const file = newFile("/tmp/gtkx-worker/child.txt");
+
+        const basename = call(GIO_LIB, "g_file_get_basename", [{ type: GOBJECT, value: file }], STRING, WORKER);
+        const parent = await callAsync(GIO_LIB, "g_file_get_parent", [{ type: GOBJECT, value: file }], GOBJECT, WORKER);
+        const parentPath = call(GIO_LIB, "g_file_get_path", [{ type: GOBJECT, value: parent }], STRING);
+
+        expect(basename).toBe("child.txt");
+        expect(parentPath).toBe("/tmp/gtkx-worker");
+    });
+
+    it("accepts a cancellable", () => {
+        const cancellable = createCancellable();
+
+        const cancelled = call(
+            GIO_LIB,
+            "g_cancellable_is_cancelled",
+            [{ type: GOBJECT, value: cancellable }],
+            BOOLEAN,
+            WORKER,
+        );
+
+        expect(cancelled).toBe(false);
+    });
+
+    it("rejects objects that are not thread-safe", async () => {
+        const label = createLabel("not thread-safe");
+
+        expect(() =>
+            call(GOBJECT_LIB, "g_object_is_floating", [{ type: GOBJECT, value: label }], BOOLEAN, WORKER),
+        ).toThrow(/GtkLabel cannot be passed to a call on a worker thread/);
+
+        await expect(
+            callAsync(GOBJECT_LIB, "g_object_is_floating", [{ type: GOBJECT, value: label }], BOOLEAN, WORKER),
+        ).rejects.toThrow(/GtkLabel cannot be passed to a call on a worker thread/);
+    });
+
+    it("rejects callbacks", () => {
+        expect(() =>
+            call(
+                GLIB_LIB,
+                "g_idle_add_full",
+                [
+                    { type: INT32, value: 0 },
+                    { type: { type: "callback", trampoline: "closure" }, value: () => false },
+                ],
+                UINT64,
+                WORKER,
+            ),
+        ).toThrow(TypeError);
+    });
+
+    it("rejects unknown threads", () => {
+        expect(() =>
+            call(GLIB_LIB, "g_get_user_name", [], STRING, { thread: "render" } as unknown as typeof WORKER),
+        ).toThrow(TypeError);
+    });
+});
@@ -1,5 +1,8 @@
-/** Integer type descriptor for FFI calls. */
-type IntegerType = { type: "int"; size: 8 | 16 | 32 | 64; unsigned?: boolean };
+/**
+ * Integer type descriptor for FFI calls. Values are returned as numbers, which are only exact up to 2^53; with
+ * `bigint`, they are returned as `BigInt`s instead. `BigInt` values are accepted either way.
+ */
+type IntegerType = { type: "int"; size: 8 | 16 | 32 | 64; unsigned?: boolean; bigint?: boolean };
 
 /** Floating-point type descriptor for FFI calls. */
 type FloatType = { type: "float"; size: 32 | 64 };
@@ -16,12 +19,73 @@ type GObjectType = { type: "gobject"; borrowed?: boolean };
 /** Boxed type descriptor for FFI calls. */
 type BoxedType = { type: "boxed"; borrowed?: boolean; innerType: string; lib?: string; getTypeFn?: string };
 
-/** Array type descriptor for FFI calls. Supports regular arrays and GList/GSList. */
-type ArrayType = { type: "array"; itemType: Type; listType?: "glist" | "gslist"; borrowed?: boolean };
+/**
+ * GEnum type descriptor. Values are validated against the enum's GType and may be given as numbers, nicks
+ * (e.g. "vertical") or names (e.g. "GTK_ORIENTATION_VERTICAL"). With `nick`, values are returned as nicks.
+ */
+type EnumType = { type: "enum"; innerType: string; lib?: string; getTypeFn?: string; nick?: boolean };
+
+/**
+ * GFlags type descriptor. Values may be given as numbers, nicks or names, "|"-separated lists of them, or arrays.
+ * Bits the flags type does not define are rejected. With `nick`, values are returned as arrays of nicks.
+ */
+type FlagsType = { type: "flags"; innerType: string; lib?: string; getTypeFn?: string; nick?: boolean };
+
+/**
+ * Array type descriptor for FFI calls. Supports regular arrays, GList/GSList, GPtrArray, GArray and GByteArray.
+ * C arrays that are not null-terminated set either a fixed `length`, or a `lengthArg`: the index of the argument
+ * holding the length (an integer or a ref to one), or "return" when the return value is the length.
+ */
+type ArrayType = {
+    type: "array";
+    itemType: Type;
+    listType?: "glist" | "gslist" | "gptrarray" | "garray" | "gbytearray";
+    borrowed?: boolean;
+    length?: number;
+    lengthArg?: number | "return";
+};
+
+/** GHashTable type descriptor. Converts to and from a JS Map; plain objects are accepted for string keys. */
+type HashTableType = { type: "hashtable"; keyType: Type; valueType: Type; borrowed?: boolean };
+
+/**
+ * GVariant type descriptor. The optional GVariant type string (e.g. "a{sv}") guides the conversion of JS values;
+ * without it, the variant type is inferred. Variants are returned as structured JS values.
+ */
+type VariantType = { type: "variant"; signature?: string; borrowed?: boolean };
+
+/**
+ * Binary data descriptor. Accepts a `Buffer`, `Uint8Array` or `ArrayBuffer`, passed as a `GBytes*` or, with
+ * `kind: "pointer"`, a `guint8*` sized by `length` or `lengthArg` like sized arrays. Returned data is copied into a
+ * new `Buffer`.
+ */
+type BytesType = {
+    type: "bytes";
+    kind?: "gbytes" | "pointer";
+    borrowed?: boolean;
+    length?: number;
+    lengthArg?: number | "return";
+};
+
+/**
+ * GError out-parameter descriptor. The call allocates the `GError**` itself; if the function reports an error,
+ * the call throws an `Error` named "NativeError" with `domain` (the quark string), `quark`, `code` and `message`.
+ */
+type ErrorType = { type: "error" };
+
+/**
+ * GCancellable descriptor. Accepts an `AbortSignal`, or null; a `GCancellable` is created for the call and cancelled
+ * when the signal aborts, so `*_async` operations can be cancelled from JavaScript. An already aborted signal starts
+ * the operation cancelled.
+ */
+type CancellableType = { type: "cancellable" };
 
 /** Reference type descriptor for out/inout parameters. */
 type RefType = { type: "ref"; innerType: Type };
 
+/** Struct type descriptor for C structs passed or returned by value. Fields are laid out in order with C alignment. */
+type StructType = { type: "struct"; fields: { name: string; type: Type }[] };
+
 /** Null type descriptor for FFI calls. */
 type NullType = { type: "null" };
 
@@ -37,6 +101,14 @@ type UndefinedType = { type: "undefined" };
  * - "sourceFunc": Use source func trampoline (GSourceFunc)
  * - "drawFunc": Use draw func trampoline (GtkDrawingAreaDrawFunc)
  * - "compareDataFunc": Use compare data func trampoline (GCompareDataFunc)
+ *
+ * With `async`, a closure is queued to JavaScript and the emission continues without waiting for it to run.
+ * Only callbacks that return nothing, such as handlers of `clicked` or `notify`, may be async.
+ *
+ * `coalesce` makes a callback async and merges consecutive queued emissions for high-frequency signals: with
+ * "latest", a new emission replaces the queued one; with "batch", the callback receives a single array holding the
+ * argument arrays of all merged emissions. Emissions are only merged into the last queued callback, so they keep their
+ * order relative to other callbacks.
  */
 type CallbackType = {
     type: "callback";
@@ -45,6 +117,8 @@ type CallbackType = {
     sourceType?: Type;
     resultType?: Type;
     returnType?: Type;
+    async?: boolean;
+    coalesce?: "latest" | "batch";
 };
 
 /**
@@ -58,8 +132,16 @@ export type Type =
     | StringType
     | GObjectType
     | BoxedType
+    | EnumType
+    | FlagsType
     | ArrayType
+    | HashTableType
     | RefType
+    | StructType
+    | VariantType
+    | BytesType
+    | ErrorType
+    | CancellableType
     | CallbackType
     | NullType
     | UndefinedType;
@@ -69,6 +151,11 @@ export type Type =
  */
 export type Arg = { type: Type; value: unknown; optional?: boolean };
 
+/**
+ * Argument type of a function defined with `define`.
+ */
+export type ArgType = { type: Type; optional?: boolean };
+
 /**
  * Reference wrapper for out/inout parameters in FFI calls.
  * The value property will be mutated by the native function.
@@ -3,6 +3,9 @@ import { defineConfig } from "vitest/config";
 export default defineConfig({
     test: {
         include: ["tests/**/*.test.ts"],
+        benchmark: {
+            include: ["tests/bench/**/*.bench.ts"],
+        },
         typecheck: {
             tsconfig: "tsconfig.test.json",
         },